@@ -8,9 +8,9 @@ use std::{env, fs, path::PathBuf};
 /// Change this one constant to relocate all SQL files
 const SQL_BASE_PATH: &str = "src/bookdb/service/db/data";
 
-/// You can also make this configurable via environment variable:
-/// const SQL_BASE_PATH: &str = env!("BOOKDB_SQL_PATH", "src/bookdb/service/db/data");
-/// Then set BOOKDB_SQL_PATH=src/sql in your environment to relocate
+// You can also make this configurable via environment variable:
+// const SQL_BASE_PATH: &str = env!("BOOKDB_SQL_PATH", "src/bookdb/service/db/data");
+// Then set BOOKDB_SQL_PATH=src/sql in your environment to relocate
 
 // ============================================================================
 // BUILD FUNCTIONS
@@ -26,7 +26,7 @@ pub fn build_const_from_dir(src_dir: &PathBuf, out_path: &PathBuf, sql_version:
         sql_version
     ));
 
-    let entries = fs::read_dir(&src_dir)?;
+    let entries = fs::read_dir(src_dir)?;
     let mut file_count = 0;
 
     for entry in entries {
@@ -43,9 +43,10 @@ pub fn build_const_from_dir(src_dir: &PathBuf, out_path: &PathBuf, sql_version:
             // Use configurable base path instead of hardcoded path
             let include_path = format!("{}/{}/{}", base_path, sql_version, file_name);
 
-            // emit: pub const RESOLVE_PROJECT_ID: &str = include_str!("src/bookdb/service/db/data/sqlv2/resolve_project_id.sql");
+            // emit: pub const RESOLVE_PROJECT_ID: &str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/.../resolve_project_id.sql"));
+            // (include_str! is relative to the including file, which lives in OUT_DIR)
             buf.push_str(&format!(
-                "pub const {}: &str = include_str!(\"{}\");\n",
+                "pub const {}: &str = include_str!(concat!(env!(\"CARGO_MANIFEST_DIR\"), \"/{}\"));\n",
                 const_name, include_path
             ));
             
@@ -53,7 +54,7 @@ pub fn build_const_from_dir(src_dir: &PathBuf, out_path: &PathBuf, sql_version:
         }
     }
 
-    fs::write(&out_path, buf)?;
+    fs::write(out_path, buf)?;
     println!("cargo:warning=Generated {} SQL constants from {}", file_count, src_dir.display());
     
     Ok(())
@@ -82,7 +83,7 @@ fn setup_rebuild_triggers(sql_dir: &PathBuf, _sql_version: &str) -> Result<(), B
         println!("cargo:rerun-if-changed={}", sql_dir.display());
         
         // Watch individual SQL files for more precise rebuilds
-        for entry in fs::read_dir(&sql_dir)? {
+        for entry in fs::read_dir(sql_dir)? {
             let path = entry?.path();
             if path.extension().and_then(|s| s.to_str()) == Some("sql") {
                 println!("cargo:rerun-if-changed={}", path.display());
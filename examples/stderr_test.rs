@@ -46,7 +46,7 @@ fn main() {
     logger.trace_fn("test_function", "testing trace_fn method");
     
     // Formatting (should work with formatting feature)
-    if let Ok(_) = logger.banner("Test Banner", '=') {
+    if logger.banner("Test Banner", '=').is_ok() {
         println!("Banner method works!");
     }
     
@@ -150,14 +150,14 @@ fn test_bookdb_interactive_patterns() {
     let mut logger = Stderr::new();
     
     // BookDB uses banners for section headers
-    if let Ok(_) = logger.banner("Context Chain Validation", '=') {
+    if logger.banner("Context Chain Validation", '=').is_ok() {
         println!("✅ Banner works");
     } else {
         println!("❌ Banner failed");
     }
     
     // BookDB uses boxes for important messages
-    if let Ok(_) = logger.box_light("Critical: Database schema migration required") {
+    if logger.box_light("Critical: Database schema migration required").is_ok() {
         println!("✅ Box light works");
     } else {
         println!("❌ Box light failed");
@@ -233,7 +233,7 @@ fn test_context_validation_scenario() {
     let mut logger = Stderr::new();
     
     // Simulate V3 context validation workflow
-    if let Ok(_) = logger.banner("Context Chain Validation", '-') {
+    if logger.banner("Context Chain Validation", '-').is_ok() {
         let input = "@work@webapp.config.var.secrets";
         logger.trace_fn("v3_validator", &format!("validating input: '{}'", input));
         
@@ -275,7 +275,7 @@ fn test_import_operation_scenario() {
     let file_path = "config.env";
     let variable_count = 23;
     
-    if let Ok(_) = logger.banner("Import Operation", '=') {
+    if logger.banner("Import Operation", '=').is_ok() {
         logger.info(&format!("Starting import from: {}", file_path));
         
         logger.trace_fn("import", "parsing file format");
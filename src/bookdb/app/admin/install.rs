@@ -3,6 +3,7 @@
 // CRITICAL: Blocks all BookDB usage until 'bookdb install' is run
 // Prevents data corruption and ensures proper initialization
 
+use rusqlite::OptionalExtension;
 use stderr::{Stderr, StderrConfig};
 use std::path::Path;
 
@@ -26,7 +27,7 @@ impl InstallGuard {
     
     /// CRITICAL: Call this before any BookDB operations
     /// Blocks execution if installation is incomplete
-    pub fn require_installation(&mut self) ->  Result<(), E> {
+    pub fn require_installation(&mut self) -> Result<()> {
         self.logger.trace_fn("installation_guard", "checking installation status");
         
         // Check if home base exists and is properly initialized
@@ -59,7 +60,7 @@ impl InstallGuard {
     }
     
     /// Check if database has proper installation metadata
-    fn check_installation_meta(&mut self, db_path: &Path) -> Result<bool, E> {
+    fn check_installation_meta(&mut self, db_path: &Path) -> Result<bool> {
         use rusqlite::Connection;
         
         let conn = Connection::open(db_path)?;
@@ -84,7 +85,7 @@ impl InstallGuard {
     }
     
     /// Show user-friendly installation guidance
-    fn show_installation_required(&mut self) ->  Result<(), E> {
+    fn show_installation_required(&mut self) -> Result<()> {
         self.logger.banner("BookDB Not Installed", '!')?;
         self.logger.error("BookDB has not been installed on this system.");
         self.logger.info("");
@@ -103,7 +104,7 @@ impl InstallGuard {
     }
     
     /// Show guidance for incomplete installation
-    fn show_installation_incomplete(&mut self) ->  Result<(), E> {
+    fn show_installation_incomplete(&mut self) -> Result<()> {
         self.logger.banner("Incomplete Installation", '!')?;
         self.logger.warn("BookDB installation was started but not completed properly.");
         self.logger.info("");
@@ -129,7 +130,7 @@ impl InstallationManager {
     }
     
     /// Execute the installation process
-    pub fn install(&mut self) ->  Result<(), E> {
+    pub fn install(&mut self) -> Result<()> {
         self.logger.banner("BookDB Installation", '=')?;
         
         // Check if already installed
@@ -171,14 +172,14 @@ impl InstallationManager {
     }
     
     /// Check if BookDB is already properly installed
-    fn is_already_installed(&mut self) -> Result<bool, E> {
+    fn is_already_installed(&mut self) -> Result<bool> {
         let home_db_path = self.config.get_base_path("home");
         
         if !home_db_path.exists() {
             return Ok(false);
         }
         
-        let guard = InstallGuard::new(self.config.clone());
+        let mut guard = InstallGuard::new(self.config.clone());
         match guard.check_installation_meta(&home_db_path) {
             Ok(installed) => Ok(installed),
             Err(_) => Ok(false), // Treat errors as not installed
@@ -186,7 +187,7 @@ impl InstallationManager {
     }
     
     /// Perform the actual installation
-    fn perform_installation(&mut self) ->  Result<(), E> {
+    fn perform_installation(&mut self) -> Result<()> {
         self.logger.trace_fn("installation", "starting installation process");
         
         // Step 1: Create directory structure
@@ -207,7 +208,7 @@ impl InstallationManager {
     }
     
     /// Create necessary directory structure
-    fn create_directory_structure(&mut self) ->  Result<(), E> {
+    fn create_directory_structure(&mut self) -> Result<()> {
         self.logger.trace_fn("installation", "creating directory structure");
         
         let data_dir = self.config.xdg.data_dir();
@@ -226,28 +227,28 @@ impl InstallationManager {
     }
     
     /// Create the invincible superchain: ROOT.GLOBAL.VAR.MAIN
-    fn create_invincible_superchain(&mut self, database: &mut Database) ->  Result<(), E> {
+    fn create_invincible_superchain(&mut self, database: &mut Database) -> Result<()> {
         self.logger.trace_fn("installation", "creating invincible superchain");
         
         // Create the ROOT.GLOBAL.VAR.MAIN context
-        let superchain = DefaultResolver::create_invincible_superchain("home");
+        let chain = DefaultResolver::create_invincible_superchain("home");
+        let superchain = DefaultResolver::new().resolve_cdcc(&chain, &Default::default());
         
-        // Ensure all necessary namespaces exist
+        // Ensure all necessary namespaces exist; setting the markers creates MAIN
         database.ensure_project_exists("ROOT")?;
         database.ensure_workspace_exists("ROOT", "GLOBAL")?;
-        database.ensure_varstore_exists("ROOT", "GLOBAL", "MAIN")?;
         
         // Set a special marker in the invincible superchain
-        database.set_variable_in_context("_INVINCIBLE", "1", &superchain)?;
-        database.set_variable_in_context("_INSTALLED", "1", &superchain)?;
-        database.set_variable_in_context("_VERSION", env!("CARGO_PKG_VERSION"), &superchain)?;
+        database.set_variable("_INVINCIBLE", "1", &superchain)?;
+        database.set_variable("_INSTALLED", "1", &superchain)?;
+        database.set_variable("_VERSION", env!("CARGO_PKG_VERSION"), &superchain)?;
         
         self.logger.trace_fn("installation", "invincible superchain created");
         Ok(())
     }
     
     /// Mark installation as complete in meta table
-    fn mark_installation_complete(&mut self, database: &mut Database) ->  Result<(), E> {
+    fn mark_installation_complete(&mut self, database: &mut Database) -> Result<()> {
         self.logger.trace_fn("installation", "marking installation complete");
         
         // Create meta table if not exists
@@ -281,7 +282,7 @@ impl InstallationManager {
 }
 
 /// Integration for main.rs to check installation before any operations
-pub fn require_installation_or_install(config: &Config, is_install_command: bool) ->  Result<(), E> {
+pub fn require_installation_or_install(config: &Config, is_install_command: bool) -> Result<()> {
     if is_install_command {
         // Allow install command to proceed
         return Ok(());
@@ -296,11 +297,11 @@ mod tests {
     use super::*;
     use tempfile::TempDir;
     use std::path::PathBuf;
+    use crate::bookdb::app::sup::config::XdgDirs;
     
     fn create_test_config() -> (Config, TempDir) {
         let temp_dir = TempDir::new().unwrap();
-        let mut config = Config::default();
-        config.xdg = XdgDirs::new(temp_dir.path()).unwrap();
+        let config = Config { xdg: XdgDirs::new(temp_dir.path()).unwrap() };
         (config, temp_dir)
     }
     
@@ -314,7 +315,7 @@ mod tests {
     }
     
     #[test]
-    fn test_installation_process() ->  Result<(), E> {
+    fn test_installation_process() -> Result<()> {
         let (config, _temp) = create_test_config();
         let mut manager = InstallationManager::new(config.clone());
         
@@ -329,7 +330,7 @@ mod tests {
     }
     
     #[test]
-    fn test_invincible_superchain_creation() ->  Result<(), E> {
+    fn test_invincible_superchain_creation() -> Result<()> {
         let (config, _temp) = create_test_config();
         let home_db_path = config.get_base_path("home");
         
@@ -339,8 +340,9 @@ mod tests {
         manager.create_invincible_superchain(&mut database)?;
         
         // Verify superchain exists and has marker
-        let superchain = DefaultResolver::create_invincible_superchain("home");
-        let value = database.get_variable_in_context("_INVINCIBLE", &superchain)?;
+        let chain = DefaultResolver::create_invincible_superchain("home");
+        let superchain = DefaultResolver::new().resolve_cdcc(&chain, &Default::default());
+        let value = database.get_variable("_INVINCIBLE", &superchain)?;
         assert_eq!(value, Some("1".to_string()));
         
         Ok(())
@@ -1,6 +1,6 @@
 // src/cli.rs - Root CLI module (ODX-based from Session 18)
 
-use clap::{Parser, Subcommand, ValueEnum};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 
 #[derive(Parser)]
 #[command(name = "bookdb")]
@@ -64,14 +64,38 @@ pub enum Commands {
         /// Context chain override
         #[arg(short, long)]
         context: Option<String>,
+        /// Read the value as of a revision, @<unix-seconds> or YYYY-MM-DD[THH:MM[:SS]] (UTC)
+        #[arg(long)]
+        at: Option<String>,
+        /// Print the value as native JSON (5, true, {"a":1}, "text")
+        #[arg(long)]
+        typed: bool,
+        /// Print {"key", "value", "type"} with the value as native JSON
+        #[arg(long)]
+        json: bool,
     },
     /// Set a variable value
     Setv {
-        /// Key=value pair
+        /// Key=value pair; `KEY:type=value` sets the type inline
         key_value: String,
         /// Context chain override
         #[arg(short, long)]
         context: Option<String>,
+        /// Value type: str, int, float, bool, json or duration (validated on write)
+        #[arg(long = "type")]
+        var_type: Option<String>,
+        /// Only write if the current value equals this one (exit 3 otherwise)
+        #[arg(long, value_name = "OLD", conflicts_with_all = ["if_absent", "if_present"])]
+        if_equals: Option<String>,
+        /// Only write if the key does not exist yet (exit 3 otherwise)
+        #[arg(long, conflicts_with = "if_present")]
+        if_absent: bool,
+        /// Only write if the key already exists (exit 3 otherwise)
+        #[arg(long)]
+        if_present: bool,
+        /// Expire the value after this long (`90s`, `15m`, `1h`, `7d`)
+        #[arg(long)]
+        ttl: Option<String>,
     },
     /// Delete a variable
     Delv {
@@ -81,25 +105,121 @@ pub enum Commands {
         #[arg(short, long)]
         context: Option<String>,
     },
+    /// Show the change history of a variable
+    History {
+        /// Variable key
+        key: String,
+        /// Context chain override
+        #[arg(short, long)]
+        context: Option<String>,
+        /// Show sensitive values instead of masking them
+        #[arg(long)]
+        reveal: bool,
+    },
+    /// Set a variable back to its value as of a revision
+    Revert {
+        /// Variable key
+        key: String,
+        /// Revision to go back to (see `history`)
+        #[arg(long)]
+        to: i64,
+        /// Context chain override
+        #[arg(short, long)]
+        context: Option<String>,
+    },
+    /// Undo the last N mutating commands (setv, delv, inc, dec, revert, setd, import, cp)
+    Undo {
+        /// Number of commands to undo
+        #[arg(default_value_t = 1)]
+        count: usize,
+    },
+    /// List recently journaled commands, newest first
+    Journal {
+        /// Number of entries to show
+        #[arg(short = 'n', long, default_value_t = 20)]
+        limit: usize,
+    },
+    /// Delete expired variables (`setv --ttl`) from the base
+    Purge {},
+    /// Show the audit log: who changed what, when, with which command (values as hashes)
+    Audit {
+        /// Only entries since then: a duration back from now (24h, 7d), @<unix-seconds> or a UTC date
+        #[arg(long)]
+        since: Option<String>,
+        /// Only entries on this chain or below it (e.g. infra.production)
+        #[arg(long)]
+        chain: Option<String>,
+        /// Print one JSON object per line
+        #[arg(long)]
+        json: bool,
+    },
+    /// Encrypt a keystore with a passphrase (prompted, or BOOKDB_PASSPHRASE)
+    Encrypt {
+        /// Context chain override
+        #[arg(short, long)]
+        context: Option<String>,
+    },
+    /// Store an encrypted keystore in plaintext again
+    Decrypt {
+        /// Context chain override
+        #[arg(short, long)]
+        context: Option<String>,
+    },
+    /// Lock a keystore (or a workspace's doc store) against writes without --force
+    Lock {
+        /// Chain to lock (e.g. @infra.production.var.credentials; default: cursor)
+        chain: Option<String>,
+        /// Make the whole base read-only instead (--force does not get through)
+        #[arg(long, conflicts_with = "chain")]
+        base: bool,
+    },
+    /// Unlock a keystore or doc store, or with --base make the base writable again
+    Unlock {
+        /// Chain to unlock (default: cursor)
+        chain: Option<String>,
+        /// Lift the base's read-only mode instead
+        #[arg(long, conflicts_with = "chain")]
+        base: bool,
+    },
+    /// Mark a key (or, without KEY, the whole keystore) as sensitive: masked in ls, previews and traces
+    Sensitive {
+        /// Variable key (omit to mark the keystore)
+        key: Option<String>,
+        /// Remove the mark instead
+        #[arg(long)]
+        off: bool,
+        /// Context chain override
+        #[arg(short, long)]
+        context: Option<String>,
+    },
     /// Increment a numeric variable
     Inc {
         /// Variable key
         key: String,
         /// Amount to increment (default: 1)
-        amount: Option<i64>,
+        amount: Option<String>,
         /// Context chain override
         #[arg(short, long)]
         context: Option<String>,
+        #[command(flatten)]
+        counter: CounterArgs,
     },
     /// Decrement a numeric variable
     Dec {
         /// Variable key
         key: String,
         /// Amount to decrement (default: 1)
-        amount: Option<i64>,
+        amount: Option<String>,
         /// Context chain override
         #[arg(short, long)]
         context: Option<String>,
+        #[command(flatten)]
+        counter: CounterArgs,
+    },
+    /// Counter maintenance
+    Counter {
+        #[command(subcommand)]
+        action: CounterAction,
     },
     /// List data (projects, keys, etc.)
     Ls {
@@ -108,18 +228,24 @@ pub enum Commands {
         /// Context chain override
         #[arg(short, long)]
         context: Option<String>,
+        /// List across every base in the data dir (same as a `*@` chain)
+        #[arg(long)]
+        all_bases: bool,
+        /// Show sensitive values instead of masking them
+        #[arg(long)]
+        reveal: bool,
     },
-    /// Get a document
+    /// Get a document or one of its segments
     Getd {
-        /// Document key
-        dik: String,
+        /// Document path, doc_key[/segment/path] (defaults to the doc chain tail)
+        dik: Option<String>,
         /// Context chain override
         #[arg(short, long)]
         context: Option<String>,
     },
-    /// Set a document
+    /// Set a document or one of its segments
     Setd {
-        /// Document key=value pair
+        /// Document path=content pair (`=content` writes to the doc chain tail)
         dik_value: String,
         /// Context chain override
         #[arg(short, long)]
@@ -150,6 +276,12 @@ pub enum Commands {
         /// Segment filter
         #[arg(long)]
         seg: Option<String>,
+        /// Context chain override (`*@...` exports from every base)
+        #[arg(short, long)]
+        context: Option<String>,
+        /// Show sensitive values in the `--dry-run` preview
+        #[arg(long)]
+        reveal: bool,
     },
     /// Import data from file
     Import {
@@ -176,27 +308,218 @@ pub enum Commands {
     },
     /// Change active context
     Use {
-        /// New context string
+        /// New context string, a relative form (`..`, `.workspace`, `:keystore`, `~`), or `-` for the previous context
         context_str: String,
     },
+    /// Save the current context on the cursor stack and switch to a new one
+    Push {
+        /// Context chain to switch to
+        chain: String,
+    },
+    /// Return to the context saved by the last `push`
+    Pop {},
     /// Install BookDB
     Install {},
     /// Show current cursor/context
-    Cursor {},
+    Cursor {
+        /// Show previous contexts and the push/pop stack
+        #[arg(long)]
+        history: bool,
+    },
     /// Show current status
     Status {},
     /// Show current base
     Base {},
+    /// Create a new namespace
+    New {
+        /// What to create
+        target: NewTarget,
+        /// Name of the new item
+        name: String,
+        /// Context chain override (names are relative to it)
+        #[arg(short, long)]
+        context: Option<String>,
+    },
+    /// Delete a project, workspace, keystore or doc store
+    Del {
+        /// What to delete
+        target: DelTarget,
+        /// Name of the item
+        name: String,
+        /// Context chain override (names are relative to it)
+        #[arg(short, long)]
+        context: Option<String>,
+    },
+    /// Rename or relocate a project, workspace or keystore
+    ///
+    /// `@app.prod.var.secrets` names a keystore, `@app.prod.var.*` a
+    /// workspace and `@app.*.var.*` a project.
+    Mv {
+        /// Chain to move
+        from: String,
+        /// Destination chain, at the same level
+        to: String,
+        /// Combine with an existing destination (moved entries win)
+        #[arg(long)]
+        merge: bool,
+    },
+    /// Copy a keystore, workspace, project or document, also across bases
+    ///
+    /// Levels follow `mv`: `@app.prod.var.*` copies a workspace (keystores and
+    /// documents), `@app.*.var.*` a project. Conflicts fail unless told otherwise.
+    Cp {
+        /// Chain to copy from
+        from: String,
+        /// Destination chain, at the same level
+        to: String,
+        /// Replace existing variables and documents
+        #[arg(long, conflicts_with_all = ["skip", "fail"])]
+        overwrite: bool,
+        /// Keep existing variables and documents
+        #[arg(long, conflicts_with_all = ["overwrite", "fail"])]
+        skip: bool,
+        /// Abort before writing if anything exists (default)
+        #[arg(long, conflicts_with_all = ["overwrite", "skip"])]
+        fail: bool,
+    },
+    /// Select the active base
+    Select {
+        /// Base name
+        base: String,
+    },
+    /// Wipe a base and recreate it empty
+    Rebase {
+        /// Base name
+        base: String,
+    },
+    /// Delete a base file
+    Unbase {
+        /// Base name
+        base: String,
+    },
+    /// Bind an action to a context for `#` chains
+    Bind {
+        /// Context chain to bind (e.g. @deploy.prod.var.app)
+        chain: String,
+        /// Action to run
+        #[arg(value_parser = ["dump", "render", "export"])]
+        action: String,
+        /// Template path (render) or output file (export)
+        arg: Option<String>,
+    },
+    /// Remove the action bound to a context
+    Unbind {
+        /// Context chain to unbind
+        chain: String,
+    },
+    /// Manage chain aliases (`:name` works wherever a chain does)
+    Alias {
+        #[command(subcommand)]
+        action: AliasAction,
+    },
     /// Find a key across projects
     Find {
-        /// Search pattern
+        /// Search pattern (exact key unless --glob/--regex)
         pattern: String,
+        /// Match keys with a glob (`*_KEY`, `DB?`)
+        #[arg(long)]
+        glob: bool,
+        /// Match with a regular expression
+        #[arg(long)]
+        regex: bool,
+        /// Search values instead of keys (substring, or regex with --regex)
+        #[arg(long)]
+        values: bool,
+        /// Machine-readable output
+        #[arg(long)]
+        json: bool,
+        /// Search every base in the data dir
+        #[arg(long)]
+        all_bases: bool,
+    },
+    /// Full-text search over text document segments
+    Grepd {
+        /// FTS5 query (`deploy`, `"exact phrase"`, `data*`, `a AND NOT b`)
+        query: String,
+        /// Only search documents under this chain (project/workspace/doc may be globs)
+        #[arg(short, long)]
+        context: Option<String>,
+        /// Maximum number of hits
+        #[arg(short = 'n', long, default_value_t = 20)]
+        limit: usize,
+        /// Machine-readable output
+        #[arg(long)]
+        json: bool,
+    },
+    /// Run an Action-mode chain, e.g. `bookdb '#deploy.prod.var.app'`
+    #[command(external_subcommand)]
+    Action(Vec<String>),
+}
+
+/// Counter behavior shared by `inc` and `dec`
+#[derive(Args, Clone, Debug)]
+pub struct CounterArgs {
+    /// Count in floats (the amount may be fractional)
+    #[arg(long)]
+    pub float: bool,
+    /// Lower bound; results below it are clamped
+    #[arg(long)]
+    pub min: Option<String>,
+    /// Upper bound; results above it are clamped
+    #[arg(long)]
+    pub max: Option<String>,
+    /// Wrap around min..max instead of clamping
+    #[arg(long, requires_all = ["min", "max"])]
+    pub wrap: bool,
+    /// Starting value for a missing key (default 0)
+    #[arg(long)]
+    pub init: Option<String>,
+    /// Only step if the counter currently holds this value (exit 3 otherwise)
+    #[arg(long)]
+    pub expect: Option<String>,
+}
+
+#[derive(Subcommand)]
+pub enum CounterAction {
+    /// Set a counter back to 0 (or --to), keeping its type
+    Reset {
+        /// Variable key
+        key: String,
+        /// Value to reset to
+        #[arg(long)]
+        to: Option<String>,
+        /// Context chain override
+        #[arg(short, long)]
+        context: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum AliasAction {
+    /// Store a name for a chain, e.g. `alias set prodcreds @work@infra.production.var.credentials`
+    Set {
+        /// Alias name (letters, digits, `_`, `-`)
+        name: String,
+        /// Full context chain the alias expands to
+        chain: String,
+        /// Store in $config_dir instead of the current base
+        #[arg(long)]
+        global: bool,
+    },
+    /// Remove an alias
+    Rm {
+        /// Alias name
+        name: String,
+        /// Remove the global alias instead of the base one
+        #[arg(long)]
+        global: bool,
     },
 }
 
-#[derive(ValueEnum, Clone)]
+#[derive(ValueEnum, Clone, Debug, Default)]
 pub enum LsTarget {
     /// List all available data
+    #[default]
     All,
     /// List projects
     Projects,
@@ -210,10 +533,38 @@ pub enum LsTarget {
     Docs,
     /// List bases
     Bases,
+    /// List action bindings
+    Actions,
+    /// List the segment tree of the current document
+    Segments,
+    /// List chain aliases (base and global)
+    Aliases,
+    /// List locked namespaces
+    Locks,
+}
+
+#[derive(ValueEnum, Clone, Debug)]
+pub enum NewTarget {
+    /// Create a new base
+    Base,
+    /// Create a project
+    Project,
+    /// Create a workspace (with a MAIN keystore) in the current project
+    Workspace,
+    /// Create a keystore in the current workspace
+    Keystore,
+    /// Create the doc store of a workspace in the current project
+    Docstore,
 }
 
-impl Default for LsTarget {
-    fn default() -> Self {
-        Self::All
-    }
+#[derive(ValueEnum, Clone, Debug)]
+pub enum DelTarget {
+    /// Delete a project and everything in it
+    Project,
+    /// Delete a workspace in the current project
+    Workspace,
+    /// Delete a keystore in the current workspace
+    Keystore,
+    /// Delete the doc store of a workspace in the current project
+    Docstore,
 }
@@ -1,116 +1,287 @@
-// src/main.rs - Complete main entry point with all command handlers
-
-
+// src/bookdb/app/ctrl/dispatch.rs - Parse the command line, resolve the context, route to a handler
 
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser};
+use std::path::PathBuf;
 use stderr::{Stderr, StderrConfig};
 
 //use crate::admin::install::{InstallationManager, require_installation_or_install};
 
 use crate::error::{Result, BookdbError};
 use crate::bookdb::service::db::Database;
-use crate::ctx::{ContextManager, parse_context_chain};
-
-
+use crate::bookdb::service::db::driver::{DatabaseManager, PASSPHRASE_ENV};
+use crate::bookdb::oxidize::BashFxFlags;
+use crate::bookdb::app::sup::config::{Config, resolve_paths};
+use crate::ctx::{ContextManager, CursorState, ResolvedContext};
+use crate::cli;
+use super::handlers::*;
+
+
+
+/// Extract the per-command `--context` override, if the command takes one
+pub fn get_context_from_command(command: &Option<cli::Commands>) -> Option<String> {
+  match command {
+    Some(cli::Commands::Getv { context, .. })
+    | Some(cli::Commands::Setv { context, .. })
+    | Some(cli::Commands::Delv { context, .. })
+    | Some(cli::Commands::History { context, .. })
+    | Some(cli::Commands::Revert { context, .. })
+    | Some(cli::Commands::Inc { context, .. })
+    | Some(cli::Commands::Dec { context, .. })
+    | Some(cli::Commands::Counter { action: cli::CounterAction::Reset { context, .. } })
+    | Some(cli::Commands::Encrypt { context })
+    | Some(cli::Commands::Decrypt { context })
+    | Some(cli::Commands::Sensitive { context, .. })
+    | Some(cli::Commands::Ls { context, .. })
+    | Some(cli::Commands::Export { context, .. })
+    | Some(cli::Commands::Grepd { context, .. })
+    | Some(cli::Commands::New { context, .. })
+    | Some(cli::Commands::Del { context, .. })
+    | Some(cli::Commands::Getd { context, .. })
+    | Some(cli::Commands::Setd { context, .. }) => context.clone(),
+    Some(cli::Commands::Bind { chain, .. })
+    | Some(cli::Commands::Unbind { chain }) => Some(chain.clone()),
+    Some(cli::Commands::Lock { chain, .. })
+    | Some(cli::Commands::Unlock { chain, .. }) => chain.clone(),
+    // `bookdb '#proj.ws.var.store'` arrives as an external subcommand
+    Some(cli::Commands::Action(argv)) => argv.first().cloned(),
+    _ => None,
+  }
+}
 
-pub fn load_global_context( args: &mut Vec<T>, ) -> (){
+/// Commands whose chain is the object of the operation (bind, lock, ...)
+fn is_admin_command(command: &Option<cli::Commands>) -> bool {
+  matches!(
+    command,
+    Some(cli::Commands::Bind { .. })
+      | Some(cli::Commands::Unbind { .. })
+      | Some(cli::Commands::Lock { .. })
+      | Some(cli::Commands::Unlock { .. })
+  )
+}
 
-  // Handle global context if provided
-  if let Some(global_context) = args.context {
-      let chain = parse_context_chain(&global_context, &cursor_state.base_cursor)?;
-      if args.persist {
-          context_manager.update_cursor(&chain, &mut cursor_state)?;
-      }
-      cursor_state = bookdb::context::CursorState {
-          base_cursor: chain.base_name.clone(),
-          context_cursor: chain.clone(),
-      };
+/// Only `#` chains may stand in for a subcommand; anything else is a typo,
+/// rejected the way clap rejects any unknown subcommand, before a context is entered
+fn reject_unknown_subcommand(args: &cli::Cli) {
+  if let Some(cli::Commands::Action(argv)) = &args.command {
+    let name = argv.first().map(String::as_str).unwrap_or_default();
+    if !is_action_chain(name) {
+      cli::Cli::command()
+        .error(ErrorKind::InvalidSubcommand, format!("unrecognized subcommand '{}'", name))
+        .exit();
+    }
   }
+}
 
+/// Whether an external subcommand is an Action-mode chain (`#proj.ws...`)
+fn is_action_chain(name: &str) -> bool {
+  name.starts_with('#')
 }
 
-pub fn open_database<'a>( context_manager: &'a mut ContextManager, 
-                          cursor_state: &'a mut CursorState ) -> &'a Database 
+pub fn open_database( config: &Config, base: &str, is_install: bool ) -> Result<Database>
 {
-  // Open database
-  let database_path = context_manager.get_database_path(&cursor_state.base_cursor);
-  let database = if matches!(args.command, Some(cli::Command::Install {})) {
+  let database_path = config.get_base_path(base);
+  let database = if is_install {
       // Installation - create database if needed
       Database::create_or_open(&database_path)?
   } else {
       // Normal operation - database must exist
       Database::open(&database_path)?
   };
+  
+  unlock_database(&database);
+  Ok(database)
+}
 
-  // error ?
-  database
+/// Encrypted keystores: BOOKDB_PASSPHRASE, else ask when one is first touched
+///
+/// Every base a command opens goes through here, not just the working one.
+pub fn unlock_database(database: &Database) {
+  match std::env::var(PASSPHRASE_ENV) {
+      Ok(passphrase) => database.unlock(&passphrase),
+      Err(_) => database.set_passphrase_prompt(|| rpassword::prompt_password("Passphrase: ")),
+  }
 }
 
-pub fn resolve_context_chain<'a>( args: &'a mut Vec<T>, context_manager: &'a mut ContextManager, 
-                                  cursor_state: &'a mut CursorState ) -> &'a Database 
+pub fn resolve_context_chain( args: &cli::Cli, context_manager: &mut ContextManager,
+                              cursor_state: &mut CursorState ) -> Result<ResolvedContext>
 {
-  // Get context from command or use cursor
-  let context_chain = get_context_from_command(&args.command)
-      .map(|ctx| parse_context_chain(&ctx, &cursor_state.base_cursor))
-      .transpose()?
-      .unwrap_or_else(|| cursor_state.context_cursor.clone());
-  
-  // Resolve context for database operations
-  let resolved_context = context_manager.resolve_context(&context_chain)?;
-
-  resolved_context  
-
+  // Get context from command; the chain's prefix decides whether the cursor moves
+  match get_context_from_command(&args.command) {
+    Some(ctx) => {
+      // Relative forms (`..`, `.ws`, `:keystore`, `~`) expand against the cursor
+      let chain = context_manager.parse_chain(&ctx, cursor_state)?;
+      if is_admin_command(&args.command) {
+        // Admin chains name a target, not a place to go: never move the cursor
+        return Ok(context_manager.resolve_context(&chain, cursor_state));
+      }
+      context_manager.enter_context(&chain, cursor_state)
+    }
+    None => {
+      // No override: resolve the cursor (or the invincible superchain) as-is
+      let current = cursor_state.get_current_context();
+      Ok(context_manager.resolve_context(&current, cursor_state))
+    }
+  }
 }
 
 
 
-pub fn dispatch_router(args: Vec<T>, database: &Database, context_chain: &str) ->  Result<(), E> {
+/// Everything a handler may need, set up once per invocation
+pub struct Session {
+  pub config: Config,
+  pub context_manager: ContextManager,
+  pub cursor_state: CursorState,
+  /// The command's context: its chain override, else the cursor
+  pub context: ResolvedContext,
+  /// Working base: the context's, or the cursor's for `*@` chains
+  pub database: Database,
+  /// Base registry over $data_dir/*.sqlite3 for new/select/rebase/unbase and `*@`
+  pub db_manager: DatabaseManager,
+  /// Safety bypassed (-f, or -y per BashFX): locked namespaces take writes
+  pub force: bool,
+}
+
+pub fn dispatch_router(args: cli::Cli, session: &mut Session) -> Result<()> {
 
   let mut logger = Stderr::new();
 
   // Route commands
   match args.command {
-    Some(cli::Command::Getv { key, .. }) => {
-        handle_getv_command(key, &database, &resolved_context, &mut logger)
+    Some(cli::Commands::Getv { key, at, typed, json, .. }) => {
+        handle_getv_command(key, at, (typed, json), &session.database, &session.db_manager, &session.context, &mut logger)
+    }
+    Some(cli::Commands::Setv { key_value, var_type, if_equals, if_absent, if_present, ttl, .. }) => {
+        handle_setv_command(key_value, var_type, (if_equals, if_absent, if_present), ttl, &session.database, &session.context, &mut logger)
+    }
+    Some(cli::Commands::Delv { key, .. }) => {
+        handle_delv_command(key, &session.database, &session.context, &mut logger)
+    }
+    Some(cli::Commands::History { key, reveal, .. }) => {
+        handle_history_command(key, reveal, &session.database, &session.context, &mut logger)
+    }
+    Some(cli::Commands::Revert { key, to, .. }) => {
+        handle_revert_command(key, to, &session.database, &session.context, &mut logger)
+    }
+    Some(cli::Commands::Undo { count }) => {
+        handle_undo_command(count, &session.database, &mut logger)
+    }
+    Some(cli::Commands::Journal { limit }) => {
+        handle_journal_command(limit, &session.database, &mut logger)
+    }
+    Some(cli::Commands::Audit { since, chain, json }) => {
+        handle_audit_command(since, chain, json, &session.database, &mut logger)
+    }
+    Some(cli::Commands::Purge {}) => {
+        handle_purge_command(args.dry_run, &session.database, &mut logger)
+    }
+    Some(cli::Commands::Encrypt { .. }) => {
+        handle_encrypt_command(&session.database, &session.context, &mut logger)
+    }
+    Some(cli::Commands::Decrypt { .. }) => {
+        handle_decrypt_command(&session.database, &session.context, &mut logger)
     }
-    Some(cli::Command::Setv { key_value, .. }) => {
-        handle_setv_command(key_value, &database, &resolved_context, &mut logger)
+    Some(cli::Commands::Lock { base, .. }) => {
+        handle_lock_command(true, base, &session.database, &session.context, &mut logger)
     }
-    Some(cli::Command::Delv { key, .. }) => {
-        handle_delv_command(key, &database, &resolved_context, &mut logger)
+    Some(cli::Commands::Unlock { base, .. }) => {
+        handle_lock_command(false, base, &session.database, &session.context, &mut logger)
     }
-    Some(cli::Command::Inc { key, amount, .. }) => {
-        handle_inc_command(key, amount, &database, &resolved_context, &mut logger)
+    Some(cli::Commands::Sensitive { key, off, .. }) => {
+        handle_sensitive_command(key, !off, &session.database, &session.context, &mut logger)
     }
-    Some(cli::Command::Dec { key, amount, .. }) => {
-        handle_dec_command(key, amount, &database, &resolved_context, &mut logger)
+    Some(cli::Commands::Inc { key, amount, counter, .. }) => {
+        handle_inc_command(key, amount, counter, &session.database, &session.context, &mut logger)
     }
-    Some(cli::Command::Ls { target, .. }) => {
-        handle_ls_command(target, &database, &resolved_context, &mut logger)
+    Some(cli::Commands::Dec { key, amount, counter, .. }) => {
+        handle_dec_command(key, amount, counter, &session.database, &session.context, &mut logger)
     }
-    Some(cli::Command::Export { file_path, format, proj, workspace, keystore, doc, key, seg, .. }) => {
-        handle_export_command(file_path, format, (proj, workspace, keystore, doc, key, seg), &database, &resolved_context, &mut logger)
+    Some(cli::Commands::Counter { action: cli::CounterAction::Reset { key, to, .. } }) => {
+        handle_counter_reset_command(key, to, &session.database, &session.context, &mut logger)
     }
-    Some(cli::Command::Import { file_path, mode, map_base, map_proj, map_workspace, .. }) => {
-        handle_import_command(file_path, mode, (map_base, map_proj, map_workspace), &database, &resolved_context, &mut logger)
+    Some(cli::Commands::Ls { target, all_bases, reveal, .. }) => {
+        handle_ls_command(target, all_bases, reveal, &session.config, &session.database, &session.db_manager, &session.context, &mut logger)
     }
-    Some(cli::Command::Getd { dik, .. }) => {
-        handle_getd_command(dik, &database, &resolved_context, &mut logger)
+    Some(cli::Commands::Export { file_path, format, proj, workspace, keystore, doc, key, seg, reveal, .. }) => {
+        let preview = args.dry_run.then_some(reveal);
+        handle_export_command(PathBuf::from(file_path), format, (proj, workspace, keystore, doc, key, seg), preview, &session.database, &session.db_manager, &session.context, &mut logger)
     }
-    Some(cli::Command::Setd { dik_value, .. }) => {
-        handle_setd_command(dik_value, &database, &resolved_context, &mut logger)
+    Some(cli::Commands::Import { file_path, mode, map_base, map_proj, map_workspace, .. }) => {
+        handle_import_command(PathBuf::from(file_path), mode, (map_base, map_proj, map_workspace), &session.database, &session.context, &mut logger)
     }
-    Some(cli::Command::Migrate { dry_run, .. }) => {
-        handle_migrate_command(dry_run, &database, &resolved_context, &mut logger)
+    Some(cli::Commands::Getd { dik, .. }) => {
+        handle_getd_command(dik, &session.database, &session.context, &mut logger)
     }
-    Some(cli::Command::Use { context_str }) => {
-        handle_use_command(context_str, &mut context_manager, &mut cursor_state)
+    Some(cli::Commands::Setd { dik_value, .. }) => {
+        handle_setd_command(dik_value, &session.database, &session.context, &mut logger)
     }
-    Some(cli::Command::Install {}) => {
-        handle_install_command(&database, &resolved_context, &mut logger)
+    Some(cli::Commands::Migrate { dry_run, .. }) => {
+        handle_migrate_command(dry_run, &session.database, &session.context, &mut logger)
+    }
+    Some(cli::Commands::Use { context_str }) => {
+        handle_use_command(context_str, &mut session.context_manager, &mut session.cursor_state)
+    }
+    Some(cli::Commands::Push { chain }) => {
+        handle_push_command(chain, &mut session.context_manager, &mut session.cursor_state)
+    }
+    Some(cli::Commands::Pop {}) => {
+        handle_pop_command(&mut session.context_manager, &mut session.cursor_state)
+    }
+    Some(cli::Commands::Cursor { history }) => {
+        handle_cursor_command(history, &mut session.context_manager, &session.cursor_state)
+    }
+    Some(cli::Commands::Install {}) => {
+        handle_install_command(&session.database, &session.context, &mut logger)
+    }
+    Some(cli::Commands::New { target, name, .. }) => {
+        handle_new_command(target, name, &mut session.db_manager, &session.database, &session.context, &mut logger)
+    }
+    Some(cli::Commands::Del { target, name, .. }) => {
+        handle_del_command(target, name, &session.database, &session.context, &mut logger)
+    }
+    Some(cli::Commands::Select { base }) => {
+        handle_select_command(base, &mut session.db_manager, &mut session.context_manager, &mut session.cursor_state, &mut logger)
+    }
+    Some(cli::Commands::Rebase { base }) => {
+        handle_rebase_command(base, &mut session.db_manager, &mut logger)
+    }
+    Some(cli::Commands::Unbase { base }) => {
+        handle_unbase_command(base, &mut session.db_manager, &mut logger)
+    }
+    Some(cli::Commands::Bind { action, arg, .. }) => {
+        handle_bind_command(action, arg, &session.database, &session.context, &mut logger)
+    }
+    Some(cli::Commands::Unbind { .. }) => {
+        handle_unbind_command(&session.database, &session.context, &mut logger)
+    }
+    Some(cli::Commands::Mv { from, to, merge }) => {
+        handle_mv_command(from, to, merge, session.force, &session.config, &mut session.context_manager, &mut session.cursor_state, &mut logger)
+    }
+    Some(cli::Commands::Cp { from, to, overwrite, skip, .. }) => {
+        handle_cp_command(from, to, (overwrite, skip), args.dry_run, session.force, &session.config, &mut session.context_manager, &session.cursor_state, &mut logger)
+    }
+    Some(cli::Commands::Alias { action }) => {
+        handle_alias_command(action, &session.config, &session.database, &session.context, &mut logger)
+    }
+    Some(cli::Commands::Find { pattern, glob, regex, values, json, all_bases }) => {
+        handle_find_command(pattern, (glob, regex, values), json, all_bases, &session.database, &session.db_manager, &mut logger)
+    }
+    Some(cli::Commands::Grepd { query, context, limit, json }) => {
+        handle_grepd_command(query, context.is_some(), limit, json, &session.database, &session.context, &mut logger)
+    }
+    Some(cli::Commands::Action(_)) => {
+        handle_action_command(&session.database, &session.context, &mut logger)
+    }
+    Some(cli::Commands::Status {}) => {
+        handle_cursor_command(false, &mut session.context_manager, &session.cursor_state)
+    }
+    Some(cli::Commands::Base {}) => {
+        println!("{}", session.cursor_state.base_cursor);
+        Ok(())
     }
     None => {
         // No command specified, show cursor status
-        handle_cursor_command(&mut context_manager, &cursor_state)
+        handle_cursor_command(false, &mut session.context_manager, &session.cursor_state)
     }
   }
   
@@ -118,13 +289,23 @@ pub fn dispatch_router(args: Vec<T>, database: &Database, context_chain: &str) -
 
 
 
-fn dispatch() ->  Result<(), E> {
-
-
+/// Run the CLI; returns the process exit code
+pub fn run() -> i32 {
+  match dispatch() {
+    Ok(()) => 0,
+    Err(e) => {
+      Stderr::new().error(&e.to_string());
+      e.exit_code()
+    }
+  }
+}
 
+fn dispatch() -> Result<()> {
     let mut logger = Stderr::new();
     
     let args = cli::Cli::parse();
+    reject_unknown_subcommand(&args);
+    crate::bookdb::oxidize::init_from_cli(&args);
     
     // Load configuration
     let config = Config::load().map_err(|e| {
@@ -132,20 +313,72 @@ fn dispatch() ->  Result<(), E> {
         e
     })?;
 
-
-
     // Initialize context manager
-    let mut context_manager = ContextManager::new();
+    let mut context_manager = ContextManager::new(config.clone());
     let mut cursor_state = context_manager.load_cursor_state()?;
 
+    // Resolve before opening: a `%other@...` chain targets its own base
+    // without moving cursor.base, so the db must follow the resolved context
+    let context = resolve_context_chain(&args, &mut context_manager, &mut cursor_state)?;
+
+    // `*@` chains fan out through db_manager; the cursor base stays the working db
+    let is_install = matches!(args.command, Some(cli::Commands::Install {}));
+    let working_base = if context.spans_bases() {
+        &cursor_state.base_cursor
+    } else {
+        &context.base
+    };
+    let database = open_database(&config, working_base, is_install)?;
+    
+    // Locked namespaces take writes only when safety is bypassed (-f, or -y per BashFX)
+    let force = BashFxFlags::from_cli(&args).bypass_safety();
+    database.set_force(force);
 
-    load_global_context( &args, &context_manager, &cursor_state);
+    // Base registry over $data_dir/*.sqlite3 for new/select/rebase/unbase
+    let mut db_manager = DatabaseManager::new(resolve_paths().data_dir);
+    db_manager.active_base = Some(cursor_state.base_cursor.clone());
+    db_manager.set_unlocker(unlock_database);
 
-    open_database(&context_manager, &cursor_state);
+    let mut session = Session { config, context_manager, cursor_state, context, database, db_manager, force };
+    dispatch_router(args, &mut session)
+}
 
-    resolve_context_chain(&args, &context_manager, &cursor_state); 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use crate::bookdb::app::sup::config::XdgDirs;
+
+    #[test]
+    fn test_admin_chains_leave_cursor_untouched() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let config = Config { xdg: XdgDirs::new(temp_dir.path()).unwrap() };
+        let mut manager = ContextManager::new(config.clone());
+        let mut cursor_state = manager.load_cursor_state()?;
+
+        for argv in [
+            vec!["bookdb", "lock", "@infra.production.var.credentials"],
+            vec!["bookdb", "unlock", "@infra.production.var.credentials"],
+            vec!["bookdb", "unbind", "@infra.production.var.credentials"],
+        ] {
+            let args = cli::Cli::try_parse_from(argv).unwrap();
+            let resolved = resolve_context_chain(&args, &mut manager, &mut cursor_state)?;
+            assert_eq!(resolved.project, "infra");
+        }
+
+        assert!(cursor_state.context_cursor.is_none());
+        assert!(!config.get_cursor_file_path().exists());
+        Ok(())
+    }
 
-    dispatch_router()
+    #[test]
+    fn test_only_hash_chains_stand_in_for_a_subcommand() {
+        let args = cli::Cli::try_parse_from(["bookdb", "#deploy.prod.var.app"]).unwrap();
+        assert!(matches!(&args.command, Some(cli::Commands::Action(argv)) if is_action_chain(&argv[0])));
 
+        for typo in ["stauts", "@deploy.prod.var.app", "deploy.prod"] {
+            let args = cli::Cli::try_parse_from(["bookdb", typo]).unwrap();
+            assert!(matches!(&args.command, Some(cli::Commands::Action(argv)) if !is_action_chain(&argv[0])));
+        }
+    }
 }
-
@@ -1,9 +1,28 @@
+// src/bookdb/app/ctrl/handlers.rs - One handler per CLI command, called from dispatch
+
+use std::path::PathBuf;
+use stderr::Stderr;
+
+use crate::bookdb;
+use crate::cli;
+use crate::error::{Result, BookdbError};
+use crate::bookdb::service::db::Database;
+use crate::bookdb::service::db::driver::{ActionKind, DatabaseManager};
+use crate::bookdb::app::sup::config::Config;
+use crate::ctx::ContextManager;
+use crate::bookdb::service::api as commands;
+use super::dispatch::open_database;
+
 
 /// Handle cursor status display
 pub fn handle_cursor_command(
+    history: bool,
     context_manager: &mut ContextManager,
     cursor_state: &bookdb::context::CursorState
-) ->  Result<(), E> {
+) -> Result<()> {
+    if history {
+        return context_manager.show_cursor_history(cursor_state);
+    }
     context_manager.show_cursor_status(cursor_state)
 }
 
@@ -12,22 +31,99 @@ pub fn handle_use_command(
     context_str: String,
     context_manager: &mut ContextManager,
     cursor_state: &mut bookdb::context::CursorState,
-) ->  Result<(), E> {
-    let chain = parse_context_chain(&context_str, &cursor_state.base_cursor)?;
-    context_manager.update_cursor(&chain, cursor_state)?;
+) -> Result<()> {
+    // `use -` goes back to the previous context
+    if context_str == "-" {
+        context_manager.back(cursor_state)?;
+        return Ok(());
+    }
+    let chain = context_manager.parse_chain(&context_str, cursor_state)?;
+    // `use %chain` previews the context without moving the cursor
+    context_manager.enter_context(&chain, cursor_state)?;
+    Ok(())
+}
+
+/// Handle `push <chain>`: save the current context, then switch
+pub fn handle_push_command(
+    chain_str: String,
+    context_manager: &mut ContextManager,
+    cursor_state: &mut bookdb::context::CursorState,
+) -> Result<()> {
+    let chain = context_manager.parse_chain(&chain_str, cursor_state)?;
+    context_manager.push_context(&chain, cursor_state)?;
+    Ok(())
+}
+
+/// Handle `pop`: return to the last pushed context
+pub fn handle_pop_command(
+    context_manager: &mut ContextManager,
+    cursor_state: &mut bookdb::context::CursorState,
+) -> Result<()> {
+    context_manager.pop_context(cursor_state)?;
     Ok(())
 }
 
 /// Handle variable retrieval
 pub fn handle_getv_command(
     key: String,
+    at: Option<String>,
+    (typed, json): (bool, bool),
     database: &Database,
+    db_manager: &DatabaseManager,
     context: &bookdb::context::ResolvedContext,
     logger: &mut Stderr,
-) ->  Result<(), E> {
+) -> Result<()> {
     logger.trace_fn("getv", &format!("key: {}, context: {}", key, context));
     
+    // Point-in-time read from var_history
+    if let Some(at) = at {
+        use crate::bookdb::service::db::driver::HistoryPoint;
+        if context.is_wildcard() {
+            return Err(BookdbError::Argument("--at needs a single keystore, not a wildcard chain".to_string()));
+        }
+        let point = HistoryPoint::parse(&at)?;
+        match database.get_variable_at(&key, context, point)? {
+            Some(value) => println!("{}", value),
+            None => {
+                logger.trace_fn("getv", &format!("variable did not exist at {}", at));
+                std::process::exit(1);
+            }
+        }
+        return Ok(());
+    }
+    
+    // `*@` chains: `<base@chain>\t<value>` for every base holding the key
+    if context.spans_bases() {
+        let hits: Vec<_> = db_manager.collect_variables_all(context)?
+            .into_iter()
+            .filter(|v| v.key == key)
+            .collect();
+        if hits.is_empty() {
+            logger.trace_fn("getv", "variable not found in any base");
+            std::process::exit(1);
+        }
+        for hit in hits {
+            println!("{}\t{}", hit.chain, hit.value);
+        }
+        return Ok(());
+    }
+    
+    // Wildcard chains print `<chain>\t<value>` for every keystore holding the key
+    if context.is_wildcard() {
+        return crate::bookdb::service::api::getv::execute(&key, context, database);
+    }
+    
     match database.get_variable(&key, context)? {
+        Some(value) if typed || json => {
+            let var_type = database.get_variable_type(&key, context)?;
+            let native = var_type.to_json(&value);
+            if json {
+                println!("{}", serde_json::json!({ "key": key, "value": native, "type": var_type.as_str() }));
+            } else {
+                println!("{}", native);
+            }
+            logger.trace_fn("getv", &format!("variable found and returned as {}", var_type.as_str()));
+        }
         Some(value) => {
             println!("{}", value);
             logger.trace_fn("getv", "variable found and returned");
@@ -44,17 +140,55 @@ pub fn handle_getv_command(
 /// Handle variable setting
 pub fn handle_setv_command(
     key_value: String,
+    var_type: Option<String>,
+    (if_equals, if_absent, if_present): (Option<String>, bool, bool),
+    ttl: Option<String>,
     database: &Database,
     context: &bookdb::context::ResolvedContext,
     logger: &mut Stderr,
-) ->  Result<(), E> {
-    logger.trace_fn("setv", &format!("input: {}, context: {}", key_value, context));
+) -> Result<()> {
+    use crate::bookdb::service::db::driver::vartype::{parse_duration, split_typed_key};
+    use crate::bookdb::service::db::driver::{SetOptions, VarType, WriteCondition};
     
     let (key, value) = key_value.split_once('=')
         .ok_or_else(|| BookdbError::Argument("setv requires key=value format".to_string()))?;
+    // The value may be a secret; traces name the key only
+    logger.trace_fn("setv", &format!("key: {}, context: {}", key, context));
     
-    database.set_variable(key.trim(), value.trim(), context)?;
-    logger.trace_fn("setv", &format!("set variable: {}", key.trim()));
+    // `KEY:int=5` and `--type int` name the same thing; they must agree
+    let (key, inline_type) = split_typed_key(key.trim())?;
+    let flag_type = var_type.as_deref().map(VarType::parse).transpose()?;
+    let var_type = match (inline_type, flag_type) {
+        (Some(a), Some(b)) if a != b => {
+            return Err(BookdbError::Argument(format!(
+                "Conflicting types for '{}': {} vs --type {}", key, a.as_str(), b.as_str()
+            )));
+        }
+        (inline, flag) => inline.or(flag),
+    };
+    
+    let condition = match (if_equals, if_absent, if_present) {
+        (Some(old), _, _) => Some(WriteCondition::IfEquals(old)),
+        (None, true, _) => Some(WriteCondition::IfAbsent),
+        (None, false, true) => Some(WriteCondition::IfPresent),
+        _ => None,
+    };
+    let ttl = ttl.as_deref().map(|raw| match parse_duration(raw) {
+        Some(ttl) if !ttl.is_zero() => Ok(ttl),
+        _ => Err(BookdbError::Argument(format!("Invalid --ttl '{}' (e.g. 90s, 15m, 1h, 7d)", raw))),
+    }).transpose()?;
+    
+    let options = SetOptions { var_type, condition, ttl };
+    match database.set_variable_with(key, value.trim(), &options, context) {
+        Ok(_) => {}
+        Err(e @ BookdbError::PreconditionFailed(_)) => {
+            // Scripts branch on the exit code; the message is for humans
+            logger.warn(&format!("Not set: {}", e));
+            std::process::exit(e.exit_code());
+        }
+        Err(e) => return Err(e),
+    }
+    logger.trace_fn("setv", &format!("set variable: {}", key));
     
     Ok(())
 }
@@ -65,7 +199,7 @@ pub fn handle_delv_command(
     database: &Database,
     context: &bookdb::context::ResolvedContext,
     logger: &mut Stderr,
-) ->  Result<(), E> {
+) -> Result<()> {
     logger.trace_fn("delv", &format!("key: {}, context: {}", key, context));
     
     // Check if key exists first
@@ -88,47 +222,398 @@ pub fn handle_delv_command(
     Ok(())
 }
 
+/// Handle `history KEY`: every recorded change, oldest first
+pub fn handle_history_command(
+    key: String,
+    reveal: bool,
+    database: &Database,
+    context: &bookdb::context::ResolvedContext,
+    logger: &mut Stderr,
+) -> Result<()> {
+    use crate::bookdb::service::db::driver::display_value;
+    
+    logger.trace_fn("history", &format!("key: {}, context: {}", key, context));
+    
+    let changes = database.var_history(&key, context)?;
+    if changes.is_empty() {
+        logger.warn(&format!("No history for '{}' in context {}", key, context));
+        return Ok(());
+    }
+    
+    // Past values of a sensitive key are as secret as the current one
+    let sensitive = database.is_sensitive(&key, context)?;
+    let show = |value: &Option<String>| match value {
+        Some(value) => display_value(value, sensitive, reveal),
+        None => "-".to_string(),
+    };
+    let rows: Vec<Vec<String>> = changes.iter()
+        .map(|c| vec![c.rev.to_string(), c.when.clone(), c.op.clone(), show(&c.old_value), show(&c.new_value)])
+        .collect();
+    
+    let mut formatter = bookdb::context_manager::LsTableFormatter::new();
+    formatter.display_table(&["Rev", "When", "Op", "Old", "New"], &rows, Some(&format!("History of {} in {}", key, context)))?;
+    
+    Ok(())
+}
+
+/// Handle `revert KEY --to REV`
+pub fn handle_revert_command(
+    key: String,
+    rev: i64,
+    database: &Database,
+    context: &bookdb::context::ResolvedContext,
+    logger: &mut Stderr,
+) -> Result<()> {
+    use crate::bookdb::service::db::driver::display_value;
+    
+    logger.trace_fn("revert", &format!("key: {}, rev: {}, context: {}", key, rev, context));
+    
+    match database.revert_variable(&key, context, rev)? {
+        Some(value) => {
+            let value = display_value(&value, database.is_sensitive(&key, context)?, false);
+            logger.okay(&format!("Reverted '{}' to rev {}: {}", key, rev, value));
+        }
+        None => logger.okay(&format!("Reverted '{}' to rev {}: deleted (did not exist then)", key, rev)),
+    }
+    
+    Ok(())
+}
+
+/// Handle `undo [N]`: replay the inverses of the last N journaled commands
+pub fn handle_undo_command(
+    count: usize,
+    database: &Database,
+    logger: &mut Stderr,
+) -> Result<()> {
+    logger.trace_fn("undo", &format!("count: {}", count));
+    
+    let undone = database.undo(count)?;
+    if undone.is_empty() {
+        logger.warn("Nothing to undo");
+        return Ok(());
+    }
+    for op in &undone {
+        if op.entries == 0 {
+            // Forced past an irreversible op; nothing was restored
+            logger.warn(&format!("Skipped #{} {} on {} (cannot be undone)", op.id, op.command, op.chain));
+            continue;
+        }
+        logger.okay(&format!("Undid #{} {} on {} ({} change(s))", op.id, op.command, op.chain, op.entries));
+    }
+    
+    Ok(())
+}
+
+/// Handle `journal`: the most recent journaled commands, newest first
+pub fn handle_journal_command(
+    limit: usize,
+    database: &Database,
+    logger: &mut Stderr,
+) -> Result<()> {
+    logger.trace_fn("journal", &format!("limit: {}", limit));
+    
+    let ops = database.list_journal(limit)?;
+    if ops.is_empty() {
+        logger.info("Journal is empty");
+        return Ok(());
+    }
+    
+    let rows: Vec<Vec<String>> = ops.iter()
+        .map(|op| vec![
+            op.id.to_string(),
+            op.when.clone(),
+            op.command.clone(),
+            op.chain.clone(),
+            op.entries.to_string(),
+            if op.undone { "undone".to_string() } else { String::new() },
+        ])
+        .collect();
+    
+    let mut formatter = bookdb::context_manager::LsTableFormatter::new();
+    formatter.display_table(&["#", "When", "Command", "Chain", "Changes", "State"], &rows, Some("Journal"))?;
+    
+    Ok(())
+}
+
+/// Handle `audit`: who changed what, oldest first; values only as short hashes
+pub fn handle_audit_command(
+    since: Option<String>,
+    chain: Option<String>,
+    json: bool,
+    database: &Database,
+    logger: &mut Stderr,
+) -> Result<()> {
+    use crate::bookdb::service::db::driver::parse_since;
+    logger.trace_fn("audit", &format!("since: {:?}, chain: {:?}", since, chain));
+    
+    let since = since.as_deref().map(parse_since).transpose()?;
+    let chain = chain.as_deref().map(|chain| chain.trim_start_matches('@'));
+    let entries = database.list_audit(since, chain)?;
+    if json {
+        for entry in &entries {
+            println!("{}", serde_json::to_string(entry)?);
+        }
+        return Ok(());
+    }
+    if entries.is_empty() {
+        logger.info("No audit entries");
+        return Ok(());
+    }
+    
+    let short = |hash: &Option<String>| hash.as_deref().map_or(String::new(), |h| h[..h.len().min(12)].to_string());
+    let rows: Vec<Vec<String>> = entries.iter()
+        .map(|entry| vec![
+            entry.id.to_string(),
+            entry.when.clone(),
+            entry.user.clone().unwrap_or_default(),
+            entry.pid.to_string(),
+            entry.op.clone(),
+            entry.chain.clone(),
+            entry.key.clone().unwrap_or_default(),
+            short(&entry.old_hash),
+            short(&entry.new_hash),
+        ])
+        .collect();
+    
+    let mut formatter = bookdb::context_manager::LsTableFormatter::new();
+    formatter.display_table(&["#", "When", "User", "PID", "Op", "Chain", "Key", "Old", "New"], &rows, Some("Audit"))?;
+    
+    Ok(())
+}
+
+/// Handle `purge`: delete expired variables from the working base
+pub fn handle_purge_command(
+    dry_run: bool,
+    database: &Database,
+    logger: &mut Stderr,
+) -> Result<()> {
+    logger.trace_fn("purge", &format!("dry_run: {}", dry_run));
+    
+    let purged = database.purge_expired(dry_run)?;
+    match (purged, dry_run) {
+        (0, _) => logger.info("No expired variables"),
+        (n, true) => logger.info(&format!("Would purge {} expired variable(s)", n)),
+        (n, false) => logger.okay(&format!("Purged {} expired variable(s)", n)),
+    }
+    
+    Ok(())
+}
+
+/// Handle `encrypt`: seal a keystore's values under a passphrase
+pub fn handle_encrypt_command(
+    database: &Database,
+    context: &bookdb::context::ResolvedContext,
+    logger: &mut Stderr,
+) -> Result<()> {
+    use crate::bookdb::service::db::driver::PASSPHRASE_ENV;
+    
+    logger.trace_fn("encrypt", &format!("context: {}", context));
+    
+    // A new passphrase is typed twice; a typo here would lock the keystore for good
+    if std::env::var(PASSPHRASE_ENV).is_err() {
+        let passphrase = rpassword::prompt_password("New passphrase: ")?;
+        if passphrase.is_empty() || passphrase != rpassword::prompt_password("Repeat passphrase: ")? {
+            return Err(BookdbError::Argument("Passphrases are empty or do not match".to_string()));
+        }
+        database.unlock(&passphrase);
+    }
+    
+    let count = database.encrypt_keystore(context)?;
+    logger.okay(&format!("Encrypted {} ({} variables)", context, count));
+    Ok(())
+}
+
+/// Handle `decrypt`: store an encrypted keystore in plaintext again
+pub fn handle_decrypt_command(
+    database: &Database,
+    context: &bookdb::context::ResolvedContext,
+    logger: &mut Stderr,
+) -> Result<()> {
+    logger.trace_fn("decrypt", &format!("context: {}", context));
+    
+    let count = database.decrypt_keystore(context)?;
+    logger.okay(&format!("Decrypted {} ({} variables)", context, count));
+    Ok(())
+}
+
+/// Handle `lock`/`unlock`: a namespace lock, or with `--base` the base's read-only mode
+pub fn handle_lock_command(
+    locked: bool,
+    base: bool,
+    database: &Database,
+    context: &bookdb::context::ResolvedContext,
+    logger: &mut Stderr,
+) -> Result<()> {
+    logger.trace_fn("lock", &format!("locked: {}, base: {}, context: {}", locked, base, context));
+    
+    if base {
+        database.set_read_only(locked)?;
+        let state = if locked { "read-only" } else { "writable" };
+        logger.okay(&format!("Base '{}' is now {}", database.base_name, state));
+    } else {
+        database.set_namespace_lock(context, locked)?;
+        let state = if locked { "Locked" } else { "Unlocked" };
+        logger.okay(&format!("{} {}", state, context));
+    }
+    Ok(())
+}
+
+/// Handle `sensitive [KEY] [--off]`: mark a key, or the whole keystore, as sensitive
+pub fn handle_sensitive_command(
+    key: Option<String>,
+    sensitive: bool,
+    database: &Database,
+    context: &bookdb::context::ResolvedContext,
+    logger: &mut Stderr,
+) -> Result<()> {
+    use crate::bookdb::service::db::driver::looks_sensitive;
+    
+    logger.trace_fn("sensitive", &format!("key: {:?}, sensitive: {}, context: {}", key, sensitive, context));
+    
+    database.mark_sensitive(key.as_deref(), sensitive, context)?;
+    let target = key.as_ref().map_or_else(|| format!("keystore {}", context), |key| format!("'{}'", key));
+    if sensitive {
+        logger.okay(&format!("Marked {} as sensitive", target));
+    } else if key.as_deref().is_some_and(looks_sensitive) {
+        logger.warn(&format!("Unmarked {}, but its name still marks it as sensitive", target));
+    } else {
+        logger.okay(&format!("Unmarked {}", target));
+    }
+    Ok(())
+}
+
+/// Parse the amount and `--float/--min/--max/--wrap/--init` shared by `inc` and `dec`
+fn counter_operands(
+    amount: Option<String>,
+    counter: cli::CounterArgs,
+) -> Result<(crate::bookdb::service::db::driver::Number, crate::bookdb::service::db::driver::CounterOptions)> {
+    use crate::bookdb::service::db::driver::{CounterOptions, Number};
+    
+    let parse = |raw: Option<String>| raw.as_deref().map(Number::parse).transpose();
+    let amount = parse(amount)?.unwrap_or(Number::Int(1));
+    let options = CounterOptions {
+        float: counter.float,
+        min: parse(counter.min)?,
+        max: parse(counter.max)?,
+        wrap: counter.wrap,
+        init: parse(counter.init)?,
+        expect: parse(counter.expect)?,
+    };
+    Ok((amount, options))
+}
+
 /// Handle increment command
 pub fn handle_inc_command(
     key: String,
-    amount: i64,
+    amount: Option<String>,
+    counter: cli::CounterArgs,
     database: &Database,
     context: &bookdb::context::ResolvedContext,
     logger: &mut Stderr,
-) ->  Result<(), E> {
-    logger.trace_fn("inc", &format!("key: {}, amount: {}, context: {}", key, amount, context));
-    commands::execute_inc(key, amount, context, database)
+) -> Result<()> {
+    logger.trace_fn("inc", &format!("key: {}, amount: {:?}, {:?}, context: {}", key, amount, counter, context));
+    let (amount, options) = counter_operands(amount, counter)?;
+    commands::execute_inc_with(key, amount, &options, context, database)
 }
 
 /// Handle decrement command
 pub fn handle_dec_command(
     key: String,
-    amount: i64,
+    amount: Option<String>,
+    counter: cli::CounterArgs,
     database: &Database,
     context: &bookdb::context::ResolvedContext,
     logger: &mut Stderr,
-) ->  Result<(), E> {
-    logger.trace_fn("dec", &format!("key: {}, amount: {}, context: {}", key, amount, context));
-    commands::execute_dec(key, amount, context, database)
+) -> Result<()> {
+    logger.trace_fn("dec", &format!("key: {}, amount: {:?}, {:?}, context: {}", key, amount, counter, context));
+    let (amount, options) = counter_operands(amount, counter)?;
+    commands::execute_dec_with(key, amount, &options, context, database)
+}
+
+/// Handle `counter reset KEY [--to N]`
+pub fn handle_counter_reset_command(
+    key: String,
+    to: Option<String>,
+    database: &Database,
+    context: &bookdb::context::ResolvedContext,
+    logger: &mut Stderr,
+) -> Result<()> {
+    logger.trace_fn("counter", &format!("reset key: {}, to: {:?}, context: {}", key, to, context));
+    
+    let to = to.as_deref().map(crate::bookdb::service::db::driver::Number::parse).transpose()?;
+    let value = database.reset_counter(&key, to, context)?;
+    logger.okay(&format!("Counter '{}' reset to {}", key, value));
+    
+    Ok(())
 }
 
 /// Handle listing command
 pub fn handle_ls_command(
     target: cli::LsTarget,
+    all_bases: bool,
+    reveal: bool,
+    config: &Config,
     database: &Database,
+    db_manager: &DatabaseManager,
     context: &bookdb::context::ResolvedContext,
     logger: &mut Stderr,
-) ->  Result<(), E> {
+) -> Result<()> {
     use bookdb::context_manager::LsTableFormatter;
+    use crate::bookdb::service::db::driver::display_value;
     
     logger.trace_fn("ls", &format!("target: {:?}, context: {}", target, context));
     
+    // `--all-bases` is shorthand for putting `*@` on the chain
+    let context = &if all_bases { context.with_base("*") } else { context.clone() };
     let mut formatter = LsTableFormatter::new();
     
     match target {
+        cli::LsTarget::All => {
+            for target in [cli::LsTarget::Projects, cli::LsTarget::Workspaces, cli::LsTarget::Keystores, cli::LsTarget::Keys, cli::LsTarget::Docs] {
+                handle_ls_command(target, all_bases, reveal, config, database, db_manager, context, logger)?;
+            }
+        }
+        cli::LsTarget::Keys if context.spans_bases() => {
+            let rows: Vec<Vec<String>> = db_manager.collect_variables_all(context)?
+                .into_iter()
+                .map(|v| {
+                    let value = display_value(&v.value, v.sensitive, reveal);
+                    vec![v.chain, v.key, value]
+                })
+                .collect();
+            formatter.display_table(&["Chain", "Key", "Value"], &rows, Some(&format!("Variables matching {}", context.qualified())))?;
+        }
+        cli::LsTarget::Keys if context.is_wildcard() => {
+            let rows: Vec<Vec<String>> = database.collect_variables(context)?
+                .into_iter()
+                .map(|v| {
+                    let value = display_value(&v.value, v.sensitive, reveal);
+                    vec![v.chain, v.key, value]
+                })
+                .collect();
+            formatter.display_table(&["Chain", "Key", "Value"], &rows, Some(&format!("Variables matching {}", context)))?;
+        }
         cli::LsTarget::Keys => {
-            let variables = database.list_variables(context)?;
-            formatter.display_variables(&variables, &format!("{}", context))?;
+            let mut variables: Vec<(String, String)> = database.list_variables(context)?.into_iter().collect();
+            variables.sort();
+            for (key, value) in variables.iter_mut() {
+                *value = display_value(value, database.is_sensitive(key, context)?, reveal);
+            }
+            let expiries = database.list_variable_expiries(context)?;
+            if expiries.is_empty() {
+                formatter.display_variables(&variables, &format!("{}", context))?;
+            } else {
+                // Remaining lifetime for `setv --ttl` keys, `-` for permanent ones
+                use crate::bookdb::service::db::driver::vartype::format_duration;
+                let mut rows: Vec<Vec<String>> = variables.into_iter()
+                    .map(|(key, value)| {
+                        let expires = expiries.get(&key).map_or("-".to_string(), |left| format_duration(*left));
+                        vec![key, value, expires]
+                    })
+                    .collect();
+                rows.sort();
+                formatter.display_table(&["Key", "Value", "Expires In"], &rows, Some(&format!("Variables in {}", context)))?;
+            }
         }
         cli::LsTarget::Docs => {
             let documents = database.list_documents(context)?;
@@ -139,12 +624,59 @@ pub fn handle_ls_command(
             formatter.display_namespaces(&projects, "Projects", "All bases")?;
         }
         cli::LsTarget::Workspaces => {
-            let workspaces = database.list_workspaces(&context.project_name)?;
-            formatter.display_namespaces(&workspaces, "Workspaces", &context.project_name)?;
+            let workspaces = database.list_workspaces(&context.project)?;
+            formatter.display_namespaces(&workspaces, "Workspaces", &context.project)?;
         }
         cli::LsTarget::Keystores => {
-            let keystores = database.list_keystores(&context.project_name, &context.workspace_name)?;
-            formatter.display_namespaces(&keystores, "Keystores", &format!("{}.{}", context.project_name, context.workspace_name))?;
+            let keystores = database.list_keystores(&context.project, &context.workspace)?;
+            formatter.display_namespaces(&keystores, "Keystores", &format!("{}.{}", context.project, context.workspace))?;
+        }
+        cli::LsTarget::Bases => {
+            let paths = crate::bookdb::app::sup::config::resolve_paths();
+            let rows: Vec<Vec<String>> = crate::bookdb::service::db::driver::list_bases_in(&paths.data_dir)?
+                .into_iter()
+                .map(|b| vec![b.name, b.size_bytes.to_string()])
+                .collect();
+            formatter.display_table(&["Base", "Size (bytes)"], &rows, Some("Bases"))?;
+        }
+        cli::LsTarget::Actions => {
+            let rows: Vec<Vec<String>> = database.list_actions()?
+                .into_iter()
+                .map(|b| vec![
+                    format!("#{}.{}.{}.{}", b.project, b.workspace, match b.anchor {
+                        bookdb::context::Anchor::Var => "var",
+                        bookdb::context::Anchor::Doc => "doc",
+                    }, b.tail),
+                    b.kind.to_string(),
+                    b.arg.unwrap_or_default(),
+                ])
+                .collect();
+            formatter.display_table(&["Chain", "Action", "Argument"], &rows, Some("Actions"))?;
+        }
+        cli::LsTarget::Segments => {
+            let segments = database.list_doc_segments(context.doc_key(), context)?;
+            let rows: Vec<Vec<String>> = segments.iter()
+                .map(|s| vec![s.path.clone(), s.mime.clone(), s.size_bytes.to_string()])
+                .collect();
+            formatter.display_table(&["Segment", "Mime", "Size (bytes)"], &rows, Some(context.doc_key()))?;
+        }
+        cli::LsTarget::Aliases => {
+            let global_file = config.get_aliases_file_path();
+            let rows: Vec<Vec<String>> = crate::bookdb::service::ctx::alias::list_visible_aliases(database, &global_file)?
+                .into_iter()
+                .map(|a| vec![format!(":{}", a.name), a.chain, a.scope.as_str().to_string()])
+                .collect();
+            formatter.display_table(&["Alias", "Chain", "Scope"], &rows, Some("Aliases"))?;
+        }
+        cli::LsTarget::Locks => {
+            if database.is_read_only()? {
+                logger.warn(&format!("Base '{}' is read-only", database.base_name));
+            }
+            let rows: Vec<Vec<String>> = database.list_locks()?
+                .into_iter()
+                .map(|lock| vec![lock.chain, lock.locked_at])
+                .collect();
+            formatter.display_table(&["Chain", "Locked At"], &rows, Some("Locks"))?;
         }
     }
     
@@ -156,11 +688,14 @@ pub fn handle_export_command(
     file_path: PathBuf,
     format: Option<String>,
     filters: (Option<String>, Option<String>, Option<String>, Option<String>, Option<String>, Option<String>),
+    preview: Option<bool>,
     database: &Database,
+    db_manager: &DatabaseManager,
     context: &bookdb::context::ResolvedContext,
     logger: &mut Stderr,
-) ->  Result<(), E> {
-    use bookdb::context_manager::{OperationProgress, DestructiveOpConfirm};
+) -> Result<()> {
+    use bookdb::context_manager::{OperationProgress, DestructiveOpConfirm, LsTableFormatter};
+    use crate::bookdb::service::db::driver::display_value;
     
     logger.trace_fn("export", &format!("file: {:?}, context: {}", file_path, context));
     
@@ -176,6 +711,40 @@ pub fn handle_export_command(
             .to_string()
     });
     
+    // Get data to export (apply filters if provided); wildcard chains fan
+    // out and tag each item with its fully-qualified chain
+    let data = if context.spans_bases() {
+        db_manager.export_all(context, filters.4.as_deref())?
+    } else if context.is_wildcard() {
+        database.export_wildcard(context, filters.4.as_deref())?
+    } else {
+        database.export_data(context, (
+            filters.0.as_deref(),
+            filters.1.as_deref(),
+            filters.2.as_deref(),
+            filters.3.as_deref(),
+            filters.4.as_deref(),
+            filters.5.as_deref()
+        ))?
+    };
+    
+    // `--dry-run` previews what would be written, sensitive values masked unless `--reveal`
+    if let Some(reveal) = preview {
+        let rows: Vec<Vec<String>> = data.iter()
+            .map(|item| vec![
+                item.item_type.clone(),
+                item.context.clone(),
+                item.key.clone(),
+                display_value(&item.value, item.sensitive, reveal),
+            ])
+            .collect();
+        LsTableFormatter::new().display_table(
+            &["Type", "Context", "Key", "Value"], &rows,
+            Some(&format!("Would export {} items to {} ({})", data.len(), file_path.display(), format)),
+        )?;
+        return Ok(());
+    }
+    
     // Confirm overwrite if file exists
     let mut confirm = DestructiveOpConfirm::new();
     if !confirm.confirm_overwrite(
@@ -187,16 +756,6 @@ pub fn handle_export_command(
     
     let mut progress = OperationProgress::new("Export");
     
-    // Get data to export (apply filters if provided)
-    let data = database.export_data(context, &(
-        filters.0.as_deref(),
-        filters.1.as_deref(),
-        filters.2.as_deref(),
-        filters.3.as_deref(),
-        filters.4.as_deref(),
-        filters.5.as_deref()
-    ))?;
-    
     progress.set_total(data.len());
     
     // Write to file with progress tracking
@@ -210,6 +769,10 @@ pub fn handle_export_command(
                 use std::io::Write;
                 writeln!(output, "{}", serde_json::to_string(item)?)?;
             }
+            "kv" if context.is_wildcard() => {
+                use std::io::Write;
+                writeln!(output, "{}:{}={}", item.context, item.key, item.value)?;
+            }
             "kv" => {
                 use std::io::Write;
                 writeln!(output, "{}={}", item.key, item.value)?;
@@ -232,7 +795,7 @@ pub fn handle_import_command(
     database: &Database,
     context: &bookdb::context::ResolvedContext,
     logger: &mut Stderr,
-) ->  Result<(), E> {
+) -> Result<()> {
     use bookdb::context_manager::{OperationProgress, DestructiveOpConfirm};
     
     logger.trace_fn("import", &format!("file: {:?}, context: {}", file_path, context));
@@ -242,6 +805,7 @@ pub fn handle_import_command(
     }
     
     let mode = mode.unwrap_or_else(|| "merge".to_string());
+    logger.trace_fn("import", &format!("mode: {}", mode));
     
     // Extract mappings
     let (_map_base, _map_proj, _map_workspace) = mappings;
@@ -264,12 +828,14 @@ pub fn handle_import_command(
     
     let mut imported_count = 0;
     
-    for (i, line) in lines.iter().enumerate() {
+    // The whole import is one undo step
+    database.begin_journal("import", context);
+    let imported = lines.iter().enumerate().try_for_each(|(i, line)| -> Result<()> {
         progress.increment(&format!("line {}", i + 1))?;
         
         let line = line.trim();
         if line.is_empty() || line.starts_with('#') {
-            continue;
+            return Ok(());
         }
         
         if let Some((key, value)) = line.split_once('=') {
@@ -281,7 +847,10 @@ pub fn handle_import_command(
                 imported_count += 1;
             }
         }
-    }
+        Ok(())
+    });
+    database.end_journal();
+    imported?;
     
     progress.complete()?;
     logger.okay(&format!("Successfully imported {} variables from {}", imported_count, file_path.display()));
@@ -291,25 +860,13 @@ pub fn handle_import_command(
 
 /// Handle document retrieval
 pub fn handle_getd_command(
-    dik: String,
+    dik: Option<String>,
     database: &Database,
     context: &bookdb::context::ResolvedContext,
     logger: &mut Stderr,
-) ->  Result<(), E> {
-    logger.trace_fn("getd", &format!("dik: {}, context: {}", dik, context));
-    
-    match database.get_document(&dik, context)? {
-        Some(content) => {
-            println!("{}", content);
-            logger.trace_fn("getd", "document found and returned");
-        }
-        None => {
-            logger.trace_fn("getd", "document not found");
-            std::process::exit(1);
-        }
-    }
-    
-    Ok(())
+) -> Result<()> {
+    logger.trace_fn("getd", &format!("dik: {:?}, context: {}", dik, context));
+    commands::execute_getd(dik.as_deref(), context, database)
 }
 
 /// Handle document setting
@@ -318,16 +875,9 @@ pub fn handle_setd_command(
     database: &Database,
     context: &bookdb::context::ResolvedContext,
     logger: &mut Stderr,
-) ->  Result<(), E> {
-    logger.trace_fn("setd", &format!("input: {}, context: {}", dik_value, context));
-    
-    let (dik, content) = dik_value.split_once('=')
-        .ok_or_else(|| BookdbError::Argument("setd requires dik=content format".to_string()))?;
-    
-    database.set_document(dik.trim(), content.trim(), context)?;
-    logger.trace_fn("setd", &format!("set document: {}", dik.trim()));
-    
-    Ok(())
+) -> Result<()> {
+    logger.trace_fn("setd", &format!("context: {}", context));
+    commands::execute_setd(&dik_value, context, database)
 }
 
 /// Handle migration command
@@ -336,7 +886,7 @@ pub fn handle_migrate_command(
     database: &Database,
     context: &bookdb::context::ResolvedContext,
     logger: &mut Stderr,
-) ->  Result<(), E> {
+) -> Result<()> {
     logger.trace_fn("migrate", &format!("dry_run: {}, context: {}", dry_run, context));
     
     if dry_run {
@@ -359,8 +909,8 @@ pub fn handle_install_command(
     database: &Database,
     context: &bookdb::context::ResolvedContext,
     logger: &mut Stderr,
-) ->  Result<(), E> {
-    logger.trace_fn("install", "performing installation");
+) -> Result<()> {
+    logger.trace_fn("install", &format!("performing installation in base {}", context.base));
     
     // TODO: Implement full installation logic
     // - Create XDG directories
@@ -378,3 +928,447 @@ pub fn handle_install_command(
     
     Ok(())
 }
+
+/// Handle 'new' command (currently bases only)
+pub fn handle_new_command(
+    target: cli::NewTarget,
+    name: String,
+    manager: &mut DatabaseManager,
+    database: &Database,
+    context: &bookdb::context::ResolvedContext,
+    logger: &mut Stderr,
+) -> Result<()> {
+    use crate::bookdb::service::ctx::validate_namespace_name;
+
+    logger.trace_fn("new", &format!("target: {:?}, name: {}, context: {}", target, name, context));
+
+    match target {
+        cli::NewTarget::Base => {
+            let path = manager.create_base(&name)?;
+            logger.okay(&format!("Created base '{}' at {}", name, path.display()));
+        }
+        cli::NewTarget::Project => {
+            validate_namespace_name("project", &name)?;
+            database.create_project(&name)?;
+            logger.okay(&format!("Created project '{}'", name));
+        }
+        cli::NewTarget::Workspace => {
+            validate_namespace_name("workspace", &name)?;
+            database.create_workspace(&context.project, &name)?;
+            logger.okay(&format!("Created workspace {}.{}", context.project, name));
+        }
+        cli::NewTarget::Keystore => {
+            validate_namespace_name("keystore", &name)?;
+            database.create_keystore(&context.project, &context.workspace, &name)?;
+            logger.okay(&format!("Created keystore {}.{}.var.{}", context.project, context.workspace, name));
+        }
+        cli::NewTarget::Docstore => {
+            validate_namespace_name("workspace", &name)?;
+            database.create_docstore(&context.project, &name)?;
+            logger.okay(&format!("Created docstore in {}.{}", context.project, name));
+        }
+    }
+
+    Ok(())
+}
+
+/// Handle `del project|workspace|keystore|docstore`; contents go with it
+pub fn handle_del_command(
+    target: cli::DelTarget,
+    name: String,
+    database: &Database,
+    context: &bookdb::context::ResolvedContext,
+    logger: &mut Stderr,
+) -> Result<()> {
+    use bookdb::context_manager::DestructiveOpConfirm;
+
+    logger.trace_fn("del", &format!("target: {:?}, name: {}, context: {}", target, name, context));
+
+    let (kind, label) = match target {
+        cli::DelTarget::Project => ("project", name.clone()),
+        cli::DelTarget::Workspace => ("workspace", format!("{}.{}", context.project, name)),
+        cli::DelTarget::Keystore => ("keystore", format!("{}.{}.var.{}", context.project, context.workspace, name)),
+        cli::DelTarget::Docstore => ("docstore", format!("{}.{}.doc", context.project, name)),
+    };
+
+    let mut confirm = DestructiveOpConfirm::new();
+    if !confirm.confirm_delete(kind, &label)? {
+        logger.info("Deletion cancelled.");
+        return Ok(());
+    }
+
+    let removed = match target {
+        cli::DelTarget::Project => database.delete_project(&name)?,
+        cli::DelTarget::Workspace => database.delete_workspace(&context.project, &name)?,
+        cli::DelTarget::Keystore => database.delete_keystore(&context.project, &context.workspace, &name)?,
+        cli::DelTarget::Docstore => database.delete_docstore(&context.project, &name)?,
+    };
+
+    if removed {
+        logger.okay(&format!("Deleted {} {}", kind, label));
+    } else {
+        logger.warn(&format!("No {} {} to delete", kind, label));
+    }
+    Ok(())
+}
+
+/// Handle base selection - switches cursor.base and the context cursor's base
+pub fn handle_select_command(
+    base: String,
+    manager: &mut DatabaseManager,
+    context_manager: &mut ContextManager,
+    cursor_state: &mut bookdb::context::CursorState,
+    logger: &mut Stderr,
+) -> Result<()> {
+    logger.trace_fn("select", &format!("base: {}", base));
+
+    manager.set_active(base.clone())?;
+    cursor_state.select_base(&base);
+    context_manager.save_cursor_state(cursor_state)?;
+
+    logger.okay(&format!("Selected base '{}'", base));
+    Ok(())
+}
+
+/// Handle rebase - wipe a base and recreate an empty schema
+pub fn handle_rebase_command(
+    base: String,
+    manager: &mut DatabaseManager,
+    logger: &mut Stderr,
+) -> Result<()> {
+    logger.trace_fn("rebase", &format!("base: {}", base));
+
+    if !manager.base_exists(&base) {
+        return Err(BookdbError::Database(format!("Base not found: {}", base)));
+    }
+
+    use bookdb::context_manager::DestructiveOpConfirm;
+    let mut confirm = DestructiveOpConfirm::new();
+    if !confirm.confirm_reset(&format!("base '{}'", base))? {
+        return Ok(());
+    }
+
+    manager.recreate_base(&base)?;
+    logger.okay(&format!("Base '{}' recreated", base));
+    Ok(())
+}
+
+/// Handle unbase - delete a base file
+pub fn handle_unbase_command(
+    base: String,
+    manager: &mut DatabaseManager,
+    logger: &mut Stderr,
+) -> Result<()> {
+    logger.trace_fn("unbase", &format!("base: {}", base));
+
+    if !manager.base_exists(&base) {
+        return Err(BookdbError::Database(format!("Base not found: {}", base)));
+    }
+
+    use bookdb::context_manager::DestructiveOpConfirm;
+    let mut confirm = DestructiveOpConfirm::new();
+    if !confirm.confirm_delete("base", &base)? {
+        logger.info("Deletion cancelled.");
+        return Ok(());
+    }
+
+    manager.drop_base(&base)?;
+    logger.okay(&format!("Base '{}' deleted", base));
+    Ok(())
+}
+
+/// Handle binding an action to a context
+pub fn handle_bind_command(
+    action: String,
+    arg: Option<String>,
+    database: &Database,
+    context: &bookdb::context::ResolvedContext,
+    logger: &mut Stderr,
+) -> Result<()> {
+    logger.trace_fn("bind", &format!("action: {}, context: {}", action, context));
+
+    let kind: ActionKind = action.parse()?;
+
+    // `#` chains run from any directory: pin the template to where it was bound
+    let arg = match (kind, arg) {
+        (ActionKind::Render, Some(path)) => Some(
+            std::fs::canonicalize(&path)
+                .map_err(|e| BookdbError::Argument(format!("Template '{}': {}", path, e)))?
+                .to_string_lossy()
+                .into_owned(),
+        ),
+        (_, arg) => arg,
+    };
+    database.set_action(context, kind, arg.as_deref())?;
+    logger.okay(&format!("Bound '{}' to #{}", kind, context));
+
+    Ok(())
+}
+
+/// Handle removing an action binding
+pub fn handle_unbind_command(
+    database: &Database,
+    context: &bookdb::context::ResolvedContext,
+    logger: &mut Stderr,
+) -> Result<()> {
+    logger.trace_fn("unbind", &format!("context: {}", context));
+
+    if database.delete_action(context)? {
+        logger.okay(&format!("Unbound action from #{}", context));
+    } else {
+        logger.warn(&format!("No action bound to #{}", context));
+    }
+
+    Ok(())
+}
+
+/// Handle an Action-mode (`#`) chain by running its bound action
+pub fn handle_action_command(
+    database: &Database,
+    context: &bookdb::context::ResolvedContext,
+    logger: &mut Stderr,
+) -> Result<()> {
+    if !matches!(context.prefix_mode, bookdb::context::ChainMode::Action) {
+        return Err(BookdbError::Argument(format!(
+            "Unknown command; only '#' chains can be run directly (got {})", context
+        )));
+    }
+
+    logger.trace_fn("action", &format!("context: {}", context));
+    commands::execute_action(context, database)
+}
+
+/// Handle `find`: search keys (or, with --values, values) in one base or all of them
+pub fn handle_find_command(
+    pattern: String,
+    modes: (bool, bool, bool),
+    json: bool,
+    all_bases: bool,
+    database: &Database,
+    db_manager: &DatabaseManager,
+    logger: &mut Stderr,
+) -> Result<()> {
+    use crate::bookdb::service::db::driver::FindMatcher;
+    use bookdb::context_manager::LsTableFormatter;
+
+    let (glob, regex, values) = modes;
+    let matcher = FindMatcher::from_flags(&pattern, glob, regex, values)?;
+    logger.trace_fn("find", &format!("pattern: {}, matcher: {:?}, all bases: {}", pattern, matcher, all_bases));
+
+    let hits = if all_bases {
+        let mut hits = Vec::new();
+        for base in db_manager.list_bases()? {
+            hits.extend(db_manager.open_readonly(&base.path)?.find(&matcher)?);
+        }
+        hits
+    } else {
+        database.find(&matcher)?
+    };
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&hits)?);
+        return Ok(());
+    }
+
+    if hits.is_empty() {
+        logger.info(&format!("No matches for '{}'", pattern));
+        return Ok(());
+    }
+
+    let rows: Vec<Vec<String>> = hits.into_iter()
+        .map(|h| {
+            let mut row = vec![h.kind.to_string(), h.chain, h.key];
+            row.extend(h.value);
+            row
+        })
+        .collect();
+    let headers: &[&str] = if matcher.searches_values() {
+        &["Kind", "Chain", "Key", "Value"]
+    } else {
+        &["Kind", "Chain", "Key"]
+    };
+    LsTableFormatter::new().display_table(headers, &rows, Some(&format!("Matches for '{}'", pattern)))?;
+    Ok(())
+}
+
+/// Handle `grepd`: ranked full-text search, scoped to the chain when one is given
+pub fn handle_grepd_command(
+    query: String,
+    scoped: bool,
+    limit: usize,
+    json: bool,
+    database: &Database,
+    context: &bookdb::context::ResolvedContext,
+    logger: &mut Stderr,
+) -> Result<()> {
+    logger.trace_fn("grepd", &format!("query: {}, scope: {}", query, if scoped { context.to_string() } else { "base".to_string() }));
+
+    let scope = scoped.then_some(context);
+    // JSON keeps plain-text markers; terminals get bold hit terms
+    let highlight = if json { ("[", "]") } else { ("\x1b[1m", "\x1b[0m") };
+    let hits = database.search_documents(&query, scope, highlight, limit)?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&hits)?);
+        return Ok(());
+    }
+
+    if hits.is_empty() {
+        logger.info(&format!("No documents match '{}'", query));
+        return Ok(());
+    }
+
+    for hit in &hits {
+        println!("{}\t{}", hit.chain, hit.snippet.replace('\n', " "));
+    }
+    logger.info(&format!("{} matches", hits.len()));
+    Ok(())
+}
+
+/// Handle `mv <from> <to>`: move a namespace in its base, then follow it with the cursor
+pub fn handle_mv_command(
+    from: String,
+    to: String,
+    merge: bool,
+    force: bool,
+    config: &Config,
+    context_manager: &mut ContextManager,
+    cursor_state: &mut bookdb::context::CursorState,
+    logger: &mut Stderr,
+) -> Result<()> {
+    logger.trace_fn("mv", &format!("{} -> {} (merge: {})", from, to, merge));
+
+    // Resolve without entering: neither chain should move the cursor
+    let from = context_manager.resolve_context(&context_manager.parse_chain(&from, cursor_state)?, cursor_state);
+    let to = context_manager.resolve_context(&context_manager.parse_chain(&to, cursor_state)?, cursor_state);
+    if from.base != to.base {
+        return Err(BookdbError::Argument(format!(
+            "mv works within one base ({} vs {}); use cp across bases", from.base, to.base
+        )));
+    }
+
+    let database = open_database(config, &from.base, false)?;
+    database.set_force(force);
+    let level = database.move_namespace(&from, &to, merge)?;
+    logger.okay(&format!("Moved {} -> {}", from.qualified(), to.qualified()));
+
+    if cursor_state.retarget(&from, &to, level) {
+        context_manager.save_cursor_state(cursor_state)?;
+        logger.info("Cursor updated to follow the move");
+    }
+    Ok(())
+}
+
+/// Handle `cp`: plan the copy between two resolved chains, then apply it unless dry-running
+pub fn handle_cp_command(
+    from: String,
+    to: String,
+    (overwrite, skip): (bool, bool),
+    dry_run: bool,
+    force: bool,
+    config: &Config,
+    context_manager: &mut ContextManager,
+    cursor_state: &bookdb::context::CursorState,
+    logger: &mut Stderr,
+) -> Result<()> {
+    use crate::bookdb::service::db::driver::{ConflictPolicy, CopyAction};
+
+    let policy = match (overwrite, skip) {
+        (true, _) => ConflictPolicy::Overwrite,
+        (_, true) => ConflictPolicy::Skip,
+        _ => ConflictPolicy::Fail,
+    };
+    logger.trace_fn("cp", &format!("{} -> {} ({:?}, dry run: {})", from, to, policy, dry_run));
+
+    // Resolve without entering: neither chain should move the cursor
+    let from = context_manager.resolve_context(&context_manager.parse_chain(&from, cursor_state)?, cursor_state);
+    let to = context_manager.resolve_context(&context_manager.parse_chain(&to, cursor_state)?, cursor_state);
+
+    let source = open_database(config, &from.base, false)?;
+    let other;
+    let target = if to.base == from.base {
+        &source
+    } else {
+        other = open_database(config, &to.base, false)?;
+        &other
+    };
+    target.set_force(force);
+
+    let plan = source.copy_to(&from, target, &to, policy, dry_run)?;
+
+    let label = |action: CopyAction| match action {
+        CopyAction::Create => "create",
+        CopyAction::Overwrite => "overwrite",
+        CopyAction::Skip => "skip",
+    };
+    let rows: Vec<Vec<String>> = plan.iter()
+        .map(|item| vec![label(item.action).to_string(), item.kind.to_string(), item.from.clone(), item.to.clone()])
+        .collect();
+    let title = if dry_run { "Copy plan (dry run)" } else { "Copied" };
+    let mut formatter = bookdb::context_manager::LsTableFormatter::new();
+    formatter.display_table(&["Action", "Kind", "From", "To"], &rows, Some(title))?;
+
+    let skipped = plan.iter().filter(|item| item.action == CopyAction::Skip).count();
+    let summary = format!("{} item(s) from {} to {}, {} skipped", plan.len() - skipped, from.qualified(), to.qualified(), skipped);
+    if dry_run {
+        logger.info(&format!("Would copy {}", summary));
+    } else {
+        logger.okay(&format!("Copied {}", summary));
+    }
+    Ok(())
+}
+
+/// Handle `alias set|rm`; base aliases go to the current base, `--global` to $config_dir
+pub fn handle_alias_command(
+    action: cli::AliasAction,
+    config: &Config,
+    database: &Database,
+    context: &bookdb::context::ResolvedContext,
+    logger: &mut Stderr,
+) -> Result<()> {
+    use crate::bookdb::service::ctx::alias::{
+        validate_alias_name, validate_alias_chain, load_global_aliases, save_global_aliases,
+    };
+
+    match action {
+        cli::AliasAction::Set { name, chain, global } => {
+            logger.trace_fn("alias", &format!("set :{} -> {} (global: {})", name, chain, global));
+            validate_alias_name(&name)?;
+            validate_alias_chain(&chain, &context.base)?;
+
+            if global {
+                let path = config.get_aliases_file_path();
+                let mut aliases = load_global_aliases(&path)?;
+                aliases.insert(name.clone(), chain.clone());
+                save_global_aliases(&path, &aliases)?;
+                logger.okay(&format!("Global alias :{} -> {}", name, chain));
+            } else {
+                database.set_alias(&name, &chain)?;
+                logger.okay(&format!("Alias :{} -> {} (base '{}')", name, chain, database.base_name));
+            }
+        }
+        cli::AliasAction::Rm { name, global } => {
+            logger.trace_fn("alias", &format!("rm :{} (global: {})", name, global));
+
+            let removed = if global {
+                let path = config.get_aliases_file_path();
+                let mut aliases = load_global_aliases(&path)?;
+                let removed = aliases.remove(&name).is_some();
+                if removed {
+                    save_global_aliases(&path, &aliases)?;
+                }
+                removed
+            } else {
+                database.delete_alias(&name)?
+            };
+
+            if removed {
+                logger.okay(&format!("Removed alias :{}", name));
+            } else {
+                logger.warn(&format!("No such alias :{}", name));
+            }
+        }
+    }
+
+    Ok(())
+}
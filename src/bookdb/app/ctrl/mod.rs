@@ -2,6 +2,3 @@
 pub mod cli;
 pub mod handlers;
 pub mod dispatch;
-
-
-use crate::handlers::*;
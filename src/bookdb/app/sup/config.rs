@@ -80,7 +80,7 @@ pub fn resolve_paths() -> Paths {
     }
 }
 
-pub fn ensure_dirs(paths: &Paths) -> std::io:: Result<(), E> {
+pub fn ensure_dirs(paths: &Paths) -> std::io::Result<()> {
     std::fs::create_dir_all(&paths.data_dir)?;
     std::fs::create_dir_all(&paths.config_dir)?;
     Ok(())
@@ -91,3 +91,84 @@ pub fn default_home_db_path(data_dir: &Path) -> PathBuf {
     p.push("home.sqlite3");
     p
 }
+
+/// Data/config directory pair used by the application config
+#[derive(Debug, Clone)]
+pub struct XdgDirs {
+    data_dir: PathBuf,
+    config_dir: PathBuf,
+}
+
+impl XdgDirs {
+    /// Root both directories under `root` (used for isolated installs and tests)
+    pub fn new(root: &Path) -> std::io::Result<Self> {
+        let dirs = Self {
+            data_dir: root.join("data"),
+            config_dir: root.join("config"),
+        };
+        std::fs::create_dir_all(&dirs.data_dir)?;
+        std::fs::create_dir_all(&dirs.config_dir)?;
+        Ok(dirs)
+    }
+
+    pub fn from_paths(paths: &Paths) -> Self {
+        Self {
+            data_dir: paths.data_dir.clone(),
+            config_dir: paths.config_dir.clone(),
+        }
+    }
+
+    pub fn data_dir(&self) -> &Path {
+        &self.data_dir
+    }
+
+    pub fn config_dir(&self) -> &Path {
+        &self.config_dir
+    }
+}
+
+/// Application config: where bases and cursor files live
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub xdg: XdgDirs,
+}
+
+impl Config {
+    /// Resolve directories from the environment and make sure they exist
+    pub fn load() -> std::io::Result<Self> {
+        let paths = resolve_paths();
+        ensure_dirs(&paths)?;
+        Ok(Self { xdg: XdgDirs::from_paths(&paths) })
+    }
+
+    /// Path of the base database file for `base`
+    pub fn get_base_path(&self, base: &str) -> PathBuf {
+        self.xdg.data_dir().join(format!("{}.sqlite3", base))
+    }
+
+    /// JSON cursor state ($config_dir/cursor.json)
+    pub fn get_cursor_file_path(&self) -> PathBuf {
+        self.xdg.config_dir().join("cursor.json")
+    }
+
+    /// Plain-text chain cursor ($config_dir/cursor.chain)
+    pub fn get_cursor_chain_path(&self) -> PathBuf {
+        self.xdg.config_dir().join("cursor.chain")
+    }
+
+    /// Plain-text base cursor holding the absolute db path ($config_dir/cursor.base)
+    pub fn get_cursor_base_path(&self) -> PathBuf {
+        self.xdg.config_dir().join("cursor.base")
+    }
+
+    /// Global chain aliases shared by every base ($config_dir/aliases.json)
+    pub fn get_aliases_file_path(&self) -> PathBuf {
+        self.xdg.config_dir().join("aliases.json")
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self { xdg: XdgDirs::from_paths(&resolve_paths()) }
+    }
+}
@@ -9,6 +9,14 @@ pub enum BookdbError {
     Argument(String),
     #[error("context error: {0}")]
     ContextParse(String),
+    #[error("invalid context: {0}")]
+    InvalidContext(String),
+    #[error("parse error: {0}")]
+    Parse(String),
+    #[error("config error: {0}")]
+    ConfigParse(String),
+    #[error("not installed: {0}")]
+    NotInstalled(String),
     #[error("not found: {0}")]
     KeyNotFound(String),
     #[error("database error: {0}")]
@@ -19,6 +27,18 @@ pub enum BookdbError {
     NumericOverflow,
     #[error("key not found for numeric operation: {0}")]
     NumericKeyNotFound(String),
+    #[error("type error: key '{0}' expects {1}, got '{2}'")]
+    TypeMismatch(String, String, String),
+    #[error("precondition failed: {0}")]
+    PreconditionFailed(String),
+    #[error("passphrase required: {0}")]
+    PassphraseRequired(String),
+    #[error("encryption error: {0}")]
+    Crypto(String),
+    #[error("locked: {0}")]
+    Locked(String),
+    #[error("not implemented: {0}")]
+    NotImplemented(String),
     #[error(transparent)]
     Sql(#[from] rusqlite::Error),
     #[error(transparent)]
@@ -27,4 +47,15 @@ pub enum BookdbError {
     Serde(#[from] serde_json::Error),
 }
 
+impl BookdbError {
+    /// Process exit code: 3 for a failed `--if-*`/`--expect` precondition
+    /// (2 is taken by usage errors), 1 for everything else
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            BookdbError::PreconditionFailed(_) => 3,
+            _ => 1,
+        }
+    }
+}
+
 // Added numeric error types for inc/dec operations
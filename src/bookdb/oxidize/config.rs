@@ -6,7 +6,7 @@ use crate::cli::Cli;
 /// This struct maintains the runtime configuration derived from CLI flags
 /// and provides methods for checking message visibility levels according
 /// to BashFX QUIET(n) specifications.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct OxidexConfig {
     // === BashFX Standard Modes ===
     pub debug: bool,        // First-level verbose (info, warn, okay)
@@ -46,7 +46,7 @@ impl OxidexConfig {
             
             // BookDB-specific
             db_path: cli.db_path.clone(),
-            base_context: cli.base_context.clone(),
+            base_context: cli.base.clone(),
         }
     }
     
@@ -84,12 +84,12 @@ impl OxidexConfig {
     }
     
     /// Get effective database path with fallback
-    pub fn get_db_path<'a>(&self, default: &'a str) -> &'a str {
+    pub fn get_db_path<'a>(&'a self, default: &'a str) -> &'a str {
         self.db_path.as_deref().unwrap_or(default)
     }
     
     /// Get effective base context with fallback
-    pub fn get_base_context<'a>(&self, default: &'a str) -> &'a str {
+    pub fn get_base_context<'a>(&'a self, default: &'a str) -> &'a str {
         self.base_context.as_deref().unwrap_or(default)
     }
     
@@ -109,30 +109,13 @@ impl OxidexConfig {
             (true, _, _, _) => 1,           // QUIET(1): Explicit quiet mode
             (false, false, false, false) => 1, // QUIET(1): Semi-quiet default
             (false, true, false, _) => 2,   // QUIET(2): Debug mode
-            (false, _, true, _) => 3,       // QUIET(3): Trace mode  
             (false, _, _, true) => 4,       // QUIET(4): Dev mode
+            (false, _, true, _) => 3,       // QUIET(3): Trace mode  
         }
         // Note: QUIET(0) is only achievable via environment variable
     }
 }
 
-impl Default for OxidexConfig {
-    fn default() -> Self {
-        Self {
-            debug: false,
-            trace: false,
-            quiet: false,
-            force: false,
-            yes: false,
-            dev: false,
-            dry_run: false,
-            no_color: false,
-            json: false,
-            db_path: None,
-            base_context: None,
-        }
-    }
-}
 
 #[cfg(test)]
 mod tests {
@@ -151,8 +134,8 @@ mod tests {
             no_color: false,
             json: false,
             db_path: None,
-            base_context: None,
-            command: Commands::Stats { context_chain: None },
+            base: None,
+            command: Some(Commands::Status {}),
         }
     }
     
@@ -228,7 +211,7 @@ mod tests {
     fn test_bookdb_specific_config() {
         let mut cli = mock_cli_with_flags(false, false, false, false);
         cli.db_path = Some("/custom/path.db".to_string());
-        cli.base_context = Some("custom_base".to_string());
+        cli.base = Some("custom_base".to_string());
         
         let config = OxidexConfig::from_cli(&cli);
         
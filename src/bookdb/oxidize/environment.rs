@@ -41,7 +41,7 @@ pub fn setup_environment_from_flags(cli: &Cli) {
     
     // === BookDB-Specific Environment Variables ===
     if cli.dry_run {
-        std::env::set_var("BOOKDB_DRY_RUN", "1");
+        std::env::set_var("BOOKDB_DRY_RUN", "0");
     }
     
     if cli.no_color {
@@ -49,7 +49,7 @@ pub fn setup_environment_from_flags(cli: &Cli) {
     }
     
     if cli.json {
-        std::env::set_var("BOOKDB_JSON_OUTPUT", "1");
+        std::env::set_var("BOOKDB_JSON_OUTPUT", "0");
     }
     
     // Database configuration
@@ -57,7 +57,7 @@ pub fn setup_environment_from_flags(cli: &Cli) {
         std::env::set_var("BOOKDB_DATABASE_PATH", db_path);
     }
     
-    if let Some(ref base) = cli.base_context {
+    if let Some(ref base) = cli.base {
         std::env::set_var("BOOKDB_BASE_CONTEXT", base);
     }
 }
@@ -115,21 +115,6 @@ pub fn get_current_quiet_level() -> u8 {
     // Note: QUIET(0) would require explicit QUIET_MODE=0 AND special handling
 }
 
-/// Print environment diagnostic info (for debugging ODX setup)
-pub fn print_environment_status() {
-    eprintln!("=== ODX Environment Status ===");
-    eprintln!("DEBUG_MODE: {}", get_env_flag("DEBUG_MODE"));
-    eprintln!("TRACE_MODE: {}", get_env_flag("TRACE_MODE"));
-    eprintln!("QUIET_MODE: {}", get_env_flag("QUIET_MODE"));
-    eprintln!("FORCE_MODE: {}", get_env_flag("FORCE_MODE"));
-    eprintln!("YES_MODE: {}", get_env_flag("YES_MODE"));
-    eprintln!("DEV_MODE: {}", get_env_flag("DEV_MODE"));
-    eprintln!("Current Log Level: {}", get_current_log_level());
-    eprintln!("Current QUIET Level: QUIET({})", get_current_quiet_level());
-    eprintln!("Is Dev Mode: {}", is_dev_mode());
-    eprintln!("==============================");
-}
-
 /// Check if BookDB-specific modes are enabled
 pub fn is_dry_run_mode() -> bool {
     get_env_flag("BOOKDB_DRY_RUN")
@@ -139,8 +124,9 @@ pub fn is_json_output_mode() -> bool {
     get_env_flag("BOOKDB_JSON_OUTPUT")
 }
 
+/// NO_COLOR follows no-color.org: set to anything means no color
 pub fn is_no_color_mode() -> bool {
-    get_env_flag("NO_COLOR")
+    std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
 }
 
 /// Get BookDB-specific configuration from environment
@@ -152,6 +138,20 @@ pub fn get_bookdb_base_context() -> Option<String> {
     std::env::var("BOOKDB_BASE_CONTEXT").ok()
 }
 
+/// Serialize tests that touch the process environment, starting each from a clean slate
+#[cfg(test)]
+pub(crate) fn env_test_lock() -> std::sync::MutexGuard<'static, ()> {
+    static LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+    let guard = LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    for var in [
+        "DEBUG_MODE", "TRACE_MODE", "QUIET_MODE", "FORCE_MODE", "YES_MODE", "DEV_MODE",
+        "BOOKDB_DRY_RUN", "NO_COLOR", "BOOKDB_JSON_OUTPUT", "BOOKDB_DATABASE_PATH", "BOOKDB_BASE_CONTEXT",
+    ] {
+        std::env::remove_var(var);
+    }
+    guard
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -169,13 +169,14 @@ mod tests {
             no_color: false,
             json: false,
             db_path: None,
-            base_context: None,
-            command: Commands::Stats { context_chain: None },
+            base: None,
+            command: Some(Commands::Status {}),
         }
     }
     
     #[test]
     fn test_debug_flag_sets_environment() {
+        let _env = env_test_lock();
         // Clear any existing env vars
         std::env::remove_var("DEBUG_MODE");
         
@@ -192,6 +193,7 @@ mod tests {
     
     #[test]
     fn test_trace_enables_debug() {
+        let _env = env_test_lock();
         // Clear any existing env vars
         std::env::remove_var("DEBUG_MODE");
         std::env::remove_var("TRACE_MODE");
@@ -209,6 +211,7 @@ mod tests {
     
     #[test]
     fn test_dev_flag_enables_both() {
+        let _env = env_test_lock();
         // Clear any existing env vars
         std::env::remove_var("DEBUG_MODE");
         std::env::remove_var("TRACE_MODE");
@@ -227,6 +230,7 @@ mod tests {
     
     #[test]
     fn test_bashfx_env_pattern() {
+        let _env = env_test_lock();
         // Test BashFX pattern: "0" means enabled
         std::env::set_var("TEST_FLAG", "0");
         assert!(get_env_flag("TEST_FLAG"));
@@ -243,6 +247,7 @@ mod tests {
     
     #[test]
     fn test_quiet_level_priority() {
+        let _env = env_test_lock();
         // Clear environment
         std::env::remove_var("DEBUG_MODE");
         std::env::remove_var("TRACE_MODE");
@@ -270,6 +275,7 @@ mod tests {
     
     #[test]
     fn test_bookdb_specific_environment() {
+        let _env = env_test_lock();
         let mut cli = mock_cli();
         cli.dry_run = true;
         cli.json = true;
@@ -193,8 +193,8 @@ mod tests {
         Cli {
             debug, trace, quiet, dev,
             force: false, yes: false, dry_run: false, no_color: false, json: false,
-            db_path: None, base_context: None,
-            command: Commands::Stats { context_chain: None },
+            db_path: None, base: None,
+            command: Some(Commands::Status {}),
         }
     }
     
@@ -19,7 +19,6 @@ pub use environment::{
     get_env_string,
     is_dev_mode,
     get_current_log_level,
-    print_environment_status,
 };
 pub use flags::BashFxFlags;
 
@@ -77,13 +76,14 @@ mod tests {
             no_color: false,
             json: false,
             db_path: None,
-            base_context: None,
-            command: Commands::Stats { context_chain: None },
+            base: None,
+            command: Some(Commands::Status {}),
         }
     }
     
     #[test]
     fn test_odx_initialization() {
+        let _env = environment::env_test_lock();
         let cli = mock_cli();
         let config = init_from_cli(&cli);
         
@@ -103,6 +103,7 @@ mod tests {
     
     #[test]
     fn test_odx_integration() {
+        let _env = environment::env_test_lock();
         let cli = mock_cli();
         let _config = init_from_cli(&cli);
         
@@ -113,6 +114,7 @@ mod tests {
     
     #[test]
     fn test_oxidize_operation() {
+        let _env = environment::env_test_lock();
         let cli = mock_cli();
         let config = init_from_cli(&cli);
         
@@ -122,7 +124,7 @@ mod tests {
         assert_eq!(result.unwrap(), 42);
         
         // Test failed operation
-        let error_result = oxidize_operation(|| Err("test error".into()), &config);
+        let error_result = oxidize_operation::<_, i32>(|| Err("test error".into()), &config);
         assert!(error_result.is_err());
     }
 }
@@ -0,0 +1,174 @@
+// src/commands/action.rs - Run the action bound to a `#` context chain
+//
+// FEATURES:
+// 1. `#proj.ws.var.store` looks up the binding in the base's actions table
+// 2. dump   - print the context (KEY=VALUE for vars, documents for docs)
+// 3. render - fill `{{KEY}}` placeholders in a template file, print to stdout
+// 4. export - write the context to a file (format from extension)
+
+use crate::error::{Result, BookdbError};
+use crate::bookdb::service::ctx::{Anchor, ResolvedContext};
+use crate::bookdb::service::db::Database;
+use crate::bookdb::service::db::driver::action::ActionKind;
+use stderr::{Stderr, StderrConfig};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Execute the action bound to the resolved context
+pub fn execute(context: &ResolvedContext, database: &Database) -> Result<()> {
+    let mut logger = Stderr::new();
+    logger.trace_fn("action", &format!("running action for context: {}", context));
+
+    let binding = database.get_action(context)?
+        .ok_or_else(|| BookdbError::KeyNotFound(format!("no action bound to #{}", context)))?;
+
+    logger.trace_fn("action", &format!("bound action: {}", binding.kind));
+
+    match binding.kind {
+        ActionKind::Dump => dump(context, database),
+        ActionKind::Render => {
+            let template_path = binding.arg.as_deref().unwrap_or_default();
+            let template = fs::read_to_string(template_path)?;
+            let variables = database.list_variables(context)?;
+            print!("{}", render_template(&template, &variables));
+            Ok(())
+        }
+        ActionKind::Export => {
+            let file_path = binding.arg.as_deref().unwrap_or_default();
+            super::export::execute(
+                Path::new(file_path),
+                None,
+                (None, None, None, None, None, None),
+                context,
+                database,
+            )
+        }
+    }
+}
+
+/// Print the whole context to stdout
+fn dump(context: &ResolvedContext, database: &Database) -> Result<()> {
+    match context.anchor {
+        Anchor::Var => {
+            let variables = database.list_variables(context)?;
+            let mut keys: Vec<&String> = variables.keys().collect();
+            keys.sort();
+            for key in keys {
+                println!("{}={}", key, variables[key]);
+            }
+        }
+        Anchor::Doc => {
+            for doc_key in database.list_documents(context)? {
+                let content = database.get_document(&doc_key, context)?.unwrap_or_default();
+                println!("# {}", doc_key);
+                println!("{}", content);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Replace `{{KEY}}` placeholders; unknown keys are left as-is
+pub fn render_template(template: &str, variables: &HashMap<String, String>) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        match after.find("}}") {
+            Some(end) => {
+                let key = after[..end].trim();
+                match variables.get(key) {
+                    Some(value) => output.push_str(value),
+                    None => output.push_str(&rest[start..start + 2 + end + 2]),
+                }
+                rest = &after[end + 2..];
+            }
+            None => {
+                output.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+    output.push_str(rest);
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bookdb::service::ctx::ChainMode;
+    use tempfile::TempDir;
+
+    fn create_test_context() -> ResolvedContext {
+        ResolvedContext {
+            base: "test".to_string(),
+            project: "deploy".to_string(),
+            workspace: "prod".to_string(),
+            anchor: Anchor::Var,
+            tail: "app".to_string(),
+            prefix_mode: ChainMode::Action,
+        }
+    }
+
+    fn create_test_db() -> (Database, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let db = Database::create_or_open(&db_path).unwrap();
+        (db, temp_dir)
+    }
+
+    #[test]
+    fn test_render_template() {
+        let mut vars = HashMap::new();
+        vars.insert("HOST".to_string(), "example.com".to_string());
+        vars.insert("PORT".to_string(), "443".to_string());
+
+        assert_eq!(render_template("https://{{HOST}}:{{ PORT }}/", &vars), "https://example.com:443/");
+        assert_eq!(render_template("{{MISSING}} stays", &vars), "{{MISSING}} stays");
+        assert_eq!(render_template("open {{HOST", &vars), "open {{HOST");
+    }
+
+    #[test]
+    fn test_unbound_context_fails() {
+        let (db, _temp) = create_test_db();
+        let context = create_test_context();
+
+        assert!(matches!(execute(&context, &db), Err(BookdbError::KeyNotFound(_))));
+    }
+
+    #[test]
+    fn test_export_action_writes_file() -> Result<()> {
+        let (db, temp) = create_test_db();
+        let context = create_test_context();
+        let out = temp.path().join("app.kv");
+
+        db.set_variable("HOST", "example.com", &context)?;
+        db.set_action(&context, ActionKind::Export, Some(out.to_str().unwrap()))?;
+
+        execute(&context, &db)?;
+        assert!(fs::read_to_string(&out)?.contains("HOST=example.com"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rebinding_replaces_action() -> Result<()> {
+        let (db, _temp) = create_test_db();
+        let context = create_test_context();
+
+        db.set_action(&context, ActionKind::Dump, None)?;
+        db.set_action(&context, ActionKind::Render, Some("tpl.txt"))?;
+
+        let binding = db.get_action(&context)?.unwrap();
+        assert_eq!(binding.kind, ActionKind::Render);
+        assert_eq!(db.list_actions()?.len(), 1);
+
+        assert!(db.set_action(&context, ActionKind::Export, None).is_err());
+
+        Ok(())
+    }
+}
@@ -3,6 +3,7 @@
 use crate::error::{Result, BookdbError};
 use crate::ctx::ResolvedContext;
 use crate::bookdb::service::db::Database;
+use crate::bookdb::service::db::driver::{CounterOptions, Number};
 use stderr::{Stderr, StderrConfig};
 
 /// Execute decrement command: atomically decrement a numeric variable
@@ -11,7 +12,18 @@ pub fn execute(
     amount: i64,
     context: &ResolvedContext,
     database: &Database,
-) ->  Result<(), E> {
+) -> Result<()> {
+    execute_with(key, Number::Int(amount), &CounterOptions::default(), context, database)
+}
+
+/// Execute decrement with counter options (`--float`, `--min/--max`, `--wrap`, `--init`)
+pub fn execute_with(
+    key: String,
+    amount: Number,
+    options: &CounterOptions,
+    context: &ResolvedContext,
+    database: &Database,
+) -> Result<()> {
     let mut logger = Stderr::new();
     logger.trace_fn("dec", &format!("decrementing key '{}' by {} in context: {}", key, amount, context));
     
@@ -21,7 +33,7 @@ pub fn execute(
     }
     
     // Perform atomic decrement
-    match database.decrement_variable(&key, amount, context) {
+    match database.step_counter(&key, amount.negated()?, options, context) {
         Ok(new_value) => {
             // Success - show the new value
             logger.okay(&format!("Decremented '{}' by {}: {}", key, amount, new_value));
@@ -34,10 +46,14 @@ pub fn execute(
             Err(BookdbError::NonNumericValue(key, value))
         }
         Err(BookdbError::NumericOverflow) => {
-            logger.error(&format!("Cannot decrement: Operation would cause underflow"));
+            logger.error("Cannot decrement: Operation would cause underflow");
             logger.info(&format!("Key '{}' is at minimum value for decrement by {}", key, amount));
             Err(BookdbError::NumericOverflow)
         }
+        Err(e @ BookdbError::PreconditionFailed(_)) => {
+            logger.warn(&format!("Not decremented: {}", e));
+            std::process::exit(e.exit_code());
+        }
         Err(e) => {
             logger.error(&format!("Failed to decrement '{}': {}", key, e));
             Err(e)
@@ -48,7 +64,7 @@ pub fn execute(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::bookdb::service::ctx::ResolvedContext;
+    use crate::bookdb::service::ctx::{Anchor, ChainMode, ResolvedContext};
     use tempfile::NamedTempFile;
     
     fn create_test_db() -> (Database, NamedTempFile) {
@@ -59,10 +75,12 @@ mod tests {
     
     fn create_test_context() -> ResolvedContext {
         ResolvedContext {
-            base_name: "test".to_string(),
-            project_name: "TEST_PROJECT".to_string(),
-            workspace_name: "TEST_WORKSPACE".to_string(),
-            keystore_name: "TEST_KEYSTORE".to_string(),
+            base: "test".to_string(),
+            project: "TEST_PROJECT".to_string(),
+            workspace: "TEST_WORKSPACE".to_string(),
+            anchor: Anchor::Var,
+            tail: "TEST_KEYSTORE".to_string(),
+            prefix_mode: ChainMode::Persistent,
         }
     }
     
@@ -4,7 +4,7 @@ use crate::bookdb::app::sup::error::{Result, BookdbError};
 use crate::bookdb::service::ctx::types::typesV1::ResolvedContext;
 use crate::bookdb::service::db::driver::core::Database;
 
-pub fn execute<E>(
+pub fn execute(
     key: &str,
     context: &ResolvedContext,
     database: &Database,
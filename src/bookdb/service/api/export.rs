@@ -5,14 +5,15 @@
 // 2. Supports multiple formats (JSON, key-value)
 // 3. Progress tracking for large exports
 // 4. Rich error handling and user feedback
+// 5. Wildcard chains export every match keyed by fully-qualified chain
 
 use crate::error::{Result, BookdbError};
 use crate::bookdb::service::ctx::ResolvedContext;
-use crate::bookdb::service::db::Database;
-use crate::rdx::stderr::{Stderr, StderrConfig};
+use crate::bookdb::service::db::{Database, ExportItem};
+use stderr::{Stderr, StderrConfig};
 use std::path::Path;
 use std::fs;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// Execute export command: export variables to file
 pub fn execute(
@@ -21,10 +22,14 @@ pub fn execute(
     _filters: (Option<&str>, Option<&str>, Option<&str>, Option<&str>, Option<&str>, Option<&str>),
     context: &ResolvedContext,
     database: &Database,
-) ->  Result<(), E> {
+) -> Result<()> {
     let mut logger = Stderr::new();
     logger.trace_fn("export", &format!("exporting from context: {} to file: {:?}", context, file_path));
     
+    if context.is_wildcard() {
+        return execute_wildcard(file_path, format, context, database, &mut logger);
+    }
+    
     // Get all variables from the context
     let variables = database.list_variables(context)?;
     
@@ -57,8 +62,59 @@ pub fn execute(
     Ok(())
 }
 
+/// Export every context a wildcard chain matches, one entry per chain
+fn execute_wildcard(
+    file_path: &Path,
+    format: Option<&str>,
+    context: &ResolvedContext,
+    database: &Database,
+    logger: &mut Stderr,
+) -> Result<()> {
+    let items = database.export_wildcard(context, None)?;
+    
+    if items.is_empty() {
+        logger.warn(&format!("Nothing to export from contexts matching: {}", context));
+        return Ok(());
+    }
+    
+    let export_format = determine_format(file_path, format)?;
+    let content = match export_format {
+        ExportFormat::Json => export_tagged_as_json(&items)?,
+        ExportFormat::KeyValue => {
+            // `chain:KEY=value` so entries from different keystores stay apart
+            let tagged: HashMap<String, String> = items
+                .iter()
+                .map(|item| (format!("{}:{}", item.context, item.key), item.value.clone()))
+                .collect();
+            export_as_key_value(&tagged)?
+        }
+    };
+    
+    fs::write(file_path, content)?;
+    
+    logger.okay(&format!(
+        "Exported {} items from {} to {} ({})",
+        items.len(),
+        context,
+        file_path.display(),
+        export_format.name()
+    ));
+    
+    Ok(())
+}
+
+/// Export wildcard matches as JSON, grouped by fully-qualified chain
+fn export_tagged_as_json(items: &[ExportItem]) -> Result<String> {
+    let mut grouped: BTreeMap<&str, BTreeMap<&str, &str>> = BTreeMap::new();
+    for item in items {
+        grouped.entry(item.context.as_str()).or_default().insert(item.key.as_str(), item.value.as_str());
+    }
+    serde_json::to_string_pretty(&grouped)
+        .map_err(|e| BookdbError::Io(std::io::Error::other(format!("JSON serialization failed: {}", e))))
+}
+
 #[derive(Debug, Clone, Copy)]
-enum ExportFormat {
+pub enum ExportFormat {
     Json,
     KeyValue,
 }
@@ -97,7 +153,7 @@ pub fn determine_format(file_path: &Path, explicit_format: Option<&str>) -> Resu
 /// Export variables as JSON format
 pub fn export_as_json(variables: &HashMap<String, String>) -> Result<String> {
     serde_json::to_string_pretty(variables)
-        .map_err(|e| BookdbError::Io(format!("JSON serialization failed: {}", e)))
+        .map_err(|e| BookdbError::Io(std::io::Error::other(format!("JSON serialization failed: {}", e))))
 }
 
 /// Export variables as key-value format
@@ -143,7 +199,7 @@ mod tests {
     fn create_test_db_with_data() -> (Database, TempDir) {
         let temp_dir = TempDir::new().unwrap();
         let db_path = temp_dir.path().join("test.db");
-        let mut db = Database::create_or_open(&db_path).unwrap();
+        let db = Database::create_or_open(&db_path).unwrap();
         
         let context = create_test_context();
         
@@ -156,7 +212,7 @@ mod tests {
     }
     
     #[test]
-    fn test_export_json() ->  Result<(), E> {
+    fn test_export_json() -> Result<()> {
         let (db, _temp) = create_test_db_with_data();
         let context = create_test_context();
         
@@ -179,7 +235,7 @@ mod tests {
     }
     
     #[test]
-    fn test_export_key_value() ->  Result<(), E> {
+    fn test_export_key_value() -> Result<()> {
         let (db, _temp) = create_test_db_with_data();
         let context = create_test_context();
         
@@ -202,7 +258,32 @@ mod tests {
     }
     
     #[test]
-    fn test_format_inference() ->  Result<(), E> {
+    fn test_export_wildcard() ->  Result<()> {
+        let (db, _temp) = create_test_db_with_data();
+        let prod = create_test_context().with_target("myapp", "prod", "settings");
+        db.set_variable("API_KEY", "prod-secret", &prod)?;
+        
+        let wildcard = create_test_context().with_target("myapp", "*", "settings");
+        let output_file = NamedTempFile::new().unwrap();
+        
+        execute(
+            output_file.path(),
+            Some("json"),
+            (None, None, None, None, None, None),
+            &wildcard,
+            &db,
+        )?;
+        
+        let content = fs::read_to_string(output_file.path())?;
+        let parsed: serde_json::Value = serde_json::from_str(&content)?;
+        assert_eq!(parsed["test@myapp.prod.var.settings"]["API_KEY"], "prod-secret");
+        assert_eq!(parsed["test@myapp.config.var.settings"]["API_KEY"], "secret123");
+        
+        Ok(())
+    }
+    
+    #[test]
+    fn test_format_inference() -> Result<()> {
         // Test JSON inference from .json extension
         let json_format = determine_format(Path::new("test.json"), None)?;
         assert!(matches!(json_format, ExportFormat::Json));
@@ -219,7 +300,7 @@ mod tests {
     }
     
     #[test]
-    fn test_export_empty_context() ->  Result<(), E> {
+    fn test_export_empty_context() -> Result<()> {
         let temp_dir = TempDir::new().unwrap();
         let db_path = temp_dir.path().join("empty.db");
         let db = Database::create_or_open(&db_path).unwrap();
@@ -242,10 +323,10 @@ mod tests {
     }
     
     #[test]
-    fn test_special_characters_in_values() ->  Result<(), E> {
+    fn test_special_characters_in_values() -> Result<()> {
         let temp_dir = TempDir::new().unwrap();
         let db_path = temp_dir.path().join("test.db");
-        let mut db = Database::create_or_open(&db_path).unwrap();
+        let db = Database::create_or_open(&db_path).unwrap();
         let context = create_test_context();
         
         // Add variables with special characters
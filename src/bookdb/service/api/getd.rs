@@ -1,10 +1,10 @@
 use crate::error::{Result, BookdbError};
-use crate::bookdb::service::ctx::ResolvedContext;
+use crate::bookdb::service::ctx::{split_doc_path, Anchor, ResolvedContext};
 use crate::bookdb::service::db::Database;
 use std::io::Write;
 
-pub fn execute(dik: &str, context: &ResolvedContext, db: &Database) ->  Result<(), E> {
-    let (doc_key, seg_path) = dik.split_once('.').unwrap_or((dik, "_root"));
+pub fn execute(dik: Option<&str>, context: &ResolvedContext, db: &Database) -> Result<()> {
+    let (doc_key, seg_path) = doc_target(dik, context)?;
     
     match db.get_doc_segment(doc_key, seg_path, context)? {
         Some((bytes, mime)) => {
@@ -16,6 +16,19 @@ pub fn execute(dik: &str, context: &ResolvedContext, db: &Database) ->  Result<(
             }
             Ok(())
         }
-        None => Err(BookdbError::KeyNotFound(format!("{}.{}", doc_key, seg_path))),
+        None => Err(BookdbError::KeyNotFound(format!("{}/{}", doc_key, seg_path))),
+    }
+}
+
+/// Resolve (doc_key, segment_path) from an explicit dik or the doc chain tail
+///
+/// `@proj.ws.doc.README/header` addresses the segment directly, so the dik can
+/// be omitted on doc chains.
+pub fn doc_target<'a>(dik: Option<&'a str>, context: &'a ResolvedContext) -> Result<(&'a str, &'a str)> {
+    match dik {
+        Some(dik) => Ok(split_doc_path(dik)),
+        None if context.anchor == Anchor::Doc => Ok((context.doc_key(), context.segment_path())),
+        None => Err(BookdbError::Argument(
+            "No document given: pass doc_key[/segment] or use a doc chain".to_string())),
     }
 }
@@ -5,14 +5,15 @@
 // 2. Script-friendly output (value only to stdout)
 // 3. Rich stderr logging with tracing
 // 4. Proper error handling for missing keys
+// 5. Wildcard chains print every match as `<chain>\t<value>`
 
 use crate::error::{Result, BookdbError};
 use crate::bookdb::service::ctx::ResolvedContext;
 use crate::bookdb::service::db::Database;
-use crate::rdx::stderr::{Stderr, StderrConfig};
+use stderr::{Stderr, StderrConfig};
 
 /// Execute getv command: retrieve variable value
-pub fn execute(key: &str, context: &ResolvedContext, database: &Database) ->  Result<(), E> {
+pub fn execute(key: &str, context: &ResolvedContext, database: &Database) -> Result<()> {
     let mut logger = Stderr::new();
     logger.trace_fn("getv", &format!("retrieving key: {} from context: {}", key, context));
     
@@ -21,6 +22,10 @@ pub fn execute(key: &str, context: &ResolvedContext, database: &Database) ->  Re
         return Err(BookdbError::Argument("Key cannot be empty".to_string()));
     }
     
+    if context.is_wildcard() {
+        return execute_wildcard(key, context, database, &mut logger);
+    }
+    
     // Get variable from database
     match database.get_variable(key, context)? {
         Some(value) => {
@@ -39,6 +44,30 @@ pub fn execute(key: &str, context: &ResolvedContext, database: &Database) ->  Re
     }
 }
 
+/// Look the key up in every context a wildcard chain matches
+///
+/// "Where is DB_PASSWORD set?" - one line per keystore that has the key,
+/// tagged with its fully-qualified chain.
+fn execute_wildcard(key: &str, context: &ResolvedContext, database: &Database, logger: &mut Stderr) -> Result<()> {
+    let targets = database.expand_wildcard(context)?;
+    logger.trace_fn("getv", &format!("wildcard {} matched {} keystores", context, targets.len()));
+    
+    let mut found = 0;
+    for target in &targets {
+        if let Some(value) = database.get_variable(key, target)? {
+            println!("{}\t{}", target.qualified(), value);
+            found += 1;
+        }
+    }
+    
+    if found == 0 {
+        logger.warn(&format!("Key '{}' not found in any context matching: {}", key, context));
+        std::process::exit(1);
+    }
+    
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -65,8 +94,8 @@ mod tests {
     }
     
     #[test]
-    fn test_getv_existing_key() ->  Result<(), E> {
-        let (mut db, _temp) = create_test_db();
+    fn test_getv_existing_key() -> Result<()> {
+        let (db, _temp) = create_test_db();
         let context = create_test_context();
         
         // Set up test data
@@ -84,14 +113,37 @@ mod tests {
         let (db, _temp) = create_test_db();
         let context = create_test_context();
         
-        // This should exit with code 1, but in tests we can't easily test std::process::exit
-        // In practice, the missing key path would be triggered
-        let result = std::panic::catch_unwind(|| {
-            execute("MISSING_KEY", &context, &db)
-        });
+        // execute() exits with code 1 for a missing key (script compatibility),
+        // which a test cannot observe; check the lookup that takes that path
+        assert_eq!(db.get_variable("MISSING_KEY", &context).unwrap(), None);
+    }
+    
+    #[test]
+    fn test_wildcard_fans_out_across_workspaces() -> Result<()> {
+        let (db, _temp) = create_test_db();
+        let prod = create_test_context().with_target("myapp", "prod", "secrets");
+        let staging = create_test_context().with_target("myapp", "staging", "secrets");
+        let other = create_test_context().with_target("myapp", "prod", "config");
+        
+        db.set_variable("DB_PASSWORD", "p1", &prod)?;
+        db.set_variable("DB_PASSWORD", "s1", &staging)?;
+        db.set_variable("DB_PASSWORD", "c1", &other)?;
+        
+        let wildcard = create_test_context().with_target("myapp", "*", "secrets");
+        let targets = database_chains(&db.expand_wildcard(&wildcard)?);
+        assert_eq!(targets, vec!["test@myapp.prod.var.secrets", "test@myapp.staging.var.secrets"]);
         
-        // The function will call std::process::exit(1) for missing keys
-        // This is expected behavior for script compatibility
+        let tagged = db.collect_variables(&create_test_context().with_target("*", "prod", "*"))?;
+        assert_eq!(tagged.len(), 2);
+        assert!(tagged.iter().all(|v| v.chain.starts_with("test@myapp.prod.var.")));
+        
+        assert!(execute("DB_PASSWORD", &wildcard, &db).is_ok());
+        
+        Ok(())
+    }
+    
+    fn database_chains(contexts: &[ResolvedContext]) -> Vec<String> {
+        contexts.iter().map(|c| c.qualified()).collect()
     }
     
     #[test]
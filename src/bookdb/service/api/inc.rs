@@ -3,7 +3,8 @@
 use crate::error::{Result, BookdbError};
 use crate::bookdb::service::ctx::ResolvedContext;
 use crate::bookdb::service::db::Database;
-use crate::rdx::stderr::{Stderr, StderrConfig};
+use crate::bookdb::service::db::driver::{CounterOptions, Number};
+use stderr::{Stderr, StderrConfig};
 
 /// Execute increment command: atomically increment a numeric variable
 pub fn execute(
@@ -11,7 +12,18 @@ pub fn execute(
     amount: i64,
     context: &ResolvedContext,
     database: &Database,
-) ->  Result<(), E> {
+) -> Result<()> {
+    execute_with(key, Number::Int(amount), &CounterOptions::default(), context, database)
+}
+
+/// Execute increment with counter options (`--float`, `--min/--max`, `--wrap`, `--init`)
+pub fn execute_with(
+    key: String,
+    amount: Number,
+    options: &CounterOptions,
+    context: &ResolvedContext,
+    database: &Database,
+) -> Result<()> {
     let mut logger = Stderr::new();
     logger.trace_fn("inc", &format!("incrementing key '{}' by {} in context: {}", key, amount, context));
     
@@ -21,7 +33,7 @@ pub fn execute(
     }
     
     // Perform atomic increment
-    match database.increment_variable(&key, amount, context) {
+    match database.step_counter(&key, amount, options, context) {
         Ok(new_value) => {
             // Success - show the new value
             logger.okay(&format!("Incremented '{}' by {}: {}", key, amount, new_value));
@@ -34,10 +46,14 @@ pub fn execute(
             Err(BookdbError::NonNumericValue(key, value))
         }
         Err(BookdbError::NumericOverflow) => {
-            logger.error(&format!("Cannot increment: Operation would cause overflow"));
+            logger.error("Cannot increment: Operation would cause overflow");
             logger.info(&format!("Key '{}' is at maximum value for increment by {}", key, amount));
             Err(BookdbError::NumericOverflow)
         }
+        Err(e @ BookdbError::PreconditionFailed(_)) => {
+            logger.warn(&format!("Not incremented: {}", e));
+            std::process::exit(e.exit_code());
+        }
         Err(e) => {
             logger.error(&format!("Failed to increment '{}': {}", key, e));
             Err(e)
@@ -48,7 +64,7 @@ pub fn execute(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::bookdb::service::ctx::ResolvedContext;
+    use crate::bookdb::service::ctx::{Anchor, ChainMode, ResolvedContext};
     use tempfile::NamedTempFile;
     
     fn create_test_db() -> (Database, NamedTempFile) {
@@ -59,10 +75,12 @@ mod tests {
     
     fn create_test_context() -> ResolvedContext {
         ResolvedContext {
-            base_name: "test".to_string(),
-            project_name: "TEST_PROJECT".to_string(),
-            workspace_name: "TEST_WORKSPACE".to_string(),
-            keystore_name: "TEST_KEYSTORE".to_string(),
+            base: "test".to_string(),
+            project: "TEST_PROJECT".to_string(),
+            workspace: "TEST_WORKSPACE".to_string(),
+            anchor: Anchor::Var,
+            tail: "TEST_KEYSTORE".to_string(),
+            prefix_mode: ChainMode::Persistent,
         }
     }
     
@@ -1,30 +1,46 @@
 // src/commands/ls.rs - Updated with consistent BOOKDB_CONCEPTS.md terminology
 
 use crate::error::{Result, BookdbError};
-use crate::bookdb::service::ctx::ResolvedContext;
+use crate::bookdb::service::ctx::{Anchor, ResolvedContext};
 use crate::bookdb::service::db::Database;
+use crate::bookdb::service::db::driver::{display_value, list_bases_in, DocSegmentInfo};
+use crate::bookdb::service::ctx::alias::list_visible_aliases;
+use crate::bookdb::app::sup::config::Config;
 use crate::cli::LsTarget;
-use crate::rdx::stderr::{Stderr, StderrConfig};
+use stderr::{Stderr, StderrConfig};
 use std::collections::HashMap;
 
 /// Execute ls command: list items of specified type
-pub fn execute(target: LsTarget, context: &ResolvedContext, database: &Database) ->  Result<(), E> {
+pub fn execute(target: LsTarget, context: &ResolvedContext, database: &Database) -> Result<()> {
     let mut logger = Stderr::new();
     logger.trace_fn("ls", &format!("listing {:?} in context: {}", target, context));
     
     match target {
+        LsTarget::All => {
+            list_projects(database, &mut logger)?;
+            list_keys(context, database, &mut logger)
+        }
         LsTarget::Keys => list_keys(context, database, &mut logger),
         LsTarget::Projects => list_projects(database, &mut logger),
         LsTarget::Workspaces => list_workspaces(context, database, &mut logger),    // FIXED: was Docstores
         LsTarget::Keystores => list_keystores(context, database, &mut logger),      // FIXED: was Varstores
         LsTarget::Docs => list_docs(context, database, &mut logger),
+        LsTarget::Bases => list_bases(context, &mut logger),
+        LsTarget::Actions => list_actions(database, &mut logger),
+        LsTarget::Segments => list_segments(context, database, &mut logger),
+        LsTarget::Aliases => list_aliases(database, &mut logger),
+        LsTarget::Locks => list_locks(database, &mut logger),
     }
 }
 
 /// List all variables/keys in current context
-fn list_keys(context: &ResolvedContext, database: &Database, logger: &mut Stderr) ->  Result<(), E> {
+fn list_keys(context: &ResolvedContext, database: &Database, logger: &mut Stderr) -> Result<()> {
     logger.trace_fn("ls_keys", &format!("listing variables in {}", context));
     
+    if context.is_wildcard() {
+        return list_keys_wildcard(context, database, logger);
+    }
+    
     let variables = database.list_variables(context)?;
     
     if variables.is_empty() {
@@ -38,14 +54,14 @@ fn list_keys(context: &ResolvedContext, database: &Database, logger: &mut Stderr
     
     // Create table data
     let mut table_data = vec![vec!["Key", "Value"]];
-    for (key, value) in var_list {
-        // Truncate long values for display
-        let display_value = if value.len() > 60 {
-            format!("{}...", &value[..57])
-        } else {
-            value.clone()
-        };
-        table_data.push(vec![key.as_str(), &display_value]);
+    let mut shown_values = Vec::new();
+    for (key, value) in &var_list {
+        // Mask sensitive values, truncate long ones
+        let value = display_value(value, database.is_sensitive(key, context)?, false);
+        shown_values.push(if value.len() > 60 { format!("{}...", &value[..57]) } else { value });
+    }
+    for ((key, _), value) in var_list.iter().zip(&shown_values) {
+        table_data.push(vec![key.as_str(), value.as_str()]);
     }
     
     // Convert to the format expected by simple_table
@@ -62,8 +78,37 @@ fn list_keys(context: &ResolvedContext, database: &Database, logger: &mut Stderr
     Ok(())
 }
 
+/// List variables in every keystore a wildcard chain matches, tagged by chain
+fn list_keys_wildcard(context: &ResolvedContext, database: &Database, logger: &mut Stderr) ->  Result<()> {
+    let variables = database.collect_variables(context)?;
+    
+    if variables.is_empty() {
+        logger.info(&format!("No variables found in any context matching: {}", context));
+        return Ok(());
+    }
+    
+    let rows: Vec<Vec<String>> = variables
+        .iter()
+        .map(|var| {
+            let value = display_value(&var.value, var.sensitive, false);
+            let value = if value.len() > 60 { format!("{}...", &value[..57]) } else { value };
+            vec![var.chain.clone(), var.key.clone(), value]
+        })
+        .collect();
+    
+    let mut table_refs: Vec<Vec<&str>> = vec![vec!["Chain", "Key", "Value"]];
+    table_refs.extend(rows.iter().map(|row| row.iter().map(String::as_str).collect()));
+    let table_refs: Vec<&[&str]> = table_refs.iter().map(|row| row.as_slice()).collect();
+    
+    logger.banner(&format!("Variables matching {}", context), '=')?;
+    logger.simple_table(&table_refs)?;
+    logger.info(&format!("Total: {} variables", variables.len()));
+    
+    Ok(())
+}
+
 /// List all projects in the database
-fn list_projects(database: &Database, logger: &mut Stderr) ->  Result<(), E> {
+fn list_projects(database: &Database, logger: &mut Stderr) -> Result<()> {
     logger.trace_fn("ls_projects", "listing all projects");
     
     let projects = database.list_projects()?;
@@ -75,8 +120,9 @@ fn list_projects(database: &Database, logger: &mut Stderr) ->  Result<(), E> {
     
     // Create table with numbering
     let mut table_data = vec![vec!["#", "Project"]];
-    for (i, project) in projects.iter().enumerate() {
-        table_data.push(vec![&(i + 1).to_string(), project]);
+    let numbers: Vec<String> = (1..=projects.len()).map(|i| i.to_string()).collect();
+    for (number, project) in numbers.iter().zip(&projects) {
+        table_data.push(vec![number.as_str(), project.as_str()]);
     }
     
     let table_refs: Vec<&[&str]> = table_data
@@ -92,7 +138,7 @@ fn list_projects(database: &Database, logger: &mut Stderr) ->  Result<(), E> {
 }
 
 /// List all workspaces in current project
-fn list_workspaces(context: &ResolvedContext, database: &Database, logger: &mut Stderr) ->  Result<(), E> {
+fn list_workspaces(context: &ResolvedContext, database: &Database, logger: &mut Stderr) -> Result<()> {
     logger.trace_fn("ls_workspaces", &format!("listing workspaces in project: {}", context.project));
     
     let workspaces = database.list_workspaces(&context.project)?;
@@ -104,8 +150,9 @@ fn list_workspaces(context: &ResolvedContext, database: &Database, logger: &mut
     
     // Create table with numbering
     let mut table_data = vec![vec!["#", "Workspace"]];
-    for (i, workspace) in workspaces.iter().enumerate() {
-        table_data.push(vec![&(i + 1).to_string(), workspace]);
+    let numbers: Vec<String> = (1..=workspaces.len()).map(|i| i.to_string()).collect();
+    for (number, workspace) in numbers.iter().zip(&workspaces) {
+        table_data.push(vec![number.as_str(), workspace.as_str()]);
     }
     
     let table_refs: Vec<&[&str]> = table_data
@@ -121,7 +168,7 @@ fn list_workspaces(context: &ResolvedContext, database: &Database, logger: &mut
 }
 
 /// List all keystores in current workspace
-fn list_keystores(context: &ResolvedContext, database: &Database, logger: &mut Stderr) ->  Result<(), E> {  // FIXED: was list_varstores
+fn list_keystores(context: &ResolvedContext, database: &Database, logger: &mut Stderr) -> Result<()> {  // FIXED: was list_varstores
     logger.trace_fn("ls_keystores", &format!("listing keystores in {}.{}", context.project, context.workspace));
     
     let keystores = database.list_keystores(&context.project, &context.workspace)?;  // FIXED: was list_varstores
@@ -132,7 +179,7 @@ fn list_keystores(context: &ResolvedContext, database: &Database, logger: &mut S
     }
     
     // Create table with numbering and variable counts
-    let mut table_data = vec![vec!["#", "Keystore", "Variables"]];  // FIXED: was "Varstore"
+    let mut rows: Vec<[String; 3]> = Vec::new();
     for (i, keystore) in keystores.iter().enumerate() {
         // Create context for this keystore to count variables
         let keystore_context = ResolvedContext {
@@ -148,13 +195,12 @@ fn list_keystores(context: &ResolvedContext, database: &Database, logger: &mut S
             .map(|vars| vars.len())
             .unwrap_or(0);
         
-        table_data.push(vec![
-            &(i + 1).to_string(), 
-            keystore, 
-            &var_count.to_string()
-        ]);
+        rows.push([(i + 1).to_string(), keystore.clone(), var_count.to_string()]);
     }
     
+    let mut table_data = vec![vec!["#", "Keystore", "Variables"]];  // FIXED: was "Varstore"
+    table_data.extend(rows.iter().map(|row| row.iter().map(String::as_str).collect::<Vec<_>>()));
+    
     let table_refs: Vec<&[&str]> = table_data
         .iter()
         .map(|row| row.as_slice())
@@ -168,7 +214,7 @@ fn list_keystores(context: &ResolvedContext, database: &Database, logger: &mut S
 }
 
 /// List documents in current workspace
-fn list_docs(context: &ResolvedContext, _database: &Database, logger: &mut Stderr) ->  Result<(), E> {
+fn list_docs(context: &ResolvedContext, _database: &Database, logger: &mut Stderr) -> Result<()> {
     logger.trace_fn("ls_docs", &format!("listing documents in {}.{}", context.project, context.workspace));
     
     // TODO: Implement document listing when document system is ready
@@ -178,6 +224,225 @@ fn list_docs(context: &ResolvedContext, _database: &Database, logger: &mut Stder
     Ok(())
 }
 
+/// List all bases in the data dir with their sizes
+fn list_bases(context: &ResolvedContext, logger: &mut Stderr) ->  Result<()> {
+    logger.trace_fn("ls_bases", "listing bases in data dir");
+    
+    let paths = crate::bookdb::app::sup::config::resolve_paths();
+    let bases = list_bases_in(&paths.data_dir)?;
+    
+    if bases.is_empty() {
+        logger.info(&format!("No bases found in {}", paths.data_dir.display()));
+        return Ok(());
+    }
+    
+    // Mark the base the current context resolves to
+    let rows: Vec<Vec<String>> = bases.iter()
+        .map(|b| vec![
+            if b.name == context.base { "*".to_string() } else { String::new() },
+            b.name.clone(),
+            format_size(b.size_bytes),
+        ])
+        .collect();
+    
+    let mut table_data = vec![vec!["", "Base", "Size"]];
+    for row in &rows {
+        table_data.push(row.iter().map(|s| s.as_str()).collect());
+    }
+    
+    let table_refs: Vec<&[&str]> = table_data
+        .iter()
+        .map(|row| row.as_slice())
+        .collect();
+    
+    logger.banner("Bases", '=')?;
+    logger.simple_table(&table_refs)?;
+    logger.info(&format!("Total: {} bases", bases.len()));
+    
+    Ok(())
+}
+
+/// List all action bindings in the current base
+fn list_actions(database: &Database, logger: &mut Stderr) ->  Result<()> {
+    logger.trace_fn("ls_actions", "listing action bindings");
+    
+    let bindings = database.list_actions()?;
+    
+    if bindings.is_empty() {
+        logger.info("No actions bound in this base");
+        return Ok(());
+    }
+    
+    let rows: Vec<Vec<String>> = bindings.iter()
+        .map(|b| vec![
+            format!("#{}.{}.{}.{}", b.project, b.workspace, match b.anchor {
+                Anchor::Var => "var",
+                Anchor::Doc => "doc",
+            }, b.tail),
+            b.kind.to_string(),
+            b.arg.clone().unwrap_or_default(),
+        ])
+        .collect();
+    
+    let mut table_data = vec![vec!["Chain", "Action", "Argument"]];
+    for row in &rows {
+        table_data.push(row.iter().map(|s| s.as_str()).collect());
+    }
+    
+    let table_refs: Vec<&[&str]> = table_data
+        .iter()
+        .map(|row| row.as_slice())
+        .collect();
+    
+    logger.banner("Actions", '=')?;
+    logger.simple_table(&table_refs)?;
+    logger.info(&format!("Total: {} actions", bindings.len()));
+    
+    Ok(())
+}
+
+/// List aliases visible from this base (base aliases shadow global ones)
+fn list_aliases(database: &Database, logger: &mut Stderr) ->  Result<()> {
+    logger.trace_fn("ls_aliases", "listing chain aliases");
+    
+    let global_file = Config::default().get_aliases_file_path();
+    let aliases = list_visible_aliases(database, &global_file)?;
+    
+    if aliases.is_empty() {
+        logger.info("No aliases defined");
+        return Ok(());
+    }
+    
+    let rows: Vec<Vec<String>> = aliases.iter()
+        .map(|a| vec![format!(":{}", a.name), a.chain.clone(), a.scope.as_str().to_string()])
+        .collect();
+    
+    let mut table_data = vec![vec!["Alias", "Chain", "Scope"]];
+    for row in &rows {
+        table_data.push(row.iter().map(|s| s.as_str()).collect());
+    }
+    
+    let table_refs: Vec<&[&str]> = table_data
+        .iter()
+        .map(|row| row.as_slice())
+        .collect();
+    
+    logger.banner("Aliases", '=')?;
+    logger.simple_table(&table_refs)?;
+    logger.info(&format!("Total: {} aliases", aliases.len()));
+    
+    Ok(())
+}
+
+/// List locked namespaces, and whether the base is read-only
+fn list_locks(database: &Database, logger: &mut Stderr) ->  Result<()> {
+    logger.trace_fn("ls_locks", "listing locks");
+    
+    if database.is_read_only()? {
+        logger.warn(&format!("Base '{}' is read-only", database.base_name));
+    }
+    let locks = database.list_locks()?;
+    if locks.is_empty() {
+        logger.info("No locked namespaces");
+        return Ok(());
+    }
+    
+    let mut table_data = vec![vec!["Chain", "Locked At"]];
+    for lock in &locks {
+        table_data.push(vec![lock.chain.as_str(), lock.locked_at.as_str()]);
+    }
+    let table_refs: Vec<&[&str]> = table_data.iter().map(|row| row.as_slice()).collect();
+    
+    logger.banner("Locks", '=')?;
+    logger.simple_table(&table_refs)?;
+    logger.info(&format!("Total: {} locks", locks.len()));
+    
+    Ok(())
+}
+
+/// List the segment tree of the document named by the chain tail
+fn list_segments(context: &ResolvedContext, database: &Database, logger: &mut Stderr) ->  Result<()> {
+    let doc_key = context.doc_key();
+    logger.trace_fn("ls_segments", &format!("listing segments of {} in {}.{}", doc_key, context.project, context.workspace));
+    
+    if context.anchor != Anchor::Doc {
+        return Err(BookdbError::Argument(format!(
+            "ls segments needs a doc chain, e.g. @{}.{}.doc.<document>", context.project, context.workspace
+        )));
+    }
+    
+    let segments = database.list_doc_segments(doc_key, context)?;
+    
+    if segments.is_empty() {
+        logger.info(&format!("No segments found for document: {}", doc_key));
+        return Ok(());
+    }
+    
+    let rows = segment_tree_rows(&segments);
+    
+    let mut table_data = vec![vec!["Segment", "Mime", "Size"]];
+    for row in &rows {
+        table_data.push(row.iter().map(|s| s.as_str()).collect());
+    }
+    
+    let table_refs: Vec<&[&str]> = table_data
+        .iter()
+        .map(|row| row.as_slice())
+        .collect();
+    
+    logger.banner(&format!("Segments of {}", doc_key), '=')?;
+    logger.simple_table(&table_refs)?;
+    logger.info(&format!("Total: {} segments", segments.len()));
+    
+    Ok(())
+}
+
+/// Indented tree rows for sorted segment paths
+///
+/// Intermediate path parts without a segment of their own (`api/`, `api/v2/`)
+/// get a row with empty mime/size so nesting stays visible.
+fn segment_tree_rows(segments: &[DocSegmentInfo]) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut open_dirs: Vec<&str> = Vec::new();
+    
+    for segment in segments {
+        let parts: Vec<&str> = segment.path.split('/').collect();
+        let (leaf, dirs) = parts.split_last().unwrap();
+        
+        // Keep the common prefix with the previous path, emit the new directories
+        let common = open_dirs.iter().zip(dirs.iter()).take_while(|(a, b)| a == b).count();
+        open_dirs.truncate(common);
+        for dir in &dirs[common..] {
+            rows.push(vec![format!("{}{}/", "  ".repeat(open_dirs.len()), dir), String::new(), String::new()]);
+            open_dirs.push(*dir);
+        }
+        
+        rows.push(vec![
+            format!("{}{}", "  ".repeat(dirs.len()), leaf),
+            segment.mime.clone(),
+            format_size(segment.size_bytes),
+        ]);
+    }
+    
+    rows
+}
+
+/// Human-readable file size
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -205,7 +470,7 @@ mod tests {
     fn create_test_db_with_data() -> (Database, TempDir) {
         let temp_dir = TempDir::new().unwrap();
         let db_path = temp_dir.path().join("test.db");
-        let mut db = Database::create_or_open(&db_path).unwrap();
+        let db = Database::create_or_open(&db_path).unwrap();
         let context = create_test_context();
         
         // Add some test data
@@ -216,7 +481,7 @@ mod tests {
     }
     
     #[test]
-    fn test_list_keys() ->  Result<(), E> {
+    fn test_list_keys() -> Result<()> {
         let (db, _temp) = create_test_db();
         let context = create_test_context();
         
@@ -227,7 +492,7 @@ mod tests {
     }
     
     #[test]
-    fn test_list_workspaces() ->  Result<(), E> {
+    fn test_list_workspaces() -> Result<()> {
         let (db, _temp) = create_test_db();
         let context = create_test_context();
         
@@ -238,7 +503,7 @@ mod tests {
     }
     
     #[test]
-    fn test_list_keystores() ->  Result<(), E> {
+    fn test_list_keystores() -> Result<()> {
         let (db, _temp) = create_test_db();
         let context = create_test_context();
         
@@ -249,7 +514,7 @@ mod tests {
     }
     
     #[test]
-    fn test_list_projects() ->  Result<(), E> {
+    fn test_list_projects() -> Result<()> {
         let (db, _temp) = create_test_db_with_data();
         let context = create_test_context();
         
@@ -260,7 +525,7 @@ mod tests {
     }
     
     #[test]
-    fn test_list_empty_context() ->  Result<(), E> {
+    fn test_list_empty_context() -> Result<()> {
         let temp_dir = TempDir::new().unwrap();
         let db_path = temp_dir.path().join("empty.db");
         let db = Database::create_or_open(&db_path).unwrap();
@@ -274,10 +539,10 @@ mod tests {
     }
     
     #[test]
-    fn test_long_value_truncation() ->  Result<(), E> {
+    fn test_long_value_truncation() -> Result<()> {
         let temp_dir = TempDir::new().unwrap();
         let db_path = temp_dir.path().join("test.db");
-        let mut db = Database::create_or_open(&db_path).unwrap();
+        let db = Database::create_or_open(&db_path).unwrap();
         let context = create_test_context();
         
         // Set a very long value
@@ -290,4 +555,45 @@ mod tests {
         
         Ok(())
     }
+    
+    #[test]
+    fn test_list_segments() ->  Result<()> {
+        let (db, _temp) = create_test_db();
+        let mut context = create_test_context();
+        context.anchor = Anchor::Doc;
+        context.tail = "README".to_string();
+        
+        db.set_doc_segment("README", "_root", "text/plain", b"hello", &context)?;
+        db.set_doc_segment("README", "api/v2/schema", "application/json", b"{}", &context)?;
+        
+        let segments = db.list_doc_segments("README", &context)?;
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[1].path, "api/v2/schema");
+        assert_eq!(segments[1].size_bytes, 2);
+        
+        assert!(execute(LsTarget::Segments, &context, &db).is_ok());
+        
+        Ok(())
+    }
+    
+    #[test]
+    fn test_segment_tree_rows() {
+        let segment = |path: &str| DocSegmentInfo {
+            path: path.to_string(),
+            mime: "text/plain".to_string(),
+            size_bytes: 1,
+            updated_at: 0,
+        };
+        let rows = segment_tree_rows(&[segment("_root"), segment("api/v1"), segment("api/v2/schema")]);
+        let labels: Vec<&str> = rows.iter().map(|r| r[0].as_str()).collect();
+        
+        assert_eq!(labels, vec!["_root", "api/", "  v1", "  v2/", "    schema"]);
+    }
+    
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(2048), "2.0 KB");
+        assert_eq!(format_size(5 * 1024 * 1024), "5.0 MB");
+    }
 }
@@ -2,7 +2,7 @@ use crate::error::Result;
 use crate::bookdb::service::ctx::ResolvedContext;
 use crate::bookdb::service::db::Database;
 
-pub fn execute(dry_run: bool, context: &ResolvedContext, db: &Database) ->  Result<(), E> {
+pub fn execute(dry_run: bool, context: &ResolvedContext, db: &Database) -> Result<()> {
     let chunks = db.stream_doc_chunks(context)?;
     
     if chunks.is_empty() {
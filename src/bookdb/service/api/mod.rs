@@ -11,6 +11,7 @@ pub mod ls;
 pub mod import;
 pub mod export;
 pub mod r#use;   // 'use' is a keyword, so we use raw identifier
+pub mod action;   // `#` chains: run the bound action
 
 // Re-export command execution functions for convenience
 pub use getv::execute as execute_getv;
@@ -18,9 +19,12 @@ pub use setv::execute as execute_setv;
 pub use delv::execute as execute_delv;
 pub use inc::execute as execute_inc;    // New export
 pub use dec::execute as execute_dec;    // New export
+pub use inc::execute_with as execute_inc_with;
+pub use dec::execute_with as execute_dec_with;
 pub use getd::execute as execute_getd;
 pub use setd::execute as execute_setd;
 pub use ls::execute as execute_ls;
 pub use import::execute as execute_import;
 pub use export::execute as execute_export;
 pub use r#use::execute as execute_use;
+pub use action::execute as execute_action;
@@ -1,12 +1,15 @@
-use crate::error::Result;
+use crate::error::{Result, BookdbError};
 use crate::bookdb::service::ctx::ResolvedContext;
 use crate::bookdb::service::db::Database;
+use super::getd::doc_target;
 
-pub fn execute(dik_value: &str, context: &ResolvedContext, db: &Database) ->  Result<(), E> {
+pub fn execute(dik_value: &str, context: &ResolvedContext, db: &Database) -> Result<()> {
     let (dik, value) = dik_value.split_once('=')
-        .ok_or_else(|| crate::error::BookdbError::Argument("Expected dik=value format".into()))?;
+        .ok_or_else(|| BookdbError::Argument("Expected dik=value format".into()))?;
     
-    let (doc_key, seg_path) = dik.split_once('.').unwrap_or((dik, "_root"));
+    // `=value` writes to the segment named by the doc chain
+    let dik = Some(dik.trim()).filter(|d| !d.is_empty());
+    let (doc_key, seg_path) = doc_target(dik, context)?;
     
     db.set_doc_segment(doc_key, seg_path, "text/plain", value.as_bytes(), context)?;
     println!("Ok.");
@@ -9,16 +9,16 @@
 use crate::error::{Result, BookdbError};
 use crate::bookdb::service::ctx::ResolvedContext;
 use crate::bookdb::service::db::Database;
-use crate::rdx::stderr::{Stderr, StderrConfig};
+use crate::bookdb::service::db::driver::display_value;
+use stderr::{Stderr, StderrConfig};
 
 /// Execute setv command: set variable key=value
-pub fn execute(key_value: &str, context: &ResolvedContext, database: &mut Database) ->  Result<(), E> {
+pub fn execute(key_value: &str, context: &ResolvedContext, database: &mut Database) -> Result<()> {
     let mut logger = Stderr::new();
-    logger.trace_fn("setv", &format!("setting variable: {} in context: {}", key_value, context));
-    
     // Parse key=value format
     let (key, value) = key_value.split_once('=')
         .ok_or_else(|| BookdbError::Argument("setv requires key=value format".to_string()))?;
+    logger.trace_fn("setv", &format!("setting variable: {} in context: {}", key.trim(), context));
     
     let key = key.trim();
     let value = value.trim();
@@ -44,14 +44,15 @@ pub fn execute(key_value: &str, context: &ResolvedContext, database: &mut Databa
     // Log the operation
     match existing_value {
         None => {
-            logger.info(&format!("New variable: '{}' = '{}' in {}", key, value, context));
+            let shown = display_value(value, database.is_sensitive(key, context)?, false);
+            logger.info(&format!("New variable: '{}' = '{}' in {}", key, shown, context));
             logger.trace_fn("setv", &format!("created new variable: {}", key));
         }
         Some(old_value) => {
             if old_value == value {
                 logger.trace_fn("setv", &format!("variable {} unchanged (same value)", key));
             } else {
-                logger.trace_fn("setv", &format!("updated {} from '{}' to '{}'", key, old_value, value));
+                logger.trace_fn("setv", &format!("updated {}", key));
             }
         }
     }
@@ -84,7 +85,7 @@ mod tests {
     }
     
     #[test]
-    fn test_setv_new_variable() ->  Result<(), E> {
+    fn test_setv_new_variable() -> Result<()> {
         let (mut db, _temp) = create_test_db();
         let context = create_test_context();
         
@@ -99,7 +100,7 @@ mod tests {
     }
     
     #[test]
-    fn test_setv_update_variable() ->  Result<(), E> {
+    fn test_setv_update_variable() -> Result<()> {
         let (mut db, _temp) = create_test_db();
         let context = create_test_context();
         
@@ -117,7 +118,7 @@ mod tests {
     }
     
     #[test]
-    fn test_setv_with_spaces() ->  Result<(), E> {
+    fn test_setv_with_spaces() -> Result<()> {
         let (mut db, _temp) = create_test_db();
         let context = create_test_context();
         
@@ -177,7 +178,7 @@ mod tests {
     }
     
     #[test]
-    fn test_setv_complex_values() ->  Result<(), E> {
+    fn test_setv_complex_values() -> Result<()> {
         let (mut db, _temp) = create_test_db();
         let context = create_test_context();
         
@@ -16,7 +16,7 @@ pub fn execute(
     context_str: &str,
     current_context: &ResolvedContext,
     _database: &Database,
-) ->  Result<(), E> {
+) -> Result<()> {
     let mut logger = Stderr::new();
     logger.trace_fn("use", &format!("changing context from {} to {}", current_context, context_str));
     
@@ -31,7 +31,7 @@ pub fn execute(
     }
     
     // Show context change banner
-    logger.banner(&format!("Context Changed"), '=')?;
+    logger.banner("Context Changed", '=')?;
     logger.info(&format!("Previous: {}", current_context));
     logger.info(&format!("Current:  {}", chain));
     
@@ -69,7 +69,7 @@ mod tests {
     }
     
     #[test]
-    fn test_use_valid_context() ->  Result<(), E> {
+    fn test_use_valid_context() -> Result<()> {
         let (db, _temp) = create_test_db();
         let current_context = create_test_context();
         
@@ -0,0 +1,225 @@
+// src/bookdb/service/ctx/alias.rs
+// Chain aliases: `:name` expands to a stored chain before it is parsed
+//
+// Per-base aliases live in the base's `aliases` table, global aliases in
+// $config_dir/aliases.json. A base alias shadows a global one of the same name,
+// and a name that is not an alias keeps meaning a relative keystore swap.
+
+use crate::error::{Result, BookdbError};
+use crate::bookdb::service::db::Database;
+use super::types::typesV1::ChainMode;
+use super::context::parse_relative_chain;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// Marker that introduces an alias reference (`:prodcreds`)
+pub const ALIAS_MARKER: char = ':';
+
+/// Where an alias is stored
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AliasScope {
+    /// The `aliases` table of one base
+    Base,
+    /// $config_dir/aliases.json, shared by every base
+    Global,
+}
+
+impl AliasScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AliasScope::Base => "base",
+            AliasScope::Global => "global",
+        }
+    }
+}
+
+/// A named chain as shown by `ls aliases`
+#[derive(Debug, Clone, PartialEq)]
+pub struct AliasEntry {
+    pub name: String,
+    pub chain: String,
+    pub scope: AliasScope,
+}
+
+/// Alias names are letters, digits, `_` and `-`
+pub fn validate_alias_name(name: &str) -> Result<()> {
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+        return Err(BookdbError::Argument(format!(
+            "Invalid alias name '{}': use letters, digits, '_' or '-'", name
+        )));
+    }
+    if name == "var" || name == "doc" {
+        return Err(BookdbError::Argument("Cannot use 'var' or 'doc' as alias names".to_string()));
+    }
+    Ok(())
+}
+
+/// Check that a chain can be stored behind an alias
+///
+/// Only full chains are accepted: a relative chain would resolve differently
+/// depending on where the cursor is when the alias is used.
+pub fn validate_alias_chain(chain: &str, fallback_base: &str) -> Result<()> {
+    if parse_relative_chain(chain)?.is_some() {
+        return Err(BookdbError::Argument(format!(
+            "Alias target must be a full chain, not the relative form '{}'", chain
+        )));
+    }
+    super::parse_context_chain(chain, fallback_base)?;
+    Ok(())
+}
+
+/// Split `:name` (or `%:name`, `#:name`) into a prefix override and the name
+pub fn alias_reference(raw: &str) -> Option<(Option<ChainMode>, &str)> {
+    let (prefix_mode, body) = match raw.chars().next() {
+        Some('@') => (Some(ChainMode::Persistent), &raw[1..]),
+        Some('%') => (Some(ChainMode::Ephemeral), &raw[1..]),
+        Some('#') => (Some(ChainMode::Action), &raw[1..]),
+        _ => (None, raw),
+    };
+    let name = body.strip_prefix(ALIAS_MARKER)?;
+    validate_alias_name(name).ok()?;
+    Some((prefix_mode, name))
+}
+
+/// Expand an alias reference through `lookup`
+///
+/// Returns `Ok(None)` when `raw` is not an alias reference or names no alias.
+/// An explicit prefix on the reference replaces the stored chain's prefix, so
+/// `%:prodcreds` uses the alias without moving the cursor.
+pub fn expand_alias<F>(raw: &str, mut lookup: F) -> Result<Option<String>>
+where
+    F: FnMut(&str) -> Result<Option<String>>,
+{
+    let (prefix_mode, name) = match alias_reference(raw) {
+        Some(reference) => reference,
+        None => return Ok(None),
+    };
+
+    Ok(lookup(name)?.map(|chain| match prefix_mode {
+        Some(mode) => with_prefix(&chain, mode),
+        None => chain,
+    }))
+}
+
+/// Look an alias up in the base at `base_db`, then in the global alias file
+///
+/// Runs while a chain is parsed, before the command's own connection exists,
+/// so the base is opened read-only and its schema is left alone.
+pub fn lookup_alias(name: &str, base_db: &Path, global_file: &Path) -> Result<Option<String>> {
+    if base_db.exists() {
+        if let Some(chain) = Database::open_readonly(base_db)?.get_alias(name)? {
+            return Ok(Some(chain));
+        }
+    }
+    Ok(load_global_aliases(global_file)?.remove(name))
+}
+
+/// Every alias visible from `database`: its own aliases, then unshadowed globals
+pub fn list_visible_aliases(database: &Database, global_file: &Path) -> Result<Vec<AliasEntry>> {
+    let mut entries: Vec<AliasEntry> = database.list_aliases()?
+        .into_iter()
+        .map(|(name, chain)| AliasEntry { name, chain, scope: AliasScope::Base })
+        .collect();
+
+    for (name, chain) in load_global_aliases(global_file)? {
+        if !entries.iter().any(|e| e.name == name) {
+            entries.push(AliasEntry { name, chain, scope: AliasScope::Global });
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Read the global alias file; a missing file means no aliases
+pub fn load_global_aliases(path: &Path) -> Result<BTreeMap<String, String>> {
+    if !path.exists() {
+        return Ok(BTreeMap::new());
+    }
+    let content = fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+/// Write the global alias file
+pub fn save_global_aliases(path: &Path, aliases: &BTreeMap<String, String>) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(aliases)?)?;
+    Ok(())
+}
+
+/// Replace the leading prefix of a stored chain
+fn with_prefix(chain: &str, mode: ChainMode) -> String {
+    let body = chain.strip_prefix(|c| c == '@' || c == '%' || c == '#').unwrap_or(chain);
+    let prefix = match mode {
+        ChainMode::Persistent => '@',
+        ChainMode::Ephemeral => '%',
+        ChainMode::Action => '#',
+    };
+    format!("{}{}", prefix, body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_alias_reference_forms() {
+        assert_eq!(alias_reference(":prodcreds"), Some((None, "prodcreds")));
+        assert_eq!(alias_reference("%:prod-creds"), Some((Some(ChainMode::Ephemeral), "prod-creds")));
+        assert_eq!(alias_reference("@proj.ws.var.store"), None);
+        assert_eq!(alias_reference(":bad.name"), None);
+        assert_eq!(alias_reference(":"), None);
+    }
+
+    #[test]
+    fn test_expand_alias_with_prefix_override() -> Result<()> {
+        let lookup = |name: &str| -> Result<Option<String>> {
+            Ok((name == "prodcreds").then(|| "@work@infra.production.var.credentials".to_string()))
+        };
+
+        assert_eq!(expand_alias(":prodcreds", lookup)?, Some("@work@infra.production.var.credentials".to_string()));
+        assert_eq!(expand_alias("%:prodcreds", lookup)?, Some("%work@infra.production.var.credentials".to_string()));
+        // Unknown names fall through (to the relative keystore swap)
+        assert_eq!(expand_alias(":secrets", lookup)?, None);
+        assert_eq!(expand_alias("@infra.prod.var.x", lookup)?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_alias_chain_must_be_full() {
+        assert!(validate_alias_chain("@work@infra.production.var.credentials", "home").is_ok());
+        assert!(validate_alias_chain(":credentials", "home").is_err());
+        assert!(validate_alias_chain("..", "home").is_err());
+        assert!(validate_alias_chain("@infra.production", "home").is_err());
+    }
+
+    #[test]
+    fn test_base_alias_shadows_global() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("work.sqlite3");
+        let global_file = temp_dir.path().join("aliases.json");
+
+        let mut globals = BTreeMap::new();
+        globals.insert("prod".to_string(), "@infra.prod.var.MAIN".to_string());
+        globals.insert("dev".to_string(), "@infra.dev.var.MAIN".to_string());
+        save_global_aliases(&global_file, &globals)?;
+
+        let db = Database::create_or_open(&db_path)?;
+        db.set_alias("prod", "@infra.production.var.credentials")?;
+
+        assert_eq!(lookup_alias("prod", &db_path, &global_file)?, Some("@infra.production.var.credentials".to_string()));
+        assert_eq!(lookup_alias("dev", &db_path, &global_file)?, Some("@infra.dev.var.MAIN".to_string()));
+        assert_eq!(lookup_alias("nope", &db_path, &global_file)?, None);
+
+        let visible = list_visible_aliases(&db, &global_file)?;
+        assert_eq!(visible.len(), 2);
+        assert_eq!(visible[0].scope, AliasScope::Base);
+        assert_eq!(visible[1].name, "dev");
+
+        Ok(())
+    }
+}
@@ -6,7 +6,7 @@ use std::fmt;
 use crate::error::{Result, BookdbError};
 
 // Import types from typesV1 instead of defining them here
-use super::typesV1::{ContextChain, ResolvedContext, Anchor, ChainMode};
+use super::types::typesV1::{ContextChain, ResolvedContext, Anchor, ChainMode, RelativeChain};
 
 // ============================================================================
 // DISPLAY IMPLEMENTATIONS
@@ -14,26 +14,22 @@ use super::typesV1::{ContextChain, ResolvedContext, Anchor, ChainMode};
 
 impl fmt::Display for ContextChain {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let prefix = match self.prefix_mode {
+            ChainMode::Persistent => '@',
+            ChainMode::Ephemeral => '%',
+            ChainMode::Action => '#',
+        };
+        let anchor_str = match self.anchor {
+            Anchor::Var => "var",
+            Anchor::Doc => "doc",
+        };
         if self.is_fqcc {
-            // FQCC: base@project.workspace.anchor.tail
+            // FQCC: prefix+base@project.workspace.anchor.tail
             let base_part = self.base.as_ref().unwrap();
-            let anchor_str = match self.anchor {
-                Anchor::Var => "var",
-                Anchor::Doc => "doc",
-            };
-            write!(f, "{}@{}.{}.{}.{}", 
-                    base_part, self.project, self.workspace, anchor_str, self.tail)
+            write!(f, "{}{}@{}.{}.{}.{}", 
+                    prefix, base_part, self.project, self.workspace, anchor_str, self.tail)
         } else {
             // CDCC: prefix+project.workspace.anchor.tail
-            let prefix = match self.prefix_mode {
-                ChainMode::Persistent => '@',
-                ChainMode::Ephemeral => '%',
-                ChainMode::Action => '#',
-            };
-            let anchor_str = match self.anchor {
-                Anchor::Var => "var",
-                Anchor::Doc => "doc",
-            };
             write!(f, "{}{}.{}.{}.{}", 
                     prefix, self.project, self.workspace, anchor_str, self.tail)
         }
@@ -50,6 +46,189 @@ impl fmt::Display for ResolvedContext {
     }
 }
 
+// ============================================================================
+// DOCUMENT SEGMENT PATHS
+// ============================================================================
+
+/// Segment addressed when a document path has no segment part
+pub const ROOT_SEGMENT: &str = "_root";
+
+/// Separator between document key and segment path (`README/api/v2/schema`)
+pub const SEGMENT_SEPARATOR: char = '/';
+
+impl ResolvedContext {
+    /// Document key of a doc tail (`README` for `README/header`)
+    pub fn doc_key(&self) -> &str {
+        split_doc_path(&self.tail).0
+    }
+    
+    /// Segment path of a doc tail, `_root` when the tail names a whole document
+    pub fn segment_path(&self) -> &str {
+        split_doc_path(&self.tail).1
+    }
+}
+
+/// Split a document path into (doc_key, segment_path)
+///
+/// `README/api/v2` splits on the first '/'. Paths without '/' fall back to the
+/// older `doc.segment` form; a bare key addresses the `_root` segment.
+pub fn split_doc_path(path: &str) -> (&str, &str) {
+    path.split_once(SEGMENT_SEPARATOR)
+        .or_else(|| path.split_once('.'))
+        .unwrap_or((path, ROOT_SEGMENT))
+}
+
+// ============================================================================
+// NAMESPACE NAMES
+// ============================================================================
+
+/// Check a project/workspace/keystore name before it is created
+///
+/// Names become chain parts, so they cannot hold chain syntax or shadow an anchor.
+pub fn validate_namespace_name(kind: &str, name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+        && !["var", "doc", "v", "d"].contains(&name.to_lowercase().as_str());
+    if !valid {
+        return Err(BookdbError::Argument(format!(
+            "Invalid {} name '{}': use letters, digits, '_' or '-' (not var/doc)", kind, name
+        )));
+    }
+    Ok(())
+}
+
+// ============================================================================
+// WILDCARD CHAINS
+// ============================================================================
+
+/// Wildcard matching any run of characters in a chain part (`@myapp.*.var.secrets`)
+pub const WILDCARD: char = '*';
+
+/// Whether a chain part is a glob (`*`, `prod-*`, `db?`) rather than a name
+pub fn is_glob(part: &str) -> bool {
+    part.contains(WILDCARD) || part.contains('?')
+}
+
+/// Match `text` against a glob where `*` is any run and `?` any one character
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // Position of the last `*` and the text index it is currently covering up to
+    let mut backtrack: Option<(usize, usize)> = None;
+    
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == WILDCARD {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star, covered)) = backtrack {
+            // Let the last `*` swallow one more character and retry
+            p = star + 1;
+            t = covered + 1;
+            backtrack = Some((star, t));
+        } else {
+            return false;
+        }
+    }
+    
+    pattern[p..].iter().all(|c| *c == WILDCARD)
+}
+
+/// Chain prefix spanning every base in the data dir (`*@ROOT.GLOBAL.var.MAIN`)
+pub const ALL_BASES: &str = "*@";
+
+impl ContextChain {
+    /// Whether the chain fans out over several contexts
+    pub fn is_wildcard(&self) -> bool {
+        self.spans_bases() || is_glob(&self.project) || is_glob(&self.workspace) || is_glob(&self.tail)
+    }
+    
+    /// Whether the base part is a glob, so the chain reaches past one base file
+    pub fn spans_bases(&self) -> bool {
+        self.base.as_deref().is_some_and(is_glob)
+    }
+}
+
+impl ResolvedContext {
+    /// Whether the context fans out over several contexts
+    pub fn is_wildcard(&self) -> bool {
+        self.spans_bases() || is_glob(&self.project) || is_glob(&self.workspace) || is_glob(&self.tail)
+    }
+    
+    /// Whether the base part is a glob, so the context reaches past one base file
+    pub fn spans_bases(&self) -> bool {
+        is_glob(&self.base)
+    }
+    
+    /// The same context on another base
+    pub fn with_base(&self, base: &str) -> ResolvedContext {
+        ResolvedContext {
+            base: base.to_string(),
+            ..self.clone()
+        }
+    }
+    
+    /// The same context pointed at a concrete project/workspace/tail
+    pub fn with_target(&self, project: &str, workspace: &str, tail: &str) -> ResolvedContext {
+        ResolvedContext {
+            project: project.to_string(),
+            workspace: workspace.to_string(),
+            tail: tail.to_string(),
+            ..self.clone()
+        }
+    }
+    
+    /// Fully-qualified chain, `base@project.workspace.anchor.tail`
+    pub fn qualified(&self) -> String {
+        format!("{}@{}", self.base, self)
+    }
+}
+
+// ============================================================================
+// NAMESPACE LEVELS
+// ============================================================================
+
+/// How much of the tree a chain names for whole-namespace commands (`mv`, `cp`)
+///
+/// A `*` tail names the workspace and `*.*` the project:
+/// `@app.*.var.*` is project `app`, `@app.prod.var.*` workspace `app.prod`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChainLevel {
+    Project,
+    Workspace,
+    Tail,
+}
+
+impl ResolvedContext {
+    /// The namespace level this context names; other globs are rejected
+    pub fn level(&self) -> Result<ChainLevel> {
+        let star = |part: &str| part == WILDCARD.to_string();
+        let level = match (star(&self.workspace), star(&self.tail)) {
+            (true, true) => ChainLevel::Project,
+            (false, true) => ChainLevel::Workspace,
+            (false, false) => ChainLevel::Tail,
+            (true, false) => return Err(BookdbError::ContextParse(format!(
+                "{}: a '*' workspace needs a '*' tail to name the whole project", self
+            ))),
+        };
+        
+        let concrete: &[&str] = match level {
+            ChainLevel::Project => &[&self.base, &self.project],
+            ChainLevel::Workspace => &[&self.base, &self.project, &self.workspace],
+            ChainLevel::Tail => &[&self.base, &self.project, &self.workspace, &self.tail],
+        };
+        if concrete.iter().any(|part| is_glob(part)) {
+            return Err(BookdbError::ContextParse(format!(
+                "{}: only a trailing '*' (whole workspace or project) is allowed here", self
+            )));
+        }
+        Ok(level)
+    }
+}
+
 // ============================================================================
 // CORE PARSING FUNCTION
 // ============================================================================
@@ -61,7 +240,9 @@ pub fn parse_context_chain(raw: &str, fallback_base: &str) -> Result<ContextChai
     }
     
     // Step 1: Determine prefix mode
+    // A bare `*@...` spans every base; it is read-only like `%*@...`
     let (prefix_mode, chain_body) = match raw.chars().next().unwrap() {
+        _ if raw.starts_with(ALL_BASES) => (ChainMode::Ephemeral, raw),
         '@' => (ChainMode::Persistent, &raw[1..]),
         '%' => (ChainMode::Ephemeral, &raw[1..]),
         '#' => (ChainMode::Action, &raw[1..]),
@@ -133,10 +314,22 @@ pub fn parse_context_chain(raw: &str, fallback_base: &str) -> Result<ContextChai
         return Err(BookdbError::ContextParse("Empty tail (keystore/doc_key)".to_string()));
     }
     
+    // Segment paths (README/api/v2) only address documents
+    if let Some((doc_key, seg_path)) = tail.split_once(SEGMENT_SEPARATOR) {
+        if anchor == Anchor::Var {
+            return Err(BookdbError::ContextParse(format!(
+                "Segment path in '{}' is only valid on doc chains", tail)));
+        }
+        if doc_key.is_empty() || seg_path.split(SEGMENT_SEPARATOR).any(|part| part.is_empty()) {
+            return Err(BookdbError::ContextParse(format!(
+                "Empty segment in document path '{}'", tail)));
+        }
+    }
+    
     // Check for reserved namespace violations
     if ["var", "doc"].contains(&project.as_str()) ||
        ["var", "doc"].contains(&workspace.as_str()) ||
-       ["var", "doc"].contains(&tail.as_str()) {
+       ["var", "doc"].contains(&split_doc_path(&tail).0) {
         return Err(BookdbError::ContextParse(
             "Cannot use 'var' or 'doc' as namespace names".to_string()));
     }
@@ -155,6 +348,68 @@ pub fn parse_context_chain(raw: &str, fallback_base: &str) -> Result<ContextChai
     })
 }
 
+// ============================================================================
+// RELATIVE CHAINS
+// ============================================================================
+
+/// Parse a relative chain (`..`, `.staging`, `:secrets`, `~`)
+///
+/// The prefix is optional and defaults to `@`. Returns `Ok(None)` for anything
+/// that is not a relative form so the caller can fall back to full parsing.
+pub fn parse_relative_chain(raw: &str) -> Result<Option<(ChainMode, RelativeChain)>> {
+    let (prefix_mode, body) = match raw.chars().next() {
+        Some('@') => (ChainMode::Persistent, &raw[1..]),
+        Some('%') => (ChainMode::Ephemeral, &raw[1..]),
+        Some('#') => (ChainMode::Action, &raw[1..]),
+        _ => (ChainMode::Persistent, raw),
+    };
+    
+    let relative = match body {
+        "~" => RelativeChain::Superchain,
+        ".." => RelativeChain::Up,
+        _ => {
+            let (kind, name, build): (&str, &str, fn(String) -> RelativeChain) =
+                if let Some(name) = body.strip_prefix('.') {
+                    ("workspace", name, RelativeChain::Workspace)
+                } else if let Some(name) = body.strip_prefix(':') {
+                    ("keystore", name, RelativeChain::Tail)
+                } else {
+                    return Ok(None);
+                };
+            
+            if name.is_empty() || name.contains('.') {
+                return Err(BookdbError::ContextParse(format!(
+                    "Invalid relative {} '{}'", kind, body)));
+            }
+            if ["var", "doc"].contains(&split_doc_path(name).0) {
+                return Err(BookdbError::ContextParse(
+                    "Cannot use 'var' or 'doc' as namespace names".to_string()));
+            }
+            build(name.to_string())
+        }
+    };
+    
+    Ok(Some((prefix_mode, relative)))
+}
+
+// ============================================================================
+// V3 PARSING BRIDGE
+// ============================================================================
+
+/// Parse through the V3 segment parser and hand back a legacy ContextChain
+///
+/// Exported as `parse_context_chain` under the `context-chain-v3` feature so
+/// callers keep the same signature; CDCC chains still get the fallback base.
+#[cfg(feature = "context-chain-v3")]
+pub fn parse_context_chain_v3_compat(raw: &str, fallback_base: &str) -> Result<ContextChain> {
+    let v3 = super::types::segment::parse_context_chain_v3(raw)?;
+    let mut chain = ContextChain::from(v3);
+    if chain.base.is_none() {
+        chain.base = Some(fallback_base.to_string());
+    }
+    Ok(chain)
+}
+
 // ============================================================================
 // TESTS
 // ============================================================================
@@ -164,9 +419,9 @@ mod tests {
     use super::*;
     
     #[test]
-    fn test_fqcc_parsing() ->  Result<(), E> {
-        // CORRECT: Base has no prefix
-        let chain = parse_context_chain("work@website.api_keys.var.credentials", "home")?;
+    fn test_fqcc_parsing() -> Result<()> {
+        // The chain prefix comes first, then the base
+        let chain = parse_context_chain("@work@website.api_keys.var.credentials", "home")?;
         
         assert_eq!(chain.base, Some("work".to_string()));
         assert_eq!(chain.project, "website");
@@ -180,7 +435,7 @@ mod tests {
     }
     
     #[test]
-    fn test_cdcc_parsing() ->  Result<(), E> {
+    fn test_cdcc_parsing() -> Result<()> {
         // CORRECT: CDCC with @ prefix on chain, not base
         let chain = parse_context_chain("@frontend.deployment.var.production", "work")?;
         
@@ -195,15 +450,14 @@ mod tests {
     }
     
     #[test]
-    fn test_base_prefix_rejection() {
-        // INVALID: Base cannot have prefixes
-        assert!(parse_context_chain("@work@website.api_keys.var.credentials", "home").is_err());
-        assert!(parse_context_chain("%work@website.api_keys.var.credentials", "home").is_err());
-        assert!(parse_context_chain("#work@website.api_keys.var.credentials", "home").is_err());
+    fn test_unprefixed_chain_rejection() {
+        // INVALID: every chain starts with @, % or #, FQCC included
+        assert!(parse_context_chain("work@website.api_keys.var.credentials", "home").is_err());
+        assert!(parse_context_chain("website.api_keys.var.credentials", "home").is_err());
     }
     
     #[test]
-    fn test_ephemeral_mode() ->  Result<(), E> {
+    fn test_ephemeral_mode() -> Result<()> {
         let chain = parse_context_chain("%temp@quick.test.var.check", "home")?;
         
         assert_eq!(chain.prefix_mode, ChainMode::Ephemeral);
@@ -213,7 +467,17 @@ mod tests {
     }
     
     #[test]
-    fn test_doc_anchor() ->  Result<(), E> {
+    fn test_action_mode() -> Result<()> {
+        let chain = parse_context_chain("#quick.action.var.test", "home")?;
+        
+        assert_eq!(chain.prefix_mode, ChainMode::Action);
+        assert_eq!(format!("{}", chain), "#quick.action.var.test");
+        
+        Ok(())
+    }
+    
+    #[test]
+    fn test_doc_anchor() -> Result<()> {
         let chain = parse_context_chain("@project.docs.doc.README_md", "home")?;
         
         assert_eq!(chain.anchor, Anchor::Doc);
@@ -223,7 +487,7 @@ mod tests {
     }
     
     #[test]
-    fn test_case_insensitive_anchor() ->  Result<(), E> {
+    fn test_case_insensitive_anchor() -> Result<()> {
         let chain1 = parse_context_chain("@proj.work.VAR.test", "home")?;
         let chain2 = parse_context_chain("@proj.work.var.test", "home")?;
         let chain3 = parse_context_chain("@proj.work.Var.test", "home")?;
@@ -249,17 +513,25 @@ mod tests {
         // Reserved namespace names
         assert!(parse_context_chain("@var.workspace.var.store", "home").is_err());
         assert!(parse_context_chain("@proj.doc.var.store", "home").is_err());
+        assert!(parse_context_chain("@proj.workspace.var.var", "home").is_err());
+        assert!(parse_context_chain("@proj.workspace.var.doc", "home").is_err());
+        
+        // Empty components
+        assert!(parse_context_chain("@.workspace.var.store", "home").is_err());
+        assert!(parse_context_chain("@proj..var.store", "home").is_err());
+        assert!(parse_context_chain("@proj.workspace.var.", "home").is_err());
         
         // Missing prefix
         assert!(parse_context_chain("proj.workspace.var.store", "home").is_err());
     }
     
     #[test]
-    fn test_display_formatting() ->  Result<(), E> {
-        // FQCC: no prefix on base name
-        let chain = parse_context_chain("work@website.api_keys.var.credentials", "home")?;
+    fn test_display_formatting() -> Result<()> {
+        // FQCC: the prefix is kept ahead of the base, so the display parses back
+        let chain = parse_context_chain("%work@website.api_keys.var.credentials", "home")?;
         let display = format!("{}", chain);
-        assert_eq!(display, "work@website.api_keys.var.credentials");
+        assert_eq!(display, "%work@website.api_keys.var.credentials");
+        assert_eq!(parse_context_chain(&display, "home")?, chain);
         
         // CDCC: prefix on chain, not base
         let ephemeral = parse_context_chain("%temp.test.doc.readme", "home")?;
@@ -268,4 +540,137 @@ mod tests {
         
         Ok(())
     }
+
+    #[cfg(feature = "context-chain-v3")]
+    #[test]
+    fn test_v3_bridge_matches_v1() -> Result<()> {
+        for raw in ["@proj.ws.var.store", "%other@scratch.tmp.doc.readme", "#deploy.prod.VAR.app"] {
+            let v1 = parse_context_chain(raw, "home")?;
+            let v3 = parse_context_chain_v3_compat(raw, "home")?;
+            assert_eq!(v1, v3, "chain {} parsed differently", raw);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_doc_segment_path_chain() -> Result<()> {
+        let chain = parse_context_chain("@proj.ws.doc.README/api/v2/schema", "home")?;
+        assert_eq!(chain.tail, "README/api/v2/schema");
+        
+        let resolved = crate::bookdb::service::ctx::DefaultResolver::new()
+            .resolve_cdcc(&chain, &Default::default());
+        assert_eq!(resolved.doc_key(), "README");
+        assert_eq!(resolved.segment_path(), "api/v2/schema");
+        
+        // Var chains and empty path parts are rejected
+        assert!(parse_context_chain("@proj.ws.var.store/sub", "home").is_err());
+        assert!(parse_context_chain("@proj.ws.doc.README//x", "home").is_err());
+        assert!(parse_context_chain("@proj.ws.doc./header", "home").is_err());
+        
+        Ok(())
+    }
+    
+    #[test]
+    fn test_split_doc_path() {
+        assert_eq!(split_doc_path("README"), ("README", ROOT_SEGMENT));
+        assert_eq!(split_doc_path("README/header"), ("README", "header"));
+        assert_eq!(split_doc_path("README/api/v2"), ("README", "api/v2"));
+        assert_eq!(split_doc_path("README.header"), ("README", "header"));
+    }
+
+    #[test]
+    fn test_validate_namespace_name() {
+        assert!(validate_namespace_name("keystore", "secrets").is_ok());
+        assert!(validate_namespace_name("workspace", "prod-eu_1").is_ok());
+        assert!(validate_namespace_name("keystore", "").is_err());
+        assert!(validate_namespace_name("keystore", "a.b").is_err());
+        assert!(validate_namespace_name("project", "*").is_err());
+        assert!(validate_namespace_name("workspace", "VAR").is_err());
+    }
+    
+    #[test]
+    fn test_chain_level() -> Result<()> {
+        let level = |raw: &str| -> Result<ChainLevel> {
+            let chain = parse_context_chain(raw, "home")?;
+            ResolvedContext {
+                base: "home".to_string(),
+                project: chain.project,
+                workspace: chain.workspace,
+                anchor: chain.anchor,
+                tail: chain.tail,
+                prefix_mode: chain.prefix_mode,
+            }.level()
+        };
+        
+        assert_eq!(level("@app.*.var.*")?, ChainLevel::Project);
+        assert_eq!(level("@app.prod.var.*")?, ChainLevel::Workspace);
+        assert_eq!(level("@app.prod.var.secrets")?, ChainLevel::Tail);
+        assert!(level("@app.*.var.secrets").is_err());
+        assert!(level("@app.prod-*.var.*").is_err());
+        
+        Ok(())
+    }
+    
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("*", "anything"));
+        assert!(glob_match("prod*", "production"));
+        assert!(glob_match("*_KEY", "API_KEY"));
+        assert!(glob_match("db?", "db1"));
+        assert!(glob_match("a*b*c", "aXXbYYc"));
+        assert!(!glob_match("a*b*c", "aXXbYY"));
+        assert!(!glob_match("prod", "production"));
+        assert!(!glob_match("db?", "db"));
+    }
+    
+    #[test]
+    fn test_wildcard_chain() -> Result<()> {
+        let chain = parse_context_chain("@myapp.*.var.secrets", "home")?;
+        assert!(chain.is_wildcard());
+        assert_eq!(chain.workspace, "*");
+        
+        let chain = parse_context_chain("@*.production.var.*", "home")?;
+        assert!(chain.is_wildcard());
+        
+        let chain = parse_context_chain("@myapp.prod.var.secrets", "home")?;
+        assert!(!chain.is_wildcard());
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_cross_base_chain() -> Result<()> {
+        let chain = parse_context_chain("*@ROOT.GLOBAL.var.MAIN", "home")?;
+        assert_eq!(chain.base.as_deref(), Some("*"));
+        assert_eq!(chain.prefix_mode, ChainMode::Ephemeral);
+        assert!(chain.spans_bases());
+        assert!(chain.is_wildcard());
+        
+        let chain = parse_context_chain("%w*@myapp.prod.var.secrets", "home")?;
+        assert!(chain.spans_bases());
+        
+        let chain = parse_context_chain("@work@myapp.prod.var.secrets", "home")?;
+        assert!(!chain.spans_bases());
+        
+        Ok(())
+    }
+    
+    #[test]
+    fn test_parse_relative_chain() -> Result<()> {
+        assert_eq!(parse_relative_chain("..")?, Some((ChainMode::Persistent, RelativeChain::Up)));
+        assert_eq!(parse_relative_chain("%~")?, Some((ChainMode::Ephemeral, RelativeChain::Superchain)));
+        assert_eq!(parse_relative_chain("@.staging")?,
+            Some((ChainMode::Persistent, RelativeChain::Workspace("staging".to_string()))));
+        assert_eq!(parse_relative_chain(":secrets")?,
+            Some((ChainMode::Persistent, RelativeChain::Tail("secrets".to_string()))));
+        
+        // Full chains are not relative
+        assert_eq!(parse_relative_chain("@proj.ws.var.store")?, None);
+        
+        assert!(parse_relative_chain(".").is_err());
+        assert!(parse_relative_chain(":a.b").is_err());
+        assert!(parse_relative_chain(".var").is_err());
+        
+        Ok(())
+    }
 }
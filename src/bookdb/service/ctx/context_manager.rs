@@ -6,7 +6,8 @@ use stderr::{Stderr, StderrConfig};
 use crate::error::{Result, BookdbError};
 
 // Import from new types structure
-use super::typesV1::{ContextChain, ResolvedContext, CursorState, DefaultResolver, Anchor, ChainMode};
+use super::types::typesV1::{ContextChain, ResolvedContext, CursorState, DefaultResolver, Anchor, ChainMode};
+use super::alias::{expand_alias, lookup_alias};
 
 // TODO: This import needs to be fixed - find where Config is defined
 use crate::bookdb::app::sup::config::Config; 
@@ -36,6 +37,23 @@ impl ContextManager {
         self.resolver.resolve_cdcc(chain, cursor_state)
     }
     
+    /// Parse a chain, expanding aliases and relative forms against the cursor
+    ///
+    /// `:name` first looks for an alias (cursor base, then global); only an
+    /// unknown name is treated as a relative keystore swap.
+    pub fn parse_chain(&self, raw: &str, cursor_state: &CursorState) -> Result<ContextChain> {
+        let base_db = self.config.get_base_path(&cursor_state.base_cursor);
+        let global_file = self.config.get_aliases_file_path();
+        
+        match expand_alias(raw, |name| lookup_alias(name, &base_db, &global_file))? {
+            Some(expanded) => {
+                Stderr::new().trace_fn("context_manager", &format!("alias {} -> {}", raw, expanded));
+                self.resolver.parse_against_cursor(&expanded, cursor_state)
+            }
+            None => self.resolver.parse_against_cursor(raw, cursor_state),
+        }
+    }
+    
     /// Load current cursor state from disk - delegates to CursorState
     pub fn load_cursor_state(&mut self) -> Result<CursorState> {
         self.logger.trace_fn("context_manager", "loading cursor state");
@@ -43,27 +61,28 @@ impl ContextManager {
     }
     
     /// Save cursor state to disk - delegates to CursorState  
-    pub fn save_cursor_state(&mut self, cursor_state: &CursorState) ->  Result<(), E> {
+    pub fn save_cursor_state(&mut self, cursor_state: &CursorState) -> Result<()> {
         self.logger.trace_fn("context_manager", "saving cursor state");
         cursor_state.save_to_disk(&self.config)
     }
     
-    /// Update cursor with atomicity rules and context banner
-    pub fn update_cursor(&mut self, new_context: &ContextChain, current_cursors: &mut CursorState) ->  Result<(), E> {
-        let old_context = current_cursors.context_cursor.clone();
-        
-        // Apply atomicity rules if changing from existing context
-        let final_context = if let Some(ref old) = old_context {
-            self.resolver.apply_atomicity(old, new_context)
-        } else {
-            new_context.clone()
-        };
+    /// Move the cursor to `new_context` exactly as given, with a context banner
+    ///
+    /// Atomicity resets only apply to relative chains (`.ws`, `:keystore`),
+    /// which `resolve_relative` has already expanded; an explicit chain names
+    /// every level and is saved as-is.
+    pub fn update_cursor(&mut self, new_context: &ContextChain, current_cursors: &mut CursorState) -> Result<()> {
+        if new_context.is_wildcard() {
+            return Err(BookdbError::Argument(format!(
+                "Wildcard chain {} names several contexts and cannot become the cursor", new_context
+            )));
+        }
         
         // Update cursor state (delegates to CursorState)
-        current_cursors.update_context(&final_context);
+        current_cursors.update_context(new_context);
         
         // Show context banner if context changed significantly
-        self.show_context_banner_if_changed(&final_context)?;
+        self.show_context_banner_if_changed(new_context)?;
         
         // Save to disk (delegates to CursorState)
         self.save_cursor_state(current_cursors)?;
@@ -71,8 +90,65 @@ impl ContextManager {
         Ok(())
     }
     
+    /// Enter a context chain for one command, letting its prefix mode decide persistence
+    ///
+    /// `@` chains move the cursor (and rewrite the cursor files); `%` and `#`
+    /// chains, and wildcard chains of any prefix, leave it untouched.
+    pub fn enter_context(&mut self, chain: &ContextChain, cursor_state: &mut CursorState) -> Result<ResolvedContext> {
+        // Wildcard chains fan out for one read and never move the cursor
+        if chain.is_wildcard() {
+            self.logger.trace_fn("context_manager", "wildcard chain, cursor unchanged");
+            return Ok(self.resolve_context(chain, cursor_state));
+        }
+        
+        match chain.prefix_mode {
+            ChainMode::Persistent => {
+                self.update_cursor(chain, cursor_state)?;
+                Ok(self.resolve_context(chain, cursor_state))
+            }
+            ChainMode::Ephemeral => {
+                self.logger.trace_fn("context_manager", "ephemeral chain, cursor unchanged");
+                self.show_context_banner_if_changed(chain)?;
+                Ok(self.resolve_context(chain, cursor_state))
+            }
+            ChainMode::Action => {
+                self.logger.trace_fn("context_manager", "action chain, cursor unchanged");
+                Ok(self.resolve_context(chain, cursor_state))
+            }
+        }
+    }
+
+    /// Go back to the previous context (`use -`), exactly as it was left
+    pub fn back(&mut self, cursor_state: &mut CursorState) -> Result<ResolvedContext> {
+        let previous = cursor_state.take_previous()
+            .ok_or_else(|| BookdbError::Argument("No previous context in cursor history".to_string()))?;
+        self.restore_context(&previous, cursor_state)
+    }
+    
+    /// Save the current context on the stack, then move to `chain`
+    pub fn push_context(&mut self, chain: &ContextChain, cursor_state: &mut CursorState) -> Result<ResolvedContext> {
+        cursor_state.push_current();
+        self.update_cursor(chain, cursor_state)?;
+        Ok(self.resolve_context(chain, cursor_state))
+    }
+    
+    /// Return to the most recently pushed context
+    pub fn pop_context(&mut self, cursor_state: &mut CursorState) -> Result<ResolvedContext> {
+        let saved = cursor_state.pop_stack()
+            .ok_or_else(|| BookdbError::Argument("Cursor stack is empty".to_string()))?;
+        self.restore_context(&saved, cursor_state)
+    }
+    
+    /// Move the cursor to a remembered context without atomicity resets
+    fn restore_context(&mut self, context: &ContextChain, cursor_state: &mut CursorState) -> Result<ResolvedContext> {
+        cursor_state.update_context(context);
+        self.show_context_banner_if_changed(context)?;
+        self.save_cursor_state(cursor_state)?;
+        Ok(self.resolve_context(context, cursor_state))
+    }
+    
     /// Show context banner when context changes (private helper)
-    fn show_context_banner_if_changed(&mut self, context: &ContextChain) ->  Result<(), E> {
+    fn show_context_banner_if_changed(&mut self, context: &ContextChain) -> Result<()> {
         let context_display = format!("{}", context);
         
         // Only show banner if context actually changed
@@ -89,7 +165,7 @@ impl ContextManager {
     }
     
     /// Display current context banner
-    pub fn show_context_banner(&mut self, context: &ContextChain) ->  Result<(), E> {
+    pub fn show_context_banner(&mut self, context: &ContextChain) -> Result<()> {
         let base_part = context.base.as_ref()
             .map(|b| format!("{}@", b))
             .unwrap_or_else(|| "<current>@".to_string());
@@ -126,7 +202,7 @@ impl ContextManager {
     }
     
     /// Show cursor status in a nice format
-    pub fn show_cursor_status(&mut self, cursor_state: &CursorState) ->  Result<(), E> {
+    pub fn show_cursor_status(&mut self, cursor_state: &CursorState) -> Result<()> {
         self.logger.banner("Current Cursor Status", '=')?;
         
         self.logger.info(&format!("Base: {}", cursor_state.base_cursor));
@@ -172,28 +248,59 @@ impl ContextManager {
         
         Ok(())
     }
+    
+    /// Show cursor history (most recent first) and the push/pop stack
+    pub fn show_cursor_history(&mut self, cursor_state: &CursorState) -> Result<()> {
+        self.logger.banner("Cursor History", '=')?;
+        
+        if cursor_state.history.is_empty() {
+            self.logger.info("History: <empty>");
+        } else {
+            let entries: Vec<String> = cursor_state.history.iter().rev()
+                .enumerate()
+                .map(|(i, context)| format!("-{} {}", i + 1, context))
+                .collect();
+            let refs: Vec<&str> = entries.iter().map(|e| e.as_str()).collect();
+            self.logger.list(&refs, "→")?;
+        }
+        
+        self.logger.info("");
+        if cursor_state.stack.is_empty() {
+            self.logger.info("Stack: <empty>");
+        } else {
+            self.logger.info(&format!("Stack ({} saved, top first):", cursor_state.stack.len()));
+            let entries: Vec<String> = cursor_state.stack.iter().rev()
+                .map(|context| context.to_string())
+                .collect();
+            let refs: Vec<&str> = entries.iter().map(|e| e.as_str()).collect();
+            self.logger.list(&refs, "→")?;
+        }
+        
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use tempfile::TempDir;
+    use std::fs;
+    use crate::bookdb::app::sup::config::XdgDirs;
+    use super::super::context::parse_context_chain;
     
     fn create_test_context_manager() -> (ContextManager, TempDir) {
         let temp_dir = TempDir::new().unwrap();
-        let mut config = Config::default();
-        // TODO: Update this based on actual Config implementation
-        // config.set_data_dir(temp_dir.path().to_path_buf());
+        let config = Config { xdg: XdgDirs::new(temp_dir.path()).unwrap() };
         
         let manager = ContextManager::new(config);
         (manager, temp_dir)
     }
     
     #[test]
-    fn test_context_banner_display() ->  Result<(), E> {
+    fn test_context_banner_display() -> Result<()> {
         let (mut manager, _temp) = create_test_context_manager();
         
-        let context = super::super::context::parse_context_chain("work@proj.workspace.var.keystore", "home")?;
+        let context = super::super::context::parse_context_chain("@work@proj.workspace.var.keystore", "home")?;
         
         // This should not panic
         manager.show_context_banner(&context)?;
@@ -202,7 +309,7 @@ mod tests {
     }
     
     #[test]
-    fn test_context_manager_delegates_to_cursor_state() ->  Result<(), E> {
+    fn test_context_manager_delegates_to_cursor_state() -> Result<()> {
         let (mut manager, _temp) = create_test_context_manager();
         
         // Test that cursor operations are delegated properly
@@ -211,5 +318,189 @@ mod tests {
         
         Ok(())
     }
+
+    // Prefix mode vs cursor persistence
+
+    fn read_cursor_files(config: &Config) -> (Option<String>, Option<String>) {
+        (
+            fs::read_to_string(config.get_cursor_base_path()).ok(),
+            fs::read_to_string(config.get_cursor_chain_path()).ok(),
+        )
+    }
+
+    #[test]
+    fn test_persistent_chain_updates_cursor_files() -> Result<()> {
+        let (mut manager, _temp) = create_test_context_manager();
+        let config = manager.config.clone();
+        let mut cursor_state = manager.load_cursor_state()?;
+
+        let chain = parse_context_chain("@work@proj.ws.var.store", &cursor_state.base_cursor)?;
+        let resolved = manager.enter_context(&chain, &mut cursor_state)?;
+        assert_eq!(resolved.base, "work");
+
+        let (base, chain_file) = read_cursor_files(&config);
+        assert_eq!(base.unwrap(), config.get_base_path("work").display().to_string());
+        assert!(chain_file.unwrap().contains("proj.ws.var.store"));
+
+        // Reload from disk: state survives the process
+        let reloaded = CursorState::load_from_disk(&config)?;
+        assert_eq!(reloaded.base_cursor, "work");
+        assert_eq!(reloaded.context_cursor.unwrap().tail, "store");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ephemeral_chain_is_side_effect_free() -> Result<()> {
+        let (mut manager, _temp) = create_test_context_manager();
+        let config = manager.config.clone();
+        let mut cursor_state = manager.load_cursor_state()?;
+
+        // Establish a persistent cursor first
+        let chain = parse_context_chain("@proj.ws.var.store", &cursor_state.base_cursor)?;
+        manager.enter_context(&chain, &mut cursor_state)?;
+        let before = read_cursor_files(&config);
+        let json_before = fs::read_to_string(config.get_cursor_file_path())?;
+
+        // Ephemeral chain on another project and base
+        let chain = parse_context_chain("%other@scratch.tmp.var.keys", &cursor_state.base_cursor)?;
+        let resolved = manager.enter_context(&chain, &mut cursor_state)?;
+
+        assert_eq!(resolved.base, "other");
+        assert_eq!(resolved.project, "scratch");
+        assert_eq!(cursor_state.base_cursor, "home");
+        assert_eq!(cursor_state.context_cursor.as_ref().unwrap().project, "proj");
+
+        assert_eq!(read_cursor_files(&config), before);
+        assert_eq!(fs::read_to_string(config.get_cursor_file_path())?, json_before);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ephemeral_chain_without_prior_cursor_writes_nothing() -> Result<()> {
+        let (mut manager, _temp) = create_test_context_manager();
+        let config = manager.config.clone();
+        let mut cursor_state = manager.load_cursor_state()?;
+
+        let chain = parse_context_chain("%proj.ws.var.store", &cursor_state.base_cursor)?;
+        manager.enter_context(&chain, &mut cursor_state)?;
+
+        assert_eq!(read_cursor_files(&config), (None, None));
+        assert!(!config.get_cursor_file_path().exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_action_chain_leaves_cursor_untouched() -> Result<()> {
+        let (mut manager, _temp) = create_test_context_manager();
+        let config = manager.config.clone();
+        let mut cursor_state = manager.load_cursor_state()?;
+
+        let chain = parse_context_chain("#deploy.prod.var.app", &cursor_state.base_cursor)?;
+        manager.enter_context(&chain, &mut cursor_state)?;
+
+        assert_eq!(read_cursor_files(&config), (None, None));
+        assert!(cursor_state.context_cursor.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_explicit_chain_keeps_its_tail_across_projects() -> Result<()> {
+        let (mut manager, _temp) = create_test_context_manager();
+        let config = manager.config.clone();
+        let mut cursor_state = manager.load_cursor_state()?;
+
+        let chain = parse_context_chain("@app.dev.var.secrets", &cursor_state.base_cursor)?;
+        manager.enter_context(&chain, &mut cursor_state)?;
+
+        // New project and workspace: the explicit tail must survive
+        let chain = parse_context_chain("@infra.prod.var.credentials", &cursor_state.base_cursor)?;
+        let resolved = manager.enter_context(&chain, &mut cursor_state)?;
+        assert_eq!((resolved.project.as_str(), resolved.workspace.as_str(), resolved.tail.as_str()),
+                   ("infra", "prod", "credentials"));
+
+        // What was saved is what was resolved
+        let saved = CursorState::load_from_disk(&config)?.context_cursor.unwrap();
+        assert_eq!((saved.project, saved.workspace, saved.tail),
+                   (resolved.project, resolved.workspace, resolved.tail));
+        assert!(read_cursor_files(&config).1.unwrap().contains("infra.prod.var.credentials"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_push_pop_across_projects() -> Result<()> {
+        let (mut manager, _temp) = create_test_context_manager();
+        let config = manager.config.clone();
+        let mut cursor_state = manager.load_cursor_state()?;
+
+        let app = parse_context_chain("@app.dev.var.secrets", &cursor_state.base_cursor)?;
+        manager.enter_context(&app, &mut cursor_state)?;
+
+        // Pushing onto another project keeps the pushed chain's workspace and tail
+        let infra = parse_context_chain("@infra.prod.var.credentials", &cursor_state.base_cursor)?;
+        let resolved = manager.push_context(&infra, &mut cursor_state)?;
+        assert_eq!((resolved.workspace.as_str(), resolved.tail.as_str()), ("prod", "credentials"));
+        let saved = CursorState::load_from_disk(&config)?.context_cursor.unwrap();
+        assert_eq!((saved.project.as_str(), saved.workspace.as_str(), saved.tail.as_str()),
+                   ("infra", "prod", "credentials"));
+
+        // Popping returns to the original project unchanged
+        let resolved = manager.pop_context(&mut cursor_state)?;
+        assert_eq!((resolved.project.as_str(), resolved.workspace.as_str(), resolved.tail.as_str()),
+                   ("app", "dev", "secrets"));
+        let saved = CursorState::load_from_disk(&config)?.context_cursor.unwrap();
+        assert_eq!((saved.project.as_str(), saved.tail.as_str()), ("app", "secrets"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_mv_and_cp_chains_expand_aliases() -> Result<()> {
+        let (mut manager, _temp) = create_test_context_manager();
+        let cursor_state = manager.load_cursor_state()?;
+        let base_db = manager.config.get_base_path(&cursor_state.base_cursor);
+        std::fs::create_dir_all(base_db.parent().unwrap())?;
+        crate::bookdb::service::db::Database::create_or_open(&base_db)?
+            .set_alias("prodcreds", "@infra.production.var.credentials")?;
+
+        // mv and cp resolve both ends like this, without entering either
+        let resolved = manager.resolve_context(&manager.parse_chain(":prodcreds", &cursor_state)?, &cursor_state);
+        assert_eq!((resolved.project.as_str(), resolved.workspace.as_str(), resolved.tail.as_str()),
+                   ("infra", "production", "credentials"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_history_and_stack_round_trip() -> Result<()> {
+        let (mut manager, _temp) = create_test_context_manager();
+        let config = manager.config.clone();
+        let mut cursor_state = manager.load_cursor_state()?;
+
+        let prod = parse_context_chain("@app.prod.var.secrets", &cursor_state.base_cursor)?;
+        let staging = parse_context_chain("@app.staging.var.secrets", &cursor_state.base_cursor)?;
+        manager.enter_context(&prod, &mut cursor_state)?;
+        manager.enter_context(&staging, &mut cursor_state)?;
+
+        // `use -` restores prod exactly (no atomicity reset of the keystore)
+        let resolved = manager.back(&mut cursor_state)?;
+        assert_eq!((resolved.workspace.as_str(), resolved.tail.as_str()), ("prod", "secrets"));
+
+        // push/pop
+        let scratch = parse_context_chain("@scratch.tmp.var.keys", &cursor_state.base_cursor)?;
+        manager.push_context(&scratch, &mut cursor_state)?;
+        let reloaded = CursorState::load_from_disk(&config)?;
+        assert_eq!(reloaded.stack.len(), 1);
+        assert_eq!(reloaded.context_cursor.unwrap().project, "scratch");
+
+        let resolved = manager.pop_context(&mut cursor_state)?;
+        assert_eq!(resolved.workspace, "prod");
+        assert!(manager.pop_context(&mut cursor_state).is_err());
+
+        Ok(())
+    }
 }
 
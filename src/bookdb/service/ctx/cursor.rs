@@ -3,7 +3,8 @@
 
 use crate::error::{Result, BookdbError};
 use crate::bookdb::app::sup::config::Config;
-use super::typesV1::{CursorState, ContextChain};
+use super::types::typesV1::{CursorState, ContextChain, ResolvedContext};
+use super::context::ChainLevel;
 use serde::{Serialize, Deserialize};
 use std::fs;
 
@@ -11,6 +12,8 @@ use std::fs;
 // CURSOR STATE OPERATIONS
 // ============================================================================
 
+/// Maximum number of previous contexts kept in the cursor file
+pub const CURSOR_HISTORY_LIMIT: usize = 20;
 
 
 impl CursorState {
@@ -23,7 +26,7 @@ impl CursorState {
         }
         
         let content = fs::read_to_string(&cursor_file)
-            .map_err(|e| BookdbError::Io(format!("Failed to read cursor file: {}", e)))?;
+            .map_err(|e| BookdbError::Io(std::io::Error::other(format!("Failed to read cursor file: {}", e))))?;
             
         let cursor_state: CursorState = serde_json::from_str(&content)
             .map_err(|e| BookdbError::ConfigParse(format!("Invalid cursor file: {}", e)))?;
@@ -32,42 +35,129 @@ impl CursorState {
     }
     
     /// Save cursor state to disk
-    pub fn save_to_disk(&self, config: &Config) ->  Result<(), E> {
+    ///
+    /// Writes the JSON state plus the plain-text `cursor.base` / `cursor.chain`
+    /// files that shell integration reads.
+    pub fn save_to_disk(&self, config: &Config) -> Result<()> {
         let cursor_file = config.get_cursor_file_path();
         
         // Ensure parent directory exists
         if let Some(parent) = cursor_file.parent() {
-            fs::create_dir_all(parent)
-                .map_err(|e| BookdbError::Io(format!("Failed to create cursor directory: {}", e)))?;
+            fs::create_dir_all(parent)?;
         }
         
-        let content = serde_json::to_string_pretty(self)
-            .map_err(|e| BookdbError::ConfigParse(format!("Failed to serialize cursor state: {}", e)))?;
-            
-        fs::write(&cursor_file, content)
-            .map_err(|e| BookdbError::Io(format!("Failed to write cursor file: {}", e)))?;
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(&cursor_file, content)?;
+        
+        let base_db = config.get_base_path(&self.base_cursor);
+        fs::write(config.get_cursor_base_path(), base_db.display().to_string())?;
+        
+        if let Some(ref context) = self.context_cursor {
+            fs::write(config.get_cursor_chain_path(), context.to_string())?;
+        }
         
         Ok(())
     }
     
-    /// Update the context cursor
+    /// Update the context cursor, remembering the context being left
     pub fn update_context(&mut self, new_context: &ContextChain) {
         // Update base cursor if explicitly specified
         if let Some(ref base) = new_context.base {
             self.base_cursor = base.clone();
         }
         
+        if let Some(previous) = self.context_cursor.take() {
+            if previous != *new_context {
+                self.record_history(previous);
+            }
+        }
+        
         // Update context cursor
         self.context_cursor = Some(new_context.clone());
     }
     
+    /// Append to the history, dropping the oldest entries past the limit
+    fn record_history(&mut self, context: ContextChain) {
+        self.history.push(context);
+        if self.history.len() > CURSOR_HISTORY_LIMIT {
+            let excess = self.history.len() - CURSOR_HISTORY_LIMIT;
+            self.history.drain(..excess);
+        }
+    }
+    
+    /// Take the most recent history entry (`use -`)
+    ///
+    /// Restoring it through `update_context` records the current context, so
+    /// repeated `use -` toggles between the last two contexts.
+    pub fn take_previous(&mut self) -> Option<ContextChain> {
+        self.history.pop()
+    }
+    
+    /// Save the current context (or the superchain) on the stack
+    pub fn push_current(&mut self) {
+        let current = self.get_current_context();
+        self.stack.push(current);
+    }
+    
+    /// Take the most recently pushed context
+    pub fn pop_stack(&mut self) -> Option<ContextChain> {
+        self.stack.pop()
+    }
+    
+    /// Switch the active base, keeping the context cursor pointed at it
+    pub fn select_base(&mut self, base: &str) {
+        self.base_cursor = base.to_string();
+        if let Some(ref mut context) = self.context_cursor {
+            context.base = Some(base.to_string());
+        }
+    }
+
+    /// Point cursor, history and stack chains at a moved namespace (`mv`)
+    ///
+    /// Only chains on the base the move happened in are touched; returns
+    /// whether anything changed.
+    pub fn retarget(&mut self, from: &ResolvedContext, to: &ResolvedContext, level: ChainLevel) -> bool {
+        let base_cursor = self.base_cursor.clone();
+        let mut changed = false;
+        
+        let chains = self.context_cursor.iter_mut()
+            .chain(self.history.iter_mut())
+            .chain(self.stack.iter_mut());
+        for chain in chains {
+            if chain.base.as_deref().unwrap_or(&base_cursor) != from.base || chain.project != from.project {
+                continue;
+            }
+            let moved = match level {
+                ChainLevel::Project => true,
+                ChainLevel::Workspace => chain.workspace == from.workspace,
+                ChainLevel::Tail => chain.workspace == from.workspace
+                    && chain.anchor == from.anchor
+                    && chain.tail == from.tail,
+            };
+            if !moved {
+                continue;
+            }
+            
+            chain.project = to.project.clone();
+            if level != ChainLevel::Project {
+                chain.workspace = to.workspace.clone();
+            }
+            if level == ChainLevel::Tail {
+                chain.tail = to.tail.clone();
+            }
+            changed = true;
+        }
+        
+        changed
+    }
+
     /// Get the current context or return the invincible superchain as fallback
     pub fn get_current_context(&self) -> ContextChain {
         if let Some(ref context) = self.context_cursor {
             context.clone()
         } else {
             // Use DefaultResolver to create invincible superchain
-            use super::resolver::DefaultResolver;
+            use super::types::typesV1::DefaultResolver;
             DefaultResolver::create_invincible_superchain(&self.base_cursor)
         }
     }
@@ -89,17 +179,17 @@ pub fn read_cursor(paths: &Paths) -> (Option<String>, Option<String>) {
 }
 
 /// Legacy cursor writing (for backward compatibility)
-pub fn write_cursor(paths: &Paths, base_db_abs: Option<&str>, chain_full: Option<&str>) ->  Result<(), E> {
+pub fn write_cursor(paths: &Paths, base_db_abs: Option<&str>, chain_full: Option<&str>) -> Result<()> {
     if let Some(b) = base_db_abs { 
         if !b.is_empty() { 
             fs::write(&paths.cursor_base_path, b)
-                .map_err(|e| BookdbError::Io(format!("Failed to write cursor base: {}", e)))?; 
+                .map_err(|e| BookdbError::Io(std::io::Error::other(format!("Failed to write cursor base: {}", e))))?; 
         } 
     }
     if let Some(c) = chain_full { 
         if !c.is_empty() { 
             fs::write(&paths.cursor_chain_path, c)
-                .map_err(|e| BookdbError::Io(format!("Failed to write cursor chain: {}", e)))?; 
+                .map_err(|e| BookdbError::Io(std::io::Error::other(format!("Failed to write cursor chain: {}", e))))?; 
         } 
     }
     Ok(())
@@ -115,23 +205,21 @@ pub fn write_cursor(paths: &Paths, base_db_abs: Option<&str>, chain_full: Option
 mod tests {
     use super::*;
     use tempfile::TempDir;
+    use crate::bookdb::app::sup::config::XdgDirs;
     use std::path::PathBuf;
     
     fn create_test_config() -> (Config, TempDir) {
         let temp_dir = TempDir::new().unwrap();
-        // Note: This will need to be adjusted based on your actual Config implementation
-        let mut config = Config::default();
-        // config.set_data_dir(temp_dir.path().to_path_buf()); // TODO: Adjust based on real Config
+        let config = Config { xdg: XdgDirs::new(temp_dir.path()).unwrap() };
         (config, temp_dir)
     }
     
     #[test]
-    fn test_cursor_state_persistence() ->  Result<(), E> {
+    fn test_cursor_state_persistence() -> Result<()> {
         let (config, _temp) = create_test_config();
         
         // Create and save cursor state
-        let mut cursor_state = CursorState::default();
-        cursor_state.base_cursor = "test_base".to_string();
+        let cursor_state = CursorState { base_cursor: "test_base".to_string(), ..Default::default() };
         
         cursor_state.save_to_disk(&config)?;
         
@@ -143,7 +231,7 @@ mod tests {
     }
     
     #[test]
-    fn test_cursor_state_default_when_file_missing() ->  Result<(), E> {
+    fn test_cursor_state_default_when_file_missing() -> Result<()> {
         let (config, _temp) = create_test_config();
         
         // Load from non-existent file should return default
@@ -180,4 +268,82 @@ mod tests {
         assert_eq!(current.workspace, "GLOBAL");
         assert_eq!(current.tail, "MAIN");
     }
+    
+    #[test]
+    fn test_history_records_left_contexts() {
+        use super::super::context::parse_context_chain;
+        let mut cursor_state = CursorState::default();
+        let prod = parse_context_chain("@app.prod.var.secrets", "home").unwrap();
+        let staging = parse_context_chain("@app.staging.var.secrets", "home").unwrap();
+        
+        cursor_state.update_context(&prod);
+        cursor_state.update_context(&prod); // re-entering is not history
+        cursor_state.update_context(&staging);
+        assert_eq!(cursor_state.history, vec![prod.clone()]);
+        
+        // `use -` toggles
+        let previous = cursor_state.take_previous().unwrap();
+        cursor_state.update_context(&previous);
+        assert_eq!(cursor_state.context_cursor.as_ref(), Some(&prod));
+        assert_eq!(cursor_state.history, vec![staging]);
+    }
+    
+    #[test]
+    fn test_history_is_bounded() {
+        use super::super::context::parse_context_chain;
+        let mut cursor_state = CursorState::default();
+        
+        for i in 0..CURSOR_HISTORY_LIMIT + 5 {
+            let chain = parse_context_chain(&format!("@proj{}.ws.var.store", i), "home").unwrap();
+            cursor_state.update_context(&chain);
+        }
+        
+        assert_eq!(cursor_state.history.len(), CURSOR_HISTORY_LIMIT);
+        assert_eq!(cursor_state.history[0].project, "proj4");
+    }
+    
+    #[test]
+    fn test_retarget_after_move() {
+        use super::super::context::parse_context_chain;
+        use super::super::types::typesV1::{Anchor, ChainMode};
+        
+        let mut cursor_state = CursorState::default();
+        cursor_state.update_context(&parse_context_chain("@app.staging.var.secrets", "home").unwrap());
+        cursor_state.update_context(&parse_context_chain("@app.prod.var.secrets", "home").unwrap());
+        
+        let context = |workspace: &str| ResolvedContext {
+            base: "home".to_string(),
+            project: "app".to_string(),
+            workspace: workspace.to_string(),
+            anchor: Anchor::Var,
+            tail: "*".to_string(),
+            prefix_mode: ChainMode::Persistent,
+        };
+        
+        assert!(cursor_state.retarget(&context("staging"), &context("stage"), ChainLevel::Workspace));
+        assert_eq!(cursor_state.history[0].workspace, "stage");
+        assert_eq!(cursor_state.context_cursor.as_ref().unwrap().workspace, "prod");
+        assert!(!cursor_state.retarget(&context("qa"), &context("test"), ChainLevel::Workspace));
+    }
+    
+    #[test]
+    fn test_stack_push_pop() {
+        let mut cursor_state = CursorState::default();
+        
+        cursor_state.push_current();
+        assert_eq!(cursor_state.stack.len(), 1);
+        assert_eq!(cursor_state.pop_stack().unwrap().project, "ROOT");
+        assert!(cursor_state.pop_stack().is_none());
+    }
+    
+    #[test]
+    fn test_legacy_cursor_file_still_loads() {
+        // Cursor files written before history/stack existed
+        let legacy = r#"{"base_cursor":"work","context_cursor":null}"#;
+        let cursor_state: CursorState = serde_json::from_str(legacy).unwrap();
+        
+        assert_eq!(cursor_state.base_cursor, "work");
+        assert!(cursor_state.history.is_empty());
+        assert!(cursor_state.stack.is_empty());
+    }
 }
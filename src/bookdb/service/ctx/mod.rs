@@ -2,6 +2,7 @@
 // It exposes a consistent API regardless of the active feature flag (v1 or v3).
 
 // 1. Declare all the implementation modules.
+pub mod alias;
 pub mod context;
 pub mod context_manager;
 pub mod cursor;
@@ -10,6 +11,10 @@ pub mod types; // The submodule for data models
 
 // 2. Expose the concrete implementation functions and structs that are always needed.
 pub use context_manager::ContextManager;
+pub use context::{split_doc_path, parse_relative_chain, ROOT_SEGMENT, SEGMENT_SEPARATOR};
+pub use context::{glob_match, is_glob, ALL_BASES, WILDCARD};
+pub use context::{validate_namespace_name, ChainLevel};
+pub use alias::{AliasEntry, AliasScope, ALIAS_MARKER};
 
 
 
@@ -17,35 +22,33 @@ pub use context_manager::ContextManager;
 // The rest of the application will just `use bookdb::service::ctx::ContextChain`
 // and will get the correct version based on the compiled features.
 
-#[cfg(feature = "context-chain-v1")]
+// The resolved/cursor types are shared by both versions: V3 chains convert
+// into a ContextChain, so DefaultResolver and the cursor work unchanged.
 pub use types::typesV1::{
     ContextChain,
     ResolvedContext,
     Anchor,
     ChainMode,
     DefaultResolver,
-    CursorState
+    CursorState,
+    RelativeChain,
 };
 
-
-// pub use typesV1::DefaultResolver;
-
-#[cfg(feature = "context-chain-v1")]
+#[cfg(all(feature = "context-chain-v1", not(feature = "context-chain-v3")))]
 pub use context::parse_context_chain; // Expose the V1 parser
 
 #[cfg(feature = "context-chain-v3")]
 pub use types::typesV3::{
-    ContextChainV3 as ContextChain, // ALIAS V3 struct to the common name
+    ContextChainV3,
     VarContextChain,
     DocContextChain,
-    // does v3 need a defautl resolver DefaultResolver
-    //CursorState
+    ContextType,
+    parse_context_chain_v3,
 };
 
-// V3 would have a different parsing entry point, which we expose here.
-// You'll need to create this function in your v3_validator.
-// #[cfg(feature = "context-chain-v3")]
-// pub use crate::bookdb::utils::validator::valV3::validate_and_create as parse_context_chain;
+// V3 parses through the segment parser and converts back to ContextChain
+#[cfg(feature = "context-chain-v3")]
+pub use context::parse_context_chain_v3_compat as parse_context_chain;
 
 
 // 4. (Optional but recommended) For development, allow access to both versions.
@@ -59,5 +62,5 @@ pub mod v1 {
 pub mod v3 {
     pub use super::types::typesV3::*;
     pub use super::types::segment::*;
-    // pub use crate::bookdb::utils::validator::valV3::validate_and_create;
+    pub use crate::bookdb::utils::validator::valV3::{validate_and_create_v3, upgrade_to_specialized, V3ContextResult};
 }
@@ -2,7 +2,9 @@
 // DefaultResolver implementation - Extracted from context.rs
 // Handles CDCC resolution and context atomicity rules
 
-use super::typesV1::{ContextChain, ResolvedContext, CursorState, DefaultResolver, Anchor, ChainMode};
+use crate::error::Result;
+use super::types::typesV1::{ContextChain, ResolvedContext, CursorState, DefaultResolver, Anchor, ChainMode, RelativeChain};
+use super::context::parse_relative_chain;
 
 impl DefaultResolver {
     pub fn new() -> Self {
@@ -26,6 +28,62 @@ impl DefaultResolver {
         }
     }
     
+    /// Resolve a V3 segment chain by converting it to a ContextChain first
+    #[cfg(feature = "context-chain-v3")]
+    pub fn resolve_v3(&self, chain: &super::types::typesV3::ContextChainV3, cursors: &CursorState) -> ResolvedContext {
+        self.resolve_cdcc(&ContextChain::from(chain.clone()), cursors)
+    }
+    
+    /// Parse a chain that may be relative to the cursor
+    ///
+    /// Relative forms (`..`, `.ws`, `:keystore`, `~`) are expanded against the
+    /// current cursor context; anything else goes through `parse_context_chain`.
+    pub fn parse_against_cursor(&self, raw: &str, cursors: &CursorState) -> Result<ContextChain> {
+        match parse_relative_chain(raw)? {
+            Some((prefix_mode, relative)) => Ok(self.resolve_relative(&relative, prefix_mode, cursors)),
+            None => super::parse_context_chain(raw, &cursors.base_cursor),
+        }
+    }
+    
+    /// Expand a relative chain into a full chain on the cursor's base
+    ///
+    /// Workspace swaps go through `apply_atomicity`, so `.staging` lands on
+    /// `project.staging.<anchor>.MAIN`.
+    pub fn resolve_relative(&self, relative: &RelativeChain, prefix_mode: ChainMode, cursors: &CursorState) -> ContextChain {
+        let current = cursors.get_current_context();
+        
+        let mut result = match relative {
+            RelativeChain::Superchain => Self::create_invincible_superchain(&cursors.base_cursor),
+            RelativeChain::Up => {
+                let mut up = current.clone();
+                if up.tail != "MAIN" {
+                    up.tail = "MAIN".to_string();
+                } else if up.workspace != "GLOBAL" {
+                    up.workspace = "GLOBAL".to_string();
+                } else {
+                    up.project = "ROOT".to_string();
+                }
+                up
+            }
+            RelativeChain::Workspace(workspace) => {
+                let mut swapped = current.clone();
+                swapped.workspace = workspace.clone();
+                self.apply_atomicity(&current, &swapped)
+            }
+            RelativeChain::Tail(tail) => {
+                let mut swapped = current.clone();
+                swapped.tail = tail.clone();
+                self.apply_atomicity(&current, &swapped)
+            }
+        };
+        
+        // Relative chains are always cursor-dependent
+        result.base = Some(cursors.base_cursor.clone());
+        result.is_fqcc = false;
+        result.prefix_mode = prefix_mode;
+        result
+    }
+    
     /// Apply context atomicity rules per CONCEPTS.md
     /// When parent context changes, children should reset to defaults
     pub fn apply_atomicity(&self, old_context: &ContextChain, new_context: &ContextChain) -> ContextChain {
@@ -82,10 +140,11 @@ mod tests {
         assert_eq!(superchain.anchor, Anchor::Var);
         assert_eq!(superchain.tail, "MAIN");
         assert!(resolver.is_invincible_superchain(&superchain));
+        assert_eq!(format!("{}", superchain), "@home@ROOT.GLOBAL.var.MAIN");
     }
     
     #[test]
-    fn test_context_atomicity() ->  Result<(), E> {
+    fn test_context_atomicity() -> Result<()> {
         let resolver = DefaultResolver::new();
         
         let old_context = parse_context_chain("@proj1.workspace1.var.store1", "work")?;
@@ -100,7 +159,7 @@ mod tests {
     }
     
     #[test]
-    fn test_workspace_change_atomicity() ->  Result<(), E> {
+    fn test_workspace_change_atomicity() -> Result<()> {
         let resolver = DefaultResolver::new();
         
         let old_context = parse_context_chain("@proj1.workspace1.var.store1", "work")?;
@@ -112,15 +171,22 @@ mod tests {
         assert_eq!(resolved.workspace, "workspace2"); // New workspace
         assert_eq!(resolved.tail, "MAIN"); // Reset to default
         
+        // Changing only the keystore resets nothing
+        let new_context = parse_context_chain("@proj1.workspace1.var.store2", "work")?;
+        let resolved = resolver.apply_atomicity(&old_context, &new_context);
+        assert_eq!(resolved.workspace, "workspace1");
+        assert_eq!(resolved.tail, "store2");
+        
         Ok(())
     }
     
     #[test]
-    fn test_cdcc_resolution() ->  Result<(), E> {
+    fn test_cdcc_resolution() -> Result<()> {
         let chain = parse_context_chain("@proj.workspace.var.store", "fallback")?;
         let cursors = CursorState {
             base_cursor: "work".to_string(),
             context_cursor: None,
+            ..Default::default()
         };
         
         let resolver = DefaultResolver::new();
@@ -133,4 +199,68 @@ mod tests {
         
         Ok(())
     }
+
+    fn cursor_at(chain: &str) -> CursorState {
+        let context = parse_context_chain(chain, "work").unwrap();
+        CursorState {
+            base_cursor: "work".to_string(),
+            context_cursor: Some(context),
+            ..Default::default()
+        }
+    }
+    
+    #[test]
+    fn test_relative_up_walks_levels() -> Result<()> {
+        let resolver = DefaultResolver::new();
+        
+        let chain = resolver.parse_against_cursor("..", &cursor_at("@proj.ws.var.store"))?;
+        assert_eq!((chain.project.as_str(), chain.workspace.as_str(), chain.tail.as_str()), ("proj", "ws", "MAIN"));
+        
+        let chain = resolver.parse_against_cursor("..", &cursor_at("@proj.ws.var.MAIN"))?;
+        assert_eq!((chain.project.as_str(), chain.workspace.as_str()), ("proj", "GLOBAL"));
+        
+        let chain = resolver.parse_against_cursor("..", &cursor_at("@proj.GLOBAL.var.MAIN"))?;
+        assert!(resolver.is_invincible_superchain(&chain));
+        
+        Ok(())
+    }
+    
+    #[test]
+    fn test_relative_swaps_respect_atomicity() -> Result<()> {
+        let resolver = DefaultResolver::new();
+        let cursors = cursor_at("@proj.prod.var.secrets");
+        
+        // Workspace swap resets the keystore
+        let chain = resolver.parse_against_cursor(".staging", &cursors)?;
+        assert_eq!(chain.project, "proj");
+        assert_eq!(chain.workspace, "staging");
+        assert_eq!(chain.tail, "MAIN");
+        
+        // Keystore swap keeps project and workspace
+        let chain = resolver.parse_against_cursor("%:config", &cursors)?;
+        assert_eq!(chain.workspace, "prod");
+        assert_eq!(chain.tail, "config");
+        assert_eq!(chain.prefix_mode, ChainMode::Ephemeral);
+        
+        let resolved = resolver.resolve_cdcc(&chain, &cursors);
+        assert_eq!(resolved.base, "work");
+        
+        Ok(())
+    }
+    
+    #[test]
+    fn test_relative_superchain() -> Result<()> {
+        let resolver = DefaultResolver::new();
+        
+        let chain = resolver.parse_against_cursor("~", &cursor_at("@proj.prod.doc.README"))?;
+        assert!(resolver.is_invincible_superchain(&chain));
+        assert_eq!(chain.base, Some("work".to_string()));
+        assert!(!chain.is_fqcc);
+        
+        // Full chains still parse normally
+        let chain = resolver.parse_against_cursor("@other.ws.var.keys", &CursorState::default())?;
+        assert_eq!(chain.project, "other");
+        
+        Ok(())
+    }
 }
@@ -1,5 +1,7 @@
 
 
 pub mod segment; 
+#[allow(non_snake_case)]
 pub mod typesV1; 
+#[allow(non_snake_case)]
 pub mod typesV3; // pub mod _dep_models; // Keep commented out
@@ -1,9 +1,12 @@
-// src/bookdb/service/ctx/typesv3.rs
-// V3 Segment-based context chain types
-// Phase 1.7 implementation - composable, type-safe context resolution
+// src/bookdb/service/ctx/types/segment.rs
+// V3 segment primitives, segment-based parser and segment validation
+// Chain-level types (ContextChainV3 and the specialized chains) live in typesV3.rs
 
 use serde::{Deserialize, Serialize};
-use super::ctx_types::{Anchor, ChainMode}; // Import from current types
+use super::typesV1::{Anchor, ChainMode};
+use super::typesV3::{ContextChainV3, ContextType};
+use crate::bookdb::service::ctx::context::{is_glob, ALL_BASES, SEGMENT_SEPARATOR, WILDCARD};
+use crate::error::BookdbError;
 
 // ============================================================================
 // SEGMENT SYSTEM TYPES
@@ -14,16 +17,16 @@ use super::ctx_types::{Anchor, ChainMode}; // Import from current types
 pub enum Segment {
     /// Base database identifier for FQCC
     Base(String),
-    
-    /// Prefix mode (@, %, #)  
+
+    /// Prefix mode (@, %, #)
     Prefix(ChainMode),
-    
+
     /// Namespace identifier (project, workspace names)
     Namespace(String),
-    
+
     /// Context anchor (var/doc) - only one allowed per chain
     Anchor(Anchor),
-    
+
     /// Terminal segment - context-specific tail
     Tail(TailSegment),
 }
@@ -41,21 +44,51 @@ pub struct VarContextTail {
     pub keystore: String,
     // Future extensibility:
     // pub access_level: Option<AccessLevel>,
-    // pub encryption_key: Option<String>,
-    // pub ttl: Option<Duration>,
+    // (encryption is per keystore: keystore_crypto, enabled with `bookdb encrypt`)
+    // (expiry is per variable: vars.var_expires, set with `setv --ttl`)
 }
 
-/// Document-specific tail segment with document operations  
+/// Document-specific tail segment with document operations
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct DocContextTail {
     pub document_key: String,
+    /// Path into doc_segments (`header`, `api/v2/schema`); None means the root segment
+    pub segment_path: Option<String>,
     // Future extensibility:
     // pub format: Option<DocumentFormat>,
     // pub version: Option<Version>,
-    // pub segment_path: Option<String>,
     // pub compression: Option<CompressionType>,
 }
 
+impl TailSegment {
+    /// The tail as a plain string (keystore or document key)
+    pub fn name(&self) -> &str {
+        match self {
+            TailSegment::Variable(var) => &var.keystore,
+            TailSegment::Document(doc) => &doc.document_key,
+        }
+    }
+
+    /// The tail as written in a chain (`README/api/v2` for segment paths)
+    pub fn path(&self) -> String {
+        match self {
+            TailSegment::Variable(var) => var.keystore.clone(),
+            TailSegment::Document(DocContextTail { document_key, segment_path: Some(path) }) => {
+                format!("{}{}{}", document_key, SEGMENT_SEPARATOR, path)
+            }
+            TailSegment::Document(doc) => doc.document_key.clone(),
+        }
+    }
+
+    /// Anchor this tail belongs under
+    pub fn anchor(&self) -> Anchor {
+        match self {
+            TailSegment::Variable(_) => Anchor::Var,
+            TailSegment::Document(_) => Anchor::Doc,
+        }
+    }
+}
+
 /// Fixed-size segment container for known patterns
 pub type FixedSegments<const N: usize> = [Segment; N];
 
@@ -66,67 +99,9 @@ pub struct DynamicSegments {
     max_size: Option<usize>,
 }
 
-/// Convenience type for most common context patterns (5 segments max)
+/// Convenience type for most common context patterns (6 segments max)
 pub type Segments = Vec<Segment>;
 
-// ============================================================================
-// V3 CONTEXT CHAIN TYPES
-// ============================================================================
-
-/// V3 Context Chain using segment-based architecture
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub struct ContextChainV3 {
-    pub segments: Segments,
-    pub chain_type: ContextType,
-    pub is_fqcc: bool,
-}
-
-/// Specialized variable context chain - wrapper around ContextChainV3
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]  
-pub struct VarContextChain {
-    /// Base name (from base@ prefix) - None means use cursor default
-    pub base: Option<String>,
-    /// Chain prefix mode
-    pub prefix_mode: ChainMode,
-    /// Project name (top-level container)
-    pub project: String,
-    /// Workspace name
-    pub workspace: String,
-    /// Variable-specific tail with keystore
-    pub tail: VarContextTail,
-    /// Whether this is a Fully Qualified Context Chain (has explicit base@)
-    pub is_fqcc: bool,
-}
-
-/// Specialized document context chain - wrapper around ContextChainV3
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub struct DocContextChain {
-    /// Base name (from base@ prefix) - None means use cursor default
-    pub base: Option<String>, 
-    /// Chain prefix mode
-    pub prefix_mode: ChainMode,
-    /// Project name (top-level container)
-    pub project: String,
-    /// Workspace name
-    pub workspace: String,
-    /// Document-specific tail with document key
-    pub tail: DocContextTail,
-    /// Whether this is a Fully Qualified Context Chain (has explicit base@)
-    pub is_fqcc: bool,
-}
-
-/// Context type discriminator for V3 chains
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-pub enum ContextType {
-    Variable,
-    Document,
-    Mixed,      // Future: multi-target operations
-}
-
-// ============================================================================
-// IMPLEMENTATION BLOCKS (Stubs for Future Implementation)
-// ============================================================================
-
 impl DynamicSegments {
     /// Create a new dynamic segments container
     pub fn new() -> Self {
@@ -135,7 +110,7 @@ impl DynamicSegments {
             max_size: None,
         }
     }
-    
+
     /// Create with maximum size constraint
     pub fn with_max_size(max_size: usize) -> Self {
         Self {
@@ -143,8 +118,8 @@ impl DynamicSegments {
             max_size: Some(max_size),
         }
     }
-    
-    /// Add a segment (stub implementation)
+
+    /// Add a segment, respecting the size limit
     pub fn push(&mut self, segment: Segment) -> Result<(), SegmentError> {
         if let Some(max) = self.max_size {
             if self.segments.len() >= max {
@@ -154,60 +129,15 @@ impl DynamicSegments {
         self.segments.push(segment);
         Ok(())
     }
-    
+
     /// Get segments as slice
     pub fn as_slice(&self) -> &[Segment] {
         &self.segments
     }
-}
 
-impl ContextChainV3 {
-    /// Create a new V3 context chain from segments
-    pub fn new(segments: Segments, chain_type: ContextType) -> Self {
-        let is_fqcc = segments.iter().any(|s| matches!(s, Segment::Base(_)));
-        Self {
-            segments,
-            chain_type,
-            is_fqcc,
-        }
-    }
-    
-    /// Extract base segment if present
-    pub fn base(&self) -> Option<&str> {
-        self.segments.iter()
-            .find_map(|s| match s {
-                Segment::Base(base) => Some(base.as_str()),
-                _ => None,
-            })
-    }
-    
-    /// Extract project name from namespace segments
-    pub fn project(&self) -> Option<&str> {
-        // Implementation would depend on segment ordering conventions
-        // This is a stub
-        None
-    }
-}
-
-// ============================================================================
-// CONVERSION TRAITS (Future Implementation)
-// ============================================================================
-
-/// Convert from legacy ContextChain to V3 types
-impl From<super::ctx_types::ContextChain> for ContextChainV3 {
-    fn from(_legacy: super::ctx_types::ContextChain) -> Self {
-        // TODO: Implement conversion from legacy ContextChain
-        // This is a stub for future implementation
-        todo!("Implement legacy ContextChain -> ContextChainV3 conversion")
-    }
-}
-
-/// Convert from V3 to legacy for backward compatibility
-impl From<ContextChainV3> for super::ctx_types::ContextChain {
-    fn from(_v3: ContextChainV3) -> Self {
-        // TODO: Implement conversion to legacy ContextChain
-        // This is a stub for future implementation
-        todo!("Implement ContextChainV3 -> legacy ContextChain conversion")
+    /// Take ownership of the collected segments
+    pub fn into_segments(self) -> Segments {
+        self.segments
     }
 }
 
@@ -219,124 +149,348 @@ impl From<ContextChainV3> for super::ctx_types::ContextChain {
 pub enum SegmentError {
     #[error("Maximum segment size exceeded")]
     MaxSizeExceeded,
-    
+
     #[error("Invalid segment type for context: {0}")]
     InvalidSegmentType(String),
-    
+
     #[error("Multiple anchor segments not allowed")]
     MultipleAnchors,
-    
+
     #[error("Missing required segment: {0}")]
     MissingSegment(String),
+
+    #[error("Empty context chain")]
+    EmptyChain,
+
+    #[error("Context chain must start with @, %, or #")]
+    InvalidPrefix,
+
+    #[error("Empty base name before @")]
+    EmptyBaseName,
+
+    #[error("Context chain must have exactly 4 parts: project.workspace.anchor.tail, got {0} parts")]
+    ComponentCount(usize),
+
+    #[error("Invalid anchor '{0}', must be 'var' or 'doc'")]
+    InvalidAnchor(String),
+
+    #[error("Invalid {0} '{1}': only alphanumeric, underscore and hyphen allowed")]
+    InvalidComponent(String, String),
+
+    #[error("Cannot use '{0}' as a namespace name")]
+    ReservedName(String),
+
+    #[error("Segment out of order: {0}")]
+    OutOfOrder(String),
+}
+
+/// Segment errors surface to the CLI as context parse errors
+impl From<SegmentError> for BookdbError {
+    fn from(err: SegmentError) -> Self {
+        BookdbError::ContextParse(err.to_string())
+    }
 }
 
 // ============================================================================
-// UTILITY FUNCTIONS (Stubs)
+// PARSING
 // ============================================================================
 
+/// Names that collide with anchors and cannot be used as namespaces
+const RESERVED_NAMES: [&str; 2] = ["var", "doc"];
+
 /// Parse a context chain string into V3 segments
-pub fn parse_context_chain_v3(_input: &str) -> Result<ContextChainV3, SegmentError> {
-    // TODO: Implement segment-based parsing
-    // This would replace the legacy parse_context_chain function
-    todo!("Implement segment-based context chain parsing")
+///
+/// Produces `[Prefix, Base?, Namespace(project), Namespace(workspace), Anchor, Tail]`.
+/// The Base segment is only present for FQCC input (`base@...`); CDCC chains
+/// leave base resolution to the cursor.
+pub fn parse_context_chain_v3(input: &str) -> Result<ContextChainV3, SegmentError> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err(SegmentError::EmptyChain);
+    }
+
+    let mut segments = DynamicSegments::with_max_size(6);
+
+    // Prefix mode
+    // A bare `*@...` spans every base and is read-only, like `%*@...`
+    let (prefix_mode, body) = match input.chars().next() {
+        _ if input.starts_with(ALL_BASES) => (ChainMode::Ephemeral, input),
+        Some('@') => (ChainMode::Persistent, &input[1..]),
+        Some('%') => (ChainMode::Ephemeral, &input[1..]),
+        Some('#') => (ChainMode::Action, &input[1..]),
+        _ => return Err(SegmentError::InvalidPrefix),
+    };
+    segments.push(Segment::Prefix(prefix_mode))?;
+
+    // Optional base@ (FQCC)
+    let rest = match body.split_once('@') {
+        Some((base, rest)) => {
+            if base.is_empty() {
+                return Err(SegmentError::EmptyBaseName);
+            }
+            validate_glob_name("base", base)?;
+            segments.push(Segment::Base(base.to_string()))?;
+            rest
+        }
+        None => body,
+    };
+
+    // project.workspace.anchor.tail
+    let parts: Vec<&str> = rest.split('.').collect();
+    if parts.len() != 4 {
+        return Err(SegmentError::ComponentCount(parts.len()));
+    }
+
+    validate_namespace("project", parts[0])?;
+    validate_namespace("workspace", parts[1])?;
+    segments.push(Segment::Namespace(parts[0].to_string()))?;
+    segments.push(Segment::Namespace(parts[1].to_string()))?;
+
+    let anchor = match parts[2].to_lowercase().as_str() {
+        "var" | "v" => Anchor::Var,
+        "doc" | "d" => Anchor::Doc,
+        _ => return Err(SegmentError::InvalidAnchor(parts[2].to_string())),
+    };
+    segments.push(Segment::Anchor(anchor))?;
+
+    let tail = match anchor {
+        Anchor::Var => {
+            validate_namespace("tail", parts[3])?;
+            TailSegment::Variable(VarContextTail { keystore: parts[3].to_string() })
+        }
+        Anchor::Doc => parse_doc_tail(parts[3])?,
+    };
+    segments.push(Segment::Tail(tail))?;
+
+    let segments = segments.into_segments();
+    validate_segments(&segments)?;
+
+    // Wildcard parts fan out over several contexts; the anchor segment still
+    // says whether they are keystores or documents
+    let base_is_glob = body.split_once('@').is_some_and(|(base, _)| is_glob(base));
+    let chain_type = if base_is_glob || [parts[0], parts[1], parts[3]].iter().any(|p| is_glob(p)) {
+        ContextType::Mixed
+    } else {
+        match anchor {
+            Anchor::Var => ContextType::Variable,
+            Anchor::Doc => ContextType::Document,
+        }
+    };
+    Ok(ContextChainV3::new(segments, chain_type))
 }
 
-/// Validate segment ordering and constraints
-pub fn validate_segments(_segments: &[Segment]) -> Result<(), SegmentError> {
-    // TODO: Implement segment validation
-    // - Only one Anchor per chain
-    // - Proper ordering constraints
-    // - Required segments present
-    todo!("Implement segment validation")
+/// Parse a doc tail, `README` or `README/api/v2/schema`
+fn parse_doc_tail(raw: &str) -> Result<TailSegment, SegmentError> {
+    let (document_key, segment_path) = match raw.split_once(SEGMENT_SEPARATOR) {
+        Some((key, path)) => (key, Some(path)),
+        None => (raw, None),
+    };
+
+    validate_namespace("tail", document_key)?;
+    if let Some(path) = segment_path {
+        for part in path.split(SEGMENT_SEPARATOR) {
+            validate_name("segment", part)?;
+        }
+    }
+
+    Ok(TailSegment::Document(DocContextTail {
+        document_key: document_key.to_string(),
+        segment_path: segment_path.map(str::to_string),
+    }))
 }
 
-// ============================================================================
-// DISPLAY IMPLEMENTATIONS
-// ============================================================================
+/// Validate segment ordering and constraints
+///
+/// Enforces `Prefix, Base?, Namespace, Namespace, Anchor, Tail` with exactly one
+/// anchor and a tail whose kind matches that anchor.
+pub fn validate_segments(segments: &[Segment]) -> Result<(), SegmentError> {
+    let anchors = segments.iter().filter(|s| matches!(s, Segment::Anchor(_))).count();
+    if anchors > 1 {
+        return Err(SegmentError::MultipleAnchors);
+    }
 
-impl std::fmt::Display for VarContextChain {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if self.is_fqcc {
-            let base = self.base.as_ref().unwrap();
-            write!(f, "{}@{}.{}.var.{}", 
-                match self.prefix_mode {
-                    ChainMode::Persistent => '@',
-                    ChainMode::Ephemeral => '%',
-                    ChainMode::Action => '#',
-                },
-                base, self.project, self.workspace, self.tail.keystore)
-        } else {
-            write!(f, "{}{}.{}.var.{}", 
-                match self.prefix_mode {
-                    ChainMode::Persistent => '@',
-                    ChainMode::Ephemeral => '%',
-                    ChainMode::Action => '#',
-                },
-                self.project, self.workspace, self.tail.keystore)
+    let mut iter = segments.iter().peekable();
+
+    match iter.next() {
+        Some(Segment::Prefix(_)) => {}
+        Some(_) => return Err(SegmentError::OutOfOrder("prefix must come first".to_string())),
+        None => return Err(SegmentError::MissingSegment("prefix".to_string())),
+    }
+
+    if let Some(Segment::Base(_)) = iter.peek() {
+        iter.next();
+    }
+
+    for name in ["project", "workspace"] {
+        match iter.next() {
+            Some(Segment::Namespace(_)) => {}
+            Some(other) => return Err(SegmentError::OutOfOrder(format!("expected {} namespace, found {:?}", name, other))),
+            None => return Err(SegmentError::MissingSegment(name.to_string())),
         }
     }
-}
 
-impl std::fmt::Display for DocContextChain {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if self.is_fqcc {
-            let base = self.base.as_ref().unwrap();
-            write!(f, "{}@{}.{}.doc.{}", 
-                match self.prefix_mode {
-                    ChainMode::Persistent => '@',
-                    ChainMode::Ephemeral => '%',
-                    ChainMode::Action => '#',
-                },
-                base, self.project, self.workspace, self.tail.document_key)
-        } else {
-            write!(f, "{}{}.{}.doc.{}", 
-                match self.prefix_mode {
-                    ChainMode::Persistent => '@',
-                    ChainMode::Ephemeral => '%',
-                    ChainMode::Action => '#',
-                },
-                self.project, self.workspace, self.tail.document_key)
+    let anchor = match iter.next() {
+        Some(Segment::Anchor(anchor)) => *anchor,
+        Some(other) => return Err(SegmentError::OutOfOrder(format!("expected anchor, found {:?}", other))),
+        None => return Err(SegmentError::MissingSegment("anchor".to_string())),
+    };
+
+    match iter.next() {
+        Some(Segment::Tail(tail)) if tail.anchor() == anchor => {}
+        Some(Segment::Tail(_)) => {
+            return Err(SegmentError::InvalidSegmentType("tail does not match anchor".to_string()))
         }
+        Some(other) => return Err(SegmentError::OutOfOrder(format!("expected tail, found {:?}", other))),
+        None => return Err(SegmentError::MissingSegment("tail".to_string())),
+    }
+
+    if let Some(extra) = iter.next() {
+        return Err(SegmentError::OutOfOrder(format!("unexpected segment after tail: {:?}", extra)));
     }
+
+    Ok(())
+}
+
+/// Validate a name: non-empty, alphanumeric/underscore/hyphen
+fn validate_name(kind: &str, name: &str) -> Result<(), SegmentError> {
+    if name.is_empty() {
+        return Err(SegmentError::MissingSegment(kind.to_string()));
+    }
+    if !name.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-') {
+        return Err(SegmentError::InvalidComponent(kind.to_string(), name.to_string()));
+    }
+    Ok(())
+}
+
+/// Validate a name that may be a glob (`*`, `prod-*`); the characters around
+/// the wildcards must still form a valid name
+fn validate_glob_name(kind: &str, name: &str) -> Result<(), SegmentError> {
+    let literal: String = name.chars().filter(|c| *c != WILDCARD && *c != '?').collect();
+    if is_glob(name) && literal.is_empty() {
+        return Ok(());
+    }
+    validate_name(kind, &literal)
+}
+
+/// Validate a namespace-level name, which additionally cannot shadow an anchor
+fn validate_namespace(kind: &str, name: &str) -> Result<(), SegmentError> {
+    validate_glob_name(kind, name)?;
+    if RESERVED_NAMES.contains(&name) {
+        return Err(SegmentError::ReservedName(name.to_string()));
+    }
+    Ok(())
 }
 
 // ============================================================================
-// NOTES
+// TESTS
 // ============================================================================
 
-/*
-V3 SEGMENT SYSTEM FEATURES:
-
-1. Type Safety:
-   - Segment enum prevents invalid combinations
-   - TailSegment specialization for var/doc contexts
-   - Compile-time validation of segment types
-
-2. Composability:
-   - Build complex context paths from simple segments
-   - Support for future multi-target operations
-   - Extensible segment types
-
-3. Performance:
-   - Vec-based for simplicity (negligible overhead)
-   - DynamicSegments with optional size limits
-   - Zero-allocation conversion methods (future)
-
-4. Extensibility:
-   - Easy addition of new segment types
-   - Plugin-style segment processors (future)
-   - Metadata and permission segments (future)
-
-IMPLEMENTATION STATUS:
-- Core types: ✅ Defined
-- Conversion traits: 🚧 Stubbed
-- Parsing: 🚧 Stubbed  
-- Validation: 🚧 Stubbed
-- Integration: ❌ Not started
-
-MIGRATION STRATEGY:
-- V3 types coexist with legacy types
-- Gradual migration via adapter layer
-- Feature flags for A/B testing
-- Maintain backward compatibility
-*/
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_fqcc_segments() {
+        let chain = parse_context_chain_v3("@work@proj.ws.var.secrets").unwrap();
+
+        assert!(chain.is_fqcc);
+        assert_eq!(chain.chain_type, ContextType::Variable);
+        assert_eq!(chain.segments, vec![
+            Segment::Prefix(ChainMode::Persistent),
+            Segment::Base("work".to_string()),
+            Segment::Namespace("proj".to_string()),
+            Segment::Namespace("ws".to_string()),
+            Segment::Anchor(Anchor::Var),
+            Segment::Tail(TailSegment::Variable(VarContextTail { keystore: "secrets".to_string() })),
+        ]);
+    }
+
+    #[test]
+    fn test_parse_cdcc_has_no_base_segment() {
+        let chain = parse_context_chain_v3("%proj.ws.DOC.readme").unwrap();
+
+        assert!(!chain.is_fqcc);
+        assert_eq!(chain.base(), None);
+        assert_eq!(chain.chain_type, ContextType::Document);
+        assert_eq!(chain.segments.len(), 5);
+    }
+
+    #[test]
+    fn test_parse_doc_segment_path() {
+        let chain = parse_context_chain_v3("@proj.ws.doc.README/api/v2/schema").unwrap();
+        let tail = chain.tail().unwrap();
+
+        assert_eq!(tail, &TailSegment::Document(DocContextTail {
+            document_key: "README".to_string(),
+            segment_path: Some("api/v2/schema".to_string()),
+        }));
+        assert_eq!(tail.path(), "README/api/v2/schema");
+
+        assert!(matches!(parse_context_chain_v3("@proj.ws.var.store/sub"), Err(SegmentError::InvalidComponent(_, _))));
+        assert!(matches!(parse_context_chain_v3("@proj.ws.doc.README//x"), Err(SegmentError::MissingSegment(_))));
+    }
+
+    #[test]
+    fn test_parse_wildcard_chain() {
+        let chain = parse_context_chain_v3("@myapp.*.var.secrets").unwrap();
+        assert_eq!(chain.chain_type, ContextType::Mixed);
+        assert_eq!(chain.anchor(), Anchor::Var);
+        assert_eq!(chain.workspace(), Some("*"));
+
+        let chain = parse_context_chain_v3("@*.production.doc.READ*").unwrap();
+        assert_eq!(chain.chain_type, ContextType::Mixed);
+        assert_eq!(chain.anchor(), Anchor::Doc);
+
+        assert!(matches!(parse_context_chain_v3("@my app*.ws.var.x"), Err(SegmentError::InvalidComponent(_, _))));
+
+        let chain = parse_context_chain_v3("*@ROOT.GLOBAL.var.MAIN").unwrap();
+        assert_eq!(chain.chain_type, ContextType::Mixed);
+        assert_eq!(chain.prefix_mode(), ChainMode::Ephemeral);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(matches!(parse_context_chain_v3(""), Err(SegmentError::EmptyChain)));
+        assert!(matches!(parse_context_chain_v3("proj.ws.var.x"), Err(SegmentError::InvalidPrefix)));
+        assert!(matches!(parse_context_chain_v3("@@proj.ws.var.x"), Err(SegmentError::EmptyBaseName)));
+        assert!(matches!(parse_context_chain_v3("@proj.ws.var"), Err(SegmentError::ComponentCount(3))));
+        assert!(matches!(parse_context_chain_v3("@proj.ws.bad.x"), Err(SegmentError::InvalidAnchor(_))));
+        assert!(matches!(parse_context_chain_v3("@var.ws.var.x"), Err(SegmentError::ReservedName(_))));
+        assert!(matches!(parse_context_chain_v3("@pr oj.ws.var.x"), Err(SegmentError::InvalidComponent(_, _))));
+    }
+
+    #[test]
+    fn test_validate_segments_rules() {
+        let tail = Segment::Tail(TailSegment::Variable(VarContextTail { keystore: "k".to_string() }));
+
+        // Two anchors
+        let segments = vec![
+            Segment::Prefix(ChainMode::Persistent),
+            Segment::Namespace("p".to_string()),
+            Segment::Namespace("w".to_string()),
+            Segment::Anchor(Anchor::Var),
+            Segment::Anchor(Anchor::Var),
+            tail.clone(),
+        ];
+        assert!(matches!(validate_segments(&segments), Err(SegmentError::MultipleAnchors)));
+
+        // Tail kind must match anchor
+        let segments = vec![
+            Segment::Prefix(ChainMode::Persistent),
+            Segment::Namespace("p".to_string()),
+            Segment::Namespace("w".to_string()),
+            Segment::Anchor(Anchor::Doc),
+            tail.clone(),
+        ];
+        assert!(matches!(validate_segments(&segments), Err(SegmentError::InvalidSegmentType(_))));
+
+        // Missing tail
+        let segments = vec![
+            Segment::Prefix(ChainMode::Persistent),
+            Segment::Namespace("p".to_string()),
+            Segment::Namespace("w".to_string()),
+            Segment::Anchor(Anchor::Var),
+        ];
+        assert!(matches!(validate_segments(&segments), Err(SegmentError::MissingSegment(_))));
+    }
+}
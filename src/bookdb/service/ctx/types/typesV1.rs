@@ -49,6 +49,12 @@ pub struct CursorState {
     pub base_cursor: String,
     /// Current context within that base
     pub context_cursor: Option<ContextChain>,
+    /// Previously active contexts, oldest first (bounded; `use -` goes back)
+    #[serde(default)]
+    pub history: Vec<ContextChain>,
+    /// Contexts saved by `push`, restored by `pop`
+    #[serde(default)]
+    pub stack: Vec<ContextChain>,
 }
 
 
@@ -65,6 +71,8 @@ impl Default for CursorState {
         CursorState {
             base_cursor: "home".to_string(),
             context_cursor: None,
+            history: Vec::new(),
+            stack: Vec::new(),
         }
     }
 }
@@ -89,10 +97,23 @@ pub enum ChainMode {
     Persistent,
     /// % - Ephemeral: one-time use, cursor unchanged
     Ephemeral,
-    /// # - Action: implicit action mode (ROADMAP)
+    /// # - Action: run the action bound to the context (see `bookdb bind`)
     Action,
 }
 
+/// Relative chain forms, resolved against the current cursor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RelativeChain {
+    /// `..` - up one level: tail -> MAIN, then workspace -> GLOBAL, then project -> ROOT
+    Up,
+    /// `.name` - swap only the workspace
+    Workspace(String),
+    /// `:name` - swap only the keystore (or document for doc chains)
+    Tail(String),
+    /// `~` - the invincible superchain ROOT.GLOBAL.var.MAIN
+    Superchain,
+}
+
 // ============================================================================
 // DATABASE ID MAPPING TYPES (CURRENT - WORKING)
 // ============================================================================
@@ -172,13 +193,6 @@ pub enum ResolvedContextIds {
 // UTILITY TYPES (CURRENT - WORKING)
 // ============================================================================
 
-/// Default resolver for CDCC and atomicity rules
-pub struct DefaultResolver;
-
-/// Context manager with stderr integration for rich user experience
-pub struct ContextManager {
-    // Note: Implementation details in context_manager.rs
-}
 
 // ============================================================================
 // NOTES
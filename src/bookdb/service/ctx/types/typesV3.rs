@@ -3,71 +3,19 @@
 // Phase 1.7 implementation - composable, type-safe context resolution
 
 use serde::{Deserialize, Serialize};
-use super::ctx_types::{Anchor, ChainMode}; // Import from current types
+use super::typesV1::{Anchor, ChainMode, ContextChain}; // Import from current types
+use crate::bookdb::service::ctx::context::SEGMENT_SEPARATOR;
 
 // ============================================================================
 // SEGMENT SYSTEM TYPES
 // ============================================================================
 
-/// Single context component container - dumb container for one context piece
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub enum Segment {
-    /// Base database identifier for FQCC
-    Base(String),
-    
-    /// Prefix mode (@, %, #)  
-    Prefix(ChainMode),
-    
-    /// Namespace identifier (project, workspace names)
-    Namespace(String),
-    
-    /// Context anchor (var/doc) - only one allowed per chain
-    Anchor(Anchor),
-    
-    /// Terminal segment - context-specific tail
-    Tail(TailSegment),
-}
-
-/// Specialized tail segments by context type - different implementations
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub enum TailSegment {
-    Variable(VarContextTail),
-    Document(DocContextTail),
-}
-
-/// Variable-specific tail segment with keystore operations
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub struct VarContextTail {
-    pub keystore: String,
-    // Future extensibility:
-    // pub access_level: Option<AccessLevel>,
-    // pub encryption_key: Option<String>,
-    // pub ttl: Option<Duration>,
-}
-
-/// Document-specific tail segment with document operations  
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub struct DocContextTail {
-    pub document_key: String,
-    // Future extensibility:
-    // pub format: Option<DocumentFormat>,
-    // pub version: Option<Version>,
-    // pub segment_path: Option<String>,
-    // pub compression: Option<CompressionType>,
-}
-
-/// Fixed-size segment container for known patterns
-pub type FixedSegments<const N: usize> = [Segment; N];
-
-/// Dynamic segment container for runtime flexibility
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct DynamicSegments {
-    segments: Vec<Segment>,
-    max_size: Option<usize>,
-}
-
-/// Convenience type for most common context patterns (5 segments max)
-pub type Segments = Vec<Segment>;
+// Segment primitives, the parser and validation live in segment.rs
+pub use super::segment::{
+    Segment, TailSegment, VarContextTail, DocContextTail,
+    FixedSegments, DynamicSegments, Segments, SegmentError,
+    parse_context_chain_v3, validate_segments,
+};
 
 // ============================================================================
 // V3 CONTEXT CHAIN TYPES (with Deref for automatic coercion)
@@ -106,6 +54,7 @@ pub struct DocContextChain {
     pub project: String,
     pub workspace: String,
     pub document_key: String,
+    pub segment_path: Option<String>,
     pub is_fqcc: bool,
 }
 
@@ -136,47 +85,13 @@ impl Deref for DocContextChain {
 pub enum ContextType {
     Variable,
     Document,
-    Mixed,      // Future: multi-target operations
+    Mixed,      // Wildcard chain fanning out over several contexts
 }
 
 // ============================================================================
-// IMPLEMENTATION BLOCKS (Stubs for Future Implementation)
+// IMPLEMENTATION BLOCKS
 // ============================================================================
 
-impl DynamicSegments {
-    /// Create a new dynamic segments container
-    pub fn new() -> Self {
-        Self {
-            segments: Vec::new(),
-            max_size: None,
-        }
-    }
-    
-    /// Create with maximum size constraint
-    pub fn with_max_size(max_size: usize) -> Self {
-        Self {
-            segments: Vec::with_capacity(max_size),
-            max_size: Some(max_size),
-        }
-    }
-    
-    /// Add a segment (stub implementation)
-    pub fn push(&mut self, segment: Segment) -> Result<(), SegmentError> {
-        if let Some(max) = self.max_size {
-            if self.segments.len() >= max {
-                return Err(SegmentError::MaxSizeExceeded);
-            }
-        }
-        self.segments.push(segment);
-        Ok(())
-    }
-    
-    /// Get segments as slice
-    pub fn as_slice(&self) -> &[Segment] {
-        &self.segments
-    }
-}
-
 impl ContextChainV3 {
     /// Create a new V3 context chain from segments
     pub fn new(segments: Segments, chain_type: ContextType) -> Self {
@@ -196,74 +111,163 @@ impl ContextChainV3 {
                 _ => None,
             })
     }
+
+    /// Prefix mode of the chain (Persistent when no prefix segment)
+    pub fn prefix_mode(&self) -> ChainMode {
+        self.segments.iter()
+            .find_map(|s| match s {
+                Segment::Prefix(mode) => Some(*mode),
+                _ => None,
+            })
+            .unwrap_or(ChainMode::Persistent)
+    }
     
-    /// Extract project name from namespace segments
+    /// Extract project name (first namespace segment)
     pub fn project(&self) -> Option<&str> {
-        // Implementation would depend on segment ordering conventions
-        // This is a stub
-        None
+        self.namespace(0)
     }
-}
 
-// ============================================================================
-// CONVERSION TRAITS (Future Implementation)
-// ============================================================================
+    /// Extract workspace name (second namespace segment)
+    pub fn workspace(&self) -> Option<&str> {
+        self.namespace(1)
+    }
 
-/// Convert from legacy ContextChain to V3 types
-impl From<super::ctx_types::ContextChain> for ContextChainV3 {
-    fn from(_legacy: super::ctx_types::ContextChain) -> Self {
-        // TODO: Implement conversion from legacy ContextChain
-        // This is a stub for future implementation
-        todo!("Implement legacy ContextChain -> ContextChainV3 conversion")
+    /// Anchor segment, falling back to the chain type
+    pub fn anchor(&self) -> Anchor {
+        self.segments.iter()
+            .find_map(|s| match s {
+                Segment::Anchor(anchor) => Some(*anchor),
+                _ => None,
+            })
+            .unwrap_or(match self.chain_type {
+                ContextType::Document => Anchor::Doc,
+                _ => Anchor::Var,
+            })
+    }
+
+    /// Terminal tail segment if present
+    pub fn tail(&self) -> Option<&TailSegment> {
+        self.segments.iter()
+            .find_map(|s| match s {
+                Segment::Tail(tail) => Some(tail),
+                _ => None,
+            })
+    }
+
+    fn namespace(&self, index: usize) -> Option<&str> {
+        self.segments.iter()
+            .filter_map(|s| match s {
+                Segment::Namespace(name) => Some(name.as_str()),
+                _ => None,
+            })
+            .nth(index)
     }
 }
 
-/// Convert from V3 to legacy for backward compatibility
-impl From<ContextChainV3> for super::ctx_types::ContextChain {
-    fn from(_v3: ContextChainV3) -> Self {
-        // TODO: Implement conversion to legacy ContextChain
-        // This is a stub for future implementation
-        todo!("Implement ContextChainV3 -> legacy ContextChain conversion")
+impl VarContextChain {
+    /// Wrap a validated generic chain with its extracted fields
+    pub fn new(inner: ContextChainV3, base: Option<String>, project: String, workspace: String, keystore: String) -> Self {
+        Self {
+            prefix_mode: inner.prefix_mode(),
+            is_fqcc: inner.is_fqcc,
+            inner,
+            base,
+            project,
+            workspace,
+            keystore,
+        }
+    }
+
+    /// Unwrap back into the generic chain
+    pub fn into_inner(self) -> ContextChainV3 {
+        self.inner
     }
 }
 
-// ============================================================================
-// ERROR TYPES
-// ============================================================================
+impl DocContextChain {
+    /// Wrap a validated generic chain with its extracted fields
+    pub fn new(
+        inner: ContextChainV3,
+        base: Option<String>,
+        project: String,
+        workspace: String,
+        document_key: String,
+        segment_path: Option<String>,
+    ) -> Self {
+        Self {
+            prefix_mode: inner.prefix_mode(),
+            is_fqcc: inner.is_fqcc,
+            inner,
+            base,
+            project,
+            workspace,
+            document_key,
+            segment_path,
+        }
+    }
 
-#[derive(Debug, thiserror::Error)]
-pub enum SegmentError {
-    #[error("Maximum segment size exceeded")]
-    MaxSizeExceeded,
-    
-    #[error("Invalid segment type for context: {0}")]
-    InvalidSegmentType(String),
-    
-    #[error("Multiple anchor segments not allowed")]
-    MultipleAnchors,
-    
-    #[error("Missing required segment: {0}")]
-    MissingSegment(String),
+    /// Unwrap back into the generic chain
+    pub fn into_inner(self) -> ContextChainV3 {
+        self.inner
+    }
 }
 
 // ============================================================================
-// UTILITY FUNCTIONS (Stubs)
+// CONVERSION TRAITS
 // ============================================================================
 
-/// Parse a context chain string into V3 segments
-pub fn parse_context_chain_v3(_input: &str) -> Result<ContextChainV3, SegmentError> {
-    // TODO: Implement segment-based parsing
-    // This would replace the legacy parse_context_chain function
-    todo!("Implement segment-based context chain parsing")
+/// Convert from legacy ContextChain to V3 types
+///
+/// The Base segment is only emitted for FQCC chains, mirroring the parser.
+impl From<ContextChain> for ContextChainV3 {
+    fn from(legacy: ContextChain) -> Self {
+        let mut segments = vec![Segment::Prefix(legacy.prefix_mode)];
+        if legacy.is_fqcc {
+            if let Some(base) = legacy.base {
+                segments.push(Segment::Base(base));
+            }
+        }
+        segments.push(Segment::Namespace(legacy.project));
+        segments.push(Segment::Namespace(legacy.workspace));
+        segments.push(Segment::Anchor(legacy.anchor));
+
+        let (tail, chain_type) = match legacy.anchor {
+            Anchor::Var => (
+                TailSegment::Variable(VarContextTail { keystore: legacy.tail }),
+                ContextType::Variable,
+            ),
+            Anchor::Doc => {
+                let (document_key, segment_path) = match legacy.tail.split_once(SEGMENT_SEPARATOR) {
+                    Some((key, path)) => (key.to_string(), Some(path.to_string())),
+                    None => (legacy.tail, None),
+                };
+                (
+                    TailSegment::Document(DocContextTail { document_key, segment_path }),
+                    ContextType::Document,
+                )
+            }
+        };
+        segments.push(Segment::Tail(tail));
+
+        ContextChainV3::new(segments, chain_type)
+    }
 }
 
-/// Validate segment ordering and constraints
-pub fn validate_segments(_segments: &[Segment]) -> Result<(), SegmentError> {
-    // TODO: Implement segment validation
-    // - Only one Anchor per chain
-    // - Proper ordering constraints
-    // - Required segments present
-    todo!("Implement segment validation")
+/// Convert from V3 to legacy for backward compatibility
+///
+/// Missing segments fall back to the invincible superchain (ROOT.GLOBAL.var.MAIN).
+impl From<ContextChainV3> for ContextChain {
+    fn from(v3: ContextChainV3) -> Self {
+        ContextChain {
+            base: v3.base().map(str::to_string),
+            project: v3.project().unwrap_or("ROOT").to_string(),
+            workspace: v3.workspace().unwrap_or("GLOBAL").to_string(),
+            anchor: v3.anchor(),
+            tail: v3.tail().map(|t| t.path()).unwrap_or_else(|| "MAIN".to_string()),
+            prefix_mode: v3.prefix_mode(),
+            is_fqcc: v3.is_fqcc,
+        }
+    }
 }
 
 // ============================================================================
@@ -274,13 +278,13 @@ impl std::fmt::Display for VarContextChain {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if self.is_fqcc {
             let base = self.base.as_ref().unwrap();
-            write!(f, "{}@{}.{}.var.{}", 
+            write!(f, "{}{}@{}.{}.var.{}", 
                 match self.prefix_mode {
                     ChainMode::Persistent => '@',
                     ChainMode::Ephemeral => '%',
                     ChainMode::Action => '#',
                 },
-                base, self.project, self.workspace, self.tail.keystore)
+                base, self.project, self.workspace, self.keystore)
         } else {
             write!(f, "{}{}.{}.var.{}", 
                 match self.prefix_mode {
@@ -288,7 +292,7 @@ impl std::fmt::Display for VarContextChain {
                     ChainMode::Ephemeral => '%',
                     ChainMode::Action => '#',
                 },
-                self.project, self.workspace, self.tail.keystore)
+                self.project, self.workspace, self.keystore)
         }
     }
 }
@@ -297,13 +301,13 @@ impl std::fmt::Display for DocContextChain {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if self.is_fqcc {
             let base = self.base.as_ref().unwrap();
-            write!(f, "{}@{}.{}.doc.{}", 
+            write!(f, "{}{}@{}.{}.doc.{}", 
                 match self.prefix_mode {
                     ChainMode::Persistent => '@',
                     ChainMode::Ephemeral => '%',
                     ChainMode::Action => '#',
                 },
-                base, self.project, self.workspace, self.tail.document_key)
+                base, self.project, self.workspace, self.document_key)?;
         } else {
             write!(f, "{}{}.{}.doc.{}", 
                 match self.prefix_mode {
@@ -311,7 +315,11 @@ impl std::fmt::Display for DocContextChain {
                     ChainMode::Ephemeral => '%',
                     ChainMode::Action => '#',
                 },
-                self.project, self.workspace, self.tail.document_key)
+                self.project, self.workspace, self.document_key)?;
+        }
+        match &self.segment_path {
+            Some(path) => write!(f, "{}{}", SEGMENT_SEPARATOR, path),
+            None => Ok(()),
         }
     }
 }
@@ -345,10 +353,10 @@ V3 SEGMENT SYSTEM FEATURES:
 
 IMPLEMENTATION STATUS:
 - Core types: ✅ Defined
-- Conversion traits: 🚧 Stubbed
-- Parsing: 🚧 Stubbed  
-- Validation: 🚧 Stubbed
-- Integration: ❌ Not started
+- Conversion traits: ✅ ContextChain <-> ContextChainV3
+- Parsing: ✅ parse_context_chain_v3 (segment.rs)
+- Validation: ✅ validate_segments (segment.rs)
+- Integration: ✅ context-chain-v3 feature routes parse_context_chain through V3
 
 MIGRATION STRATEGY:
 - V3 types coexist with legacy types
@@ -0,0 +1,10 @@
+-- src/sql2/V10__create_keystore_crypto.sql
+-- Encrypted keystores: the Argon2id salt and a sealed check value per keystore
+-- (no row = plaintext keystore)
+
+CREATE TABLE IF NOT EXISTS keystore_crypto (
+    kvns_id_fk INTEGER PRIMARY KEY,
+    kc_salt BLOB NOT NULL,
+    kc_check TEXT NOT NULL,
+    FOREIGN KEY (kvns_id_fk) REFERENCES keyval_ns(kvns_id) ON DELETE CASCADE
+);
@@ -0,0 +1,9 @@
+-- src/sql2/V11__create_sensitive_keys.sql
+-- Keys (or, with sk_key '*', whole keystores) whose values are masked in listings and logs
+
+CREATE TABLE IF NOT EXISTS sensitive_keys (
+    sk_key TEXT NOT NULL,
+    kvns_id_fk INTEGER NOT NULL,
+    PRIMARY KEY (kvns_id_fk, sk_key),
+    FOREIGN KEY (kvns_id_fk) REFERENCES keyval_ns(kvns_id) ON DELETE CASCADE
+);
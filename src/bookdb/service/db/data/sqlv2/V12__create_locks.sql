@@ -0,0 +1,17 @@
+-- src/sql2/V12__create_locks.sql
+-- Locked namespaces (a keystore or a workspace's doc store) and base-wide flags such as read-only
+
+CREATE TABLE IF NOT EXISTS ns_locks (
+    lock_id INTEGER PRIMARY KEY,
+    kvns_id_fk INTEGER UNIQUE,
+    ds_id_fk INTEGER UNIQUE,
+    lock_created INTEGER NOT NULL DEFAULT (strftime('%s','now')),
+    FOREIGN KEY (kvns_id_fk) REFERENCES keyval_ns(kvns_id) ON DELETE CASCADE,
+    FOREIGN KEY (ds_id_fk) REFERENCES doc_stores(ds_id) ON DELETE CASCADE,
+    CHECK ((kvns_id_fk IS NULL) <> (ds_id_fk IS NULL))
+);
+
+CREATE TABLE IF NOT EXISTS base_flags (
+    flag_name TEXT PRIMARY KEY,
+    flag_set INTEGER NOT NULL DEFAULT (strftime('%s','now'))
+);
@@ -0,0 +1,17 @@
+-- src/sql2/V13__create_audit.sql
+-- Audit trail: who ran what against which chain; values only as SHA-256 of their stored form
+
+CREATE TABLE IF NOT EXISTS audit (
+    audit_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    audit_at INTEGER NOT NULL DEFAULT (strftime('%s','now')),
+    audit_user TEXT,
+    audit_pid INTEGER NOT NULL,
+    audit_argv TEXT NOT NULL,
+    audit_chain TEXT NOT NULL,
+    audit_key TEXT,
+    audit_op TEXT NOT NULL,
+    audit_old_hash TEXT,
+    audit_new_hash TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_at ON audit (audit_at);
@@ -0,0 +1,6 @@
+-- src/sql2/V14__add_journal_var_attrs.sql
+-- Type and expiry a journaled variable had, so undo restores them with its value
+-- (guarded by column_exists in setup_schema, like V8)
+
+ALTER TABLE journal_entries ADD COLUMN old_type TEXT;
+ALTER TABLE journal_entries ADD COLUMN old_expires INTEGER;
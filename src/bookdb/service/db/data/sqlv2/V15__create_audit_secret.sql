@@ -0,0 +1,7 @@
+-- src/sql2/V15__create_audit_secret.sql
+-- Per-base HMAC key for audit value hashes, created on the first audited value
+
+CREATE TABLE IF NOT EXISTS audit_secret (
+    secret_id INTEGER PRIMARY KEY CHECK (secret_id = 1),
+    secret_key BLOB NOT NULL
+);
@@ -0,0 +1,45 @@
+-- src/sql2/V1__create_tables.sql
+-- Core sqlv2 schema: project namespaces, keystores, variables and document stores
+
+CREATE TABLE IF NOT EXISTS project_ns (
+    pns_id INTEGER PRIMARY KEY,
+    pns_name TEXT NOT NULL UNIQUE
+);
+
+CREATE TABLE IF NOT EXISTS keyval_ns (
+    kvns_id INTEGER PRIMARY KEY,
+    kvns_name TEXT NOT NULL,
+    workspace_name TEXT NOT NULL,
+    pns_id_fk INTEGER NOT NULL,
+    FOREIGN KEY (pns_id_fk) REFERENCES project_ns(pns_id) ON DELETE CASCADE,
+    UNIQUE (kvns_name, pns_id_fk, workspace_name)
+);
+
+CREATE TABLE IF NOT EXISTS vars (
+    var_id INTEGER PRIMARY KEY,
+    var_key TEXT NOT NULL,
+    var_value TEXT,
+    var_updated INTEGER NOT NULL DEFAULT (strftime('%s','now')),
+    kvns_id_fk INTEGER NOT NULL,
+    FOREIGN KEY (kvns_id_fk) REFERENCES keyval_ns(kvns_id) ON DELETE CASCADE,
+    UNIQUE (var_key, kvns_id_fk)
+);
+
+CREATE TABLE IF NOT EXISTS doc_stores (
+    ds_id INTEGER PRIMARY KEY,
+    ds_name TEXT NOT NULL,
+    workspace_name TEXT NOT NULL,
+    pns_id_fk INTEGER NOT NULL,
+    FOREIGN KEY (pns_id_fk) REFERENCES project_ns(pns_id) ON DELETE CASCADE,
+    UNIQUE (ds_name, pns_id_fk, workspace_name)
+);
+
+CREATE TABLE IF NOT EXISTS docs (
+    doc_id INTEGER PRIMARY KEY,
+    doc_key TEXT NOT NULL,
+    doc_content TEXT,
+    doc_updated INTEGER NOT NULL DEFAULT (strftime('%s','now')),
+    ds_id_fk INTEGER NOT NULL,
+    FOREIGN KEY (ds_id_fk) REFERENCES doc_stores(ds_id) ON DELETE CASCADE,
+    UNIQUE (doc_key, ds_id_fk)
+);
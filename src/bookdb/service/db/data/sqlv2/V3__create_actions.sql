@@ -0,0 +1,14 @@
+-- src/sql2/V3__create_actions.sql
+-- Action bindings for `#` chains (context -> named action)
+
+CREATE TABLE IF NOT EXISTS actions (
+    act_id INTEGER PRIMARY KEY,
+    act_project TEXT NOT NULL,
+    act_workspace TEXT NOT NULL,
+    act_anchor TEXT NOT NULL CHECK (act_anchor IN ('var', 'doc')),
+    act_tail TEXT NOT NULL,
+    act_kind TEXT NOT NULL CHECK (act_kind IN ('dump', 'render', 'export')),
+    act_arg TEXT,
+    act_updated INTEGER NOT NULL DEFAULT (strftime('%s','now')),
+    UNIQUE (act_project, act_workspace, act_anchor, act_tail)
+);
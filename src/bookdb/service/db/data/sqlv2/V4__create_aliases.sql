@@ -0,0 +1,9 @@
+-- src/sql2/V4__create_aliases.sql
+-- Per-base chain aliases (`:name` -> full context chain)
+
+CREATE TABLE IF NOT EXISTS aliases (
+    alias_id INTEGER PRIMARY KEY,
+    alias_name TEXT NOT NULL UNIQUE,
+    alias_chain TEXT NOT NULL,
+    alias_updated INTEGER NOT NULL DEFAULT (strftime('%s','now'))
+);
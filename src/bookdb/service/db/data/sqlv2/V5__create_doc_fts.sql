@@ -0,0 +1,29 @@
+-- src/sql2/V5__create_doc_fts.sql
+-- FTS5 index over text/* document segments, kept in sync by triggers
+
+CREATE VIRTUAL TABLE IF NOT EXISTS doc_segments_fts USING fts5(body, tokenize = 'unicode61');
+
+CREATE TRIGGER IF NOT EXISTS doc_segments_fts_insert AFTER INSERT ON doc_segments
+WHEN new.mime LIKE 'text/%'
+BEGIN
+    INSERT INTO doc_segments_fts (rowid, body) VALUES (new.seg_id, CAST(new.content AS TEXT));
+END;
+
+CREATE TRIGGER IF NOT EXISTS doc_segments_fts_update AFTER UPDATE ON doc_segments
+BEGIN
+    DELETE FROM doc_segments_fts WHERE rowid = old.seg_id;
+    INSERT INTO doc_segments_fts (rowid, body)
+    SELECT new.seg_id, CAST(new.content AS TEXT) WHERE new.mime LIKE 'text/%';
+END;
+
+-- Also fires for cascades from docs / doc_stores / project_ns
+CREATE TRIGGER IF NOT EXISTS doc_segments_fts_delete AFTER DELETE ON doc_segments
+BEGIN
+    DELETE FROM doc_segments_fts WHERE rowid = old.seg_id;
+END;
+
+-- Backfill segments written before the index existed
+INSERT INTO doc_segments_fts (rowid, body)
+SELECT seg_id, CAST(content AS TEXT) FROM doc_segments
+WHERE mime LIKE 'text/%'
+  AND seg_id NOT IN (SELECT rowid FROM doc_segments_fts);
@@ -0,0 +1,24 @@
+-- src/sql2/V6__create_var_history.sql
+-- Variable history: one row per set/delete/inc/revert; hist_id is the revision
+
+CREATE TABLE IF NOT EXISTS var_history (
+    hist_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    var_key TEXT NOT NULL,
+    hist_op TEXT NOT NULL CHECK (hist_op IN ('set', 'delete', 'inc', 'revert')),
+    old_value TEXT,
+    new_value TEXT,
+    hist_at INTEGER NOT NULL DEFAULT (strftime('%s','now')),
+    kvns_id_fk INTEGER NOT NULL,
+    FOREIGN KEY (kvns_id_fk) REFERENCES keyval_ns(kvns_id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_var_history_key ON var_history (kvns_id_fk, var_key, hist_id);
+
+-- Variables written before history existed start with a single 'set'
+INSERT INTO var_history (var_key, hist_op, old_value, new_value, hist_at, kvns_id_fk)
+SELECT v.var_key, 'set', NULL, v.var_value, v.var_updated, v.kvns_id_fk
+FROM vars v
+WHERE NOT EXISTS (
+    SELECT 1 FROM var_history h
+    WHERE h.kvns_id_fk = v.kvns_id_fk AND h.var_key = v.var_key
+);
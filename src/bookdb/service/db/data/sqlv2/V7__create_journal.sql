@@ -0,0 +1,29 @@
+-- src/sql2/V7__create_journal.sql
+-- Undo journal: one op per mutating command, each with the inverse of every write it made
+
+CREATE TABLE IF NOT EXISTS journal_ops (
+    op_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    op_command TEXT NOT NULL,
+    op_chain TEXT NOT NULL,
+    op_at INTEGER NOT NULL DEFAULT (strftime('%s','now')),
+    op_undone INTEGER NOT NULL DEFAULT 0
+);
+
+-- tail_name is the keystore (var) or doc key (seg, doc).
+-- 'var' and 'seg' invert to 'set' (old value) or 'delete'; 'doc' only to 'delete'
+CREATE TABLE IF NOT EXISTS journal_entries (
+    entry_id INTEGER PRIMARY KEY,
+    entry_kind TEXT NOT NULL CHECK (entry_kind IN ('var', 'seg', 'doc')),
+    project_name TEXT NOT NULL,
+    workspace_name TEXT NOT NULL,
+    tail_name TEXT NOT NULL,
+    entry_key TEXT NOT NULL,
+    seg_path TEXT,
+    inverse TEXT NOT NULL CHECK (inverse IN ('set', 'delete')),
+    old_value BLOB,
+    old_mime TEXT,
+    op_id_fk INTEGER NOT NULL,
+    FOREIGN KEY (op_id_fk) REFERENCES journal_ops(op_id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_journal_entries_op ON journal_entries (op_id_fk, entry_id);
@@ -0,0 +1,5 @@
+-- src/sql2/V8__add_var_type.sql
+-- Optional variable type (int, float, bool, json, duration); NULL is a plain string
+-- (ALTER TABLE has no IF NOT EXISTS: setup_schema only runs this when the column is missing)
+
+ALTER TABLE vars ADD COLUMN var_type TEXT;
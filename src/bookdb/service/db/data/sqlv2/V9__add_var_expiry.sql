@@ -0,0 +1,5 @@
+-- src/sql2/V9__add_var_expiry.sql
+-- Optional variable expiry in unix seconds; NULL never expires
+-- (guarded by column_exists in setup_schema, like V8)
+
+ALTER TABLE vars ADD COLUMN var_expires INTEGER;
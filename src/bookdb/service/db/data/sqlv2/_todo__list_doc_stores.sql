@@ -1,4 +0,0 @@
-
-
-
-
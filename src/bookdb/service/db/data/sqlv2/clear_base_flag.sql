@@ -0,0 +1,4 @@
+-- src/sql2/clear_base_flag.sql
+-- Clear a base-wide flag
+
+DELETE FROM base_flags WHERE flag_name = ?1;
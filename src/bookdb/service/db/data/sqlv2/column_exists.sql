@@ -0,0 +1,4 @@
+-- src/sql2/column_exists.sql
+-- Whether table ?1 has column ?2
+
+SELECT COUNT(*) FROM pragma_table_info(?1) WHERE name = ?2;
@@ -0,0 +1,4 @@
+-- src/sql2/create_journal_op.sql
+-- Start a journal op (?1 command, ?2 chain)
+
+INSERT INTO journal_ops (op_command, op_chain) VALUES (?1, ?2);
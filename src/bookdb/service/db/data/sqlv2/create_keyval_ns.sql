@@ -0,0 +1,5 @@
+-- src/sql2/create_keyval_ns.sql
+-- Create new keystore (project.workspace.keystore)
+
+INSERT INTO keyval_ns (kvns_name, pns_id_fk, workspace_name) 
+VALUES (?1, ?2, ?3);
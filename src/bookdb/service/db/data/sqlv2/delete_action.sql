@@ -0,0 +1,8 @@
+-- src/sql2/delete_action.sql
+-- Remove the action bound to a context
+
+DELETE FROM actions 
+WHERE act_project = ?1 
+  AND act_workspace = ?2 
+  AND act_anchor = ?3 
+  AND act_tail = ?4;
@@ -0,0 +1,5 @@
+-- src/sql2/delete_alias.sql
+-- Remove a chain alias
+
+DELETE FROM aliases 
+WHERE alias_name = ?1;
@@ -0,0 +1,4 @@
+-- src/sql2/delete_doc_segment.sql
+-- Delete one segment of document ?1 by path ?2
+
+DELETE FROM doc_segments WHERE doc_id_fk = ?1 AND path = ?2;
@@ -0,0 +1,6 @@
+-- src/sql2/delete_doc_store.sql
+-- Delete the document stores of a workspace (documents and segments cascade)
+
+DELETE FROM doc_stores 
+WHERE pns_id_fk = (SELECT pns_id FROM project_ns WHERE pns_name = ?1) 
+  AND workspace_name = ?2;
@@ -0,0 +1,7 @@
+-- src/sql2/delete_keystore.sql
+-- Delete a keystore; its variables go with it (ON DELETE CASCADE)
+
+DELETE FROM keyval_ns 
+WHERE pns_id_fk = (SELECT pns_id FROM project_ns WHERE pns_name = ?1) 
+  AND workspace_name = ?2 
+  AND kvns_name = ?3;
@@ -0,0 +1,4 @@
+-- src/sql2/delete_keystore_crypto.sql
+-- Mark keystore ?1 as plaintext again
+
+DELETE FROM keystore_crypto WHERE kvns_id_fk = ?1;
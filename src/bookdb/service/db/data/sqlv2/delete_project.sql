@@ -0,0 +1,5 @@
+-- src/sql2/delete_project.sql
+-- Delete a project; keystores, doc stores and their contents cascade
+
+DELETE FROM project_ns 
+WHERE pns_name = ?1;
@@ -0,0 +1,6 @@
+-- src/sql2/delete_workspace_keystores.sql
+-- Delete every keystore in a workspace (variables cascade)
+
+DELETE FROM keyval_ns 
+WHERE pns_id_fk = (SELECT pns_id FROM project_ns WHERE pns_name = ?1) 
+  AND workspace_name = ?2;
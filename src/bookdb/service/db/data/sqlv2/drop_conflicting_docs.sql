@@ -0,0 +1,6 @@
+-- src/sql2/drop_conflicting_docs.sql
+-- Before merging doc store ?2 into ?1, drop ?1 documents that ?2 replaces
+
+DELETE FROM docs 
+WHERE ds_id_fk = ?1 
+  AND doc_key IN (SELECT doc_key FROM docs WHERE ds_id_fk = ?2);
@@ -6,7 +6,8 @@ SELECT
     v.var_value,
     pns.pns_name as project,
     kvns.workspace_name,
-    kvns.kvns_name as keystore
+    kvns.kvns_name as keystore,
+    kvns.kvns_id
 FROM vars v 
 JOIN keyval_ns kvns ON v.kvns_id_fk = kvns.kvns_id 
 JOIN project_ns pns ON kvns.pns_id_fk = pns.pns_id 
@@ -14,4 +15,5 @@ WHERE (?1 IS NULL OR pns.pns_name LIKE '%' || ?1 || '%')
   AND (?2 IS NULL OR kvns.workspace_name LIKE '%' || ?2 || '%')
   AND (?3 IS NULL OR kvns.kvns_name LIKE '%' || ?3 || '%')
   AND (?4 IS NULL OR v.var_key LIKE '%' || ?4 || '%')
+  AND (v.var_expires IS NULL OR v.var_expires > CAST(strftime('%s','now') AS INTEGER))
 ORDER BY pns.pns_name, kvns.workspace_name, kvns.kvns_name, v.var_key;
@@ -0,0 +1,9 @@
+-- src/sql2/get_action.sql
+-- Get the action bound to a context
+
+SELECT act_kind, act_arg 
+FROM actions 
+WHERE act_project = ?1 
+  AND act_workspace = ?2 
+  AND act_anchor = ?3 
+  AND act_tail = ?4;
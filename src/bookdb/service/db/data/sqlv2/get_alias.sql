@@ -0,0 +1,6 @@
+-- src/sql2/get_alias.sql
+-- Get the chain stored under an alias
+
+SELECT alias_chain 
+FROM aliases 
+WHERE alias_name = ?1;
@@ -0,0 +1,4 @@
+-- src/sql2/get_audit_secret.sql
+-- Get this base's audit HMAC key
+
+SELECT secret_key FROM audit_secret WHERE secret_id = 1;
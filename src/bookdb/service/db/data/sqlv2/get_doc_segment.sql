@@ -0,0 +1,12 @@
+-- src/sql2/get_doc_segment.sql
+-- Get document segment content and mime type by context, doc key and path
+
+SELECT seg.content, seg.mime 
+FROM doc_segments seg 
+JOIN docs d ON seg.doc_id_fk = d.doc_id 
+JOIN doc_stores ds ON d.ds_id_fk = ds.ds_id 
+JOIN project_ns pns ON ds.pns_id_fk = pns.pns_id 
+WHERE pns.pns_name = ?1 
+  AND ds.workspace_name = ?2 
+  AND d.doc_key = ?3 
+  AND seg.path = ?4;
@@ -0,0 +1,4 @@
+-- src/sql2/get_doc_segment_raw.sql
+-- Get one segment of document ?1 by path ?2 (no _root fallback)
+
+SELECT content, mime FROM doc_segments WHERE doc_id_fk = ?1 AND path = ?2;
@@ -0,0 +1,7 @@
+-- src/sql2/get_keystore_chain.sql
+-- Chain (project.workspace.var.keystore) of keystore ?1
+
+SELECT pns.pns_name || '.' || kvns.workspace_name || '.var.' || kvns.kvns_name
+FROM keyval_ns kvns
+JOIN project_ns pns ON kvns.pns_id_fk = pns.pns_id
+WHERE kvns.kvns_id = ?1;
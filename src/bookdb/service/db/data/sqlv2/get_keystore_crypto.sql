@@ -0,0 +1,4 @@
+-- src/sql2/get_keystore_crypto.sql
+-- Salt and check value of keystore ?1, if it is encrypted
+
+SELECT kc_salt, kc_check FROM keystore_crypto WHERE kvns_id_fk = ?1;
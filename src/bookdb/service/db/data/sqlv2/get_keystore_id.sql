@@ -0,0 +1,9 @@
+-- src/sql2/get_keystore_id.sql
+-- Get keystore ID by project, workspace and keystore name
+
+SELECT kvns.kvns_id 
+FROM keyval_ns kvns 
+JOIN project_ns pns ON kvns.pns_id_fk = pns.pns_id 
+WHERE pns.pns_name = ?1 
+  AND kvns.workspace_name = ?2 
+  AND kvns.kvns_name = ?3;
@@ -0,0 +1,4 @@
+-- src/sql2/get_last_revision.sql
+-- Newest history revision (0 if none)
+
+SELECT COALESCE(MAX(hist_id), 0) FROM var_history;
@@ -0,0 +1,6 @@
+-- src/sql2/get_project_id.sql
+-- Get project ID by name
+
+SELECT pns_id 
+FROM project_ns 
+WHERE pns_name = ?1;
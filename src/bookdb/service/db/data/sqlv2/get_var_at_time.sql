@@ -0,0 +1,14 @@
+-- src/sql2/get_var_at_time.sql
+-- Latest change of a variable at or before unix time ?5 (new_value NULL = deleted)
+
+SELECT h.hist_id, h.new_value
+FROM var_history h
+JOIN keyval_ns kvns ON h.kvns_id_fk = kvns.kvns_id
+JOIN project_ns pns ON kvns.pns_id_fk = pns.pns_id
+WHERE pns.pns_name = ?1
+  AND kvns.workspace_name = ?2
+  AND kvns.kvns_name = ?3
+  AND h.var_key = ?4
+  AND h.hist_at <= ?5
+ORDER BY h.hist_id DESC
+LIMIT 1;
@@ -8,4 +8,5 @@ JOIN project_ns pns ON kvns.pns_id_fk = pns.pns_id
 WHERE pns.pns_name = ?1 
   AND kvns.workspace_name = ?2 
   AND kvns.kvns_name = ?3 
-  AND v.var_key = ?4;
+  AND v.var_key = ?4 
+  AND (v.var_expires IS NULL OR v.var_expires > CAST(strftime('%s','now') AS INTEGER));
@@ -0,0 +1,5 @@
+-- src/sql2/get_write_locks.sql
+-- Whether base flag ?3 (read-only) is set, and whether keystore ?1 or doc store ?2 is locked
+
+SELECT EXISTS (SELECT 1 FROM base_flags WHERE flag_name = ?3),
+       EXISTS (SELECT 1 FROM ns_locks WHERE kvns_id_fk = ?1 OR ds_id_fk = ?2);
@@ -0,0 +1,5 @@
+-- src/sql2/is_marked_sensitive.sql
+-- Whether key ?2 of keystore ?1 is marked sensitive, by key, by keystore or by encryption
+
+SELECT EXISTS (SELECT 1 FROM sensitive_keys WHERE kvns_id_fk = ?1 AND sk_key IN (?2, '*'))
+    OR EXISTS (SELECT 1 FROM keystore_crypto WHERE kvns_id_fk = ?1);
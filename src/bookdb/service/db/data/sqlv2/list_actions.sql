@@ -0,0 +1,6 @@
+-- src/sql2/list_actions.sql
+-- List all action bindings in the base
+
+SELECT act_project, act_workspace, act_anchor, act_tail, act_kind, act_arg 
+FROM actions 
+ORDER BY act_project, act_workspace, act_anchor, act_tail;
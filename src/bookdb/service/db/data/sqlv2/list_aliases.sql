@@ -0,0 +1,6 @@
+-- src/sql2/list_aliases.sql
+-- List all chain aliases in the base
+
+SELECT alias_name, alias_chain 
+FROM aliases 
+ORDER BY alias_name;
@@ -0,0 +1,14 @@
+-- src/sql2/list_all_doc_segments.sql
+-- Every document segment in the base with its document context (for find)
+
+SELECT 
+    d.doc_key,
+    seg.path,
+    seg.content,
+    pns.pns_name as project,
+    ds.workspace_name
+FROM doc_segments seg 
+JOIN docs d ON seg.doc_id_fk = d.doc_id 
+JOIN doc_stores ds ON d.ds_id_fk = ds.ds_id 
+JOIN project_ns pns ON ds.pns_id_fk = pns.pns_id 
+ORDER BY pns.pns_name, ds.workspace_name, d.doc_key, seg.path;
@@ -0,0 +1,10 @@
+-- src/sql2/list_audit.sql
+-- Audit entries at or after unix time ?1 (NULL: all) on chain ?2 or below it (NULL: all), oldest first
+
+SELECT audit_id, strftime('%Y-%m-%dT%H:%M:%SZ', audit_at, 'unixepoch'),
+       audit_user, audit_pid, audit_argv, audit_chain, audit_key, audit_op,
+       audit_old_hash, audit_new_hash
+FROM audit
+WHERE (?1 IS NULL OR audit_at >= ?1)
+  AND (?2 IS NULL OR audit_chain = ?2 OR substr(audit_chain, 1, length(?2) + 1) = ?2 || '.')
+ORDER BY audit_id;
@@ -0,0 +1,12 @@
+-- src/sql2/list_doc_segments.sql
+-- List the segments of a document with mime type and size
+
+SELECT seg.path, seg.mime, length(seg.content), seg.updated_at 
+FROM doc_segments seg 
+JOIN docs d ON seg.doc_id_fk = d.doc_id 
+JOIN doc_stores ds ON d.ds_id_fk = ds.ds_id 
+JOIN project_ns pns ON ds.pns_id_fk = pns.pns_id 
+WHERE pns.pns_name = ?1 
+  AND ds.workspace_name = ?2 
+  AND d.doc_key = ?3 
+ORDER BY seg.path;
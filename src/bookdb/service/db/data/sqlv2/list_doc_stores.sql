@@ -0,0 +1,9 @@
+-- src/sql2/list_doc_stores.sql
+-- List document stores in a workspace within a project
+
+SELECT ds.ds_name 
+FROM doc_stores ds 
+JOIN project_ns pns ON ds.pns_id_fk = pns.pns_id 
+WHERE pns.pns_name = ?1 
+  AND ds.workspace_name = ?2 
+ORDER BY ds.ds_name;
@@ -0,0 +1,7 @@
+-- src/sql2/list_history_since.sql
+-- History rows after revision ?1 as (keystore id, key, old, new), for auditing bulk SQL changes
+
+SELECT kvns_id_fk, var_key, old_value, new_value
+FROM var_history
+WHERE hist_id > ?1
+ORDER BY hist_id;
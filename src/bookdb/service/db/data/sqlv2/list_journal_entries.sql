@@ -0,0 +1,8 @@
+-- src/sql2/list_journal_entries.sql
+-- Entries of journal op ?1, newest first (the order to replay them in)
+
+SELECT entry_kind, project_name, workspace_name, tail_name, entry_key, seg_path, inverse,
+       CAST(old_value AS BLOB), old_mime, old_type, old_expires
+FROM journal_entries
+WHERE op_id_fk = ?1
+ORDER BY entry_id DESC;
@@ -0,0 +1,10 @@
+-- src/sql2/list_journal_ops.sql
+-- Newest ?1 journal ops with their entry counts
+
+SELECT op.op_id, op.op_command, op.op_chain,
+       strftime('%Y-%m-%dT%H:%M:%SZ', op.op_at, 'unixepoch'),
+       op.op_undone,
+       (SELECT COUNT(*) FROM journal_entries e WHERE e.op_id_fk = op.op_id)
+FROM journal_ops op
+ORDER BY op.op_id DESC
+LIMIT ?1;
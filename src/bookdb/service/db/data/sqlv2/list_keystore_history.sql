@@ -0,0 +1,4 @@
+-- src/sql2/list_keystore_history.sql
+-- Every history row of keystore ?1 (for encrypt/decrypt)
+
+SELECT hist_id, var_key, old_value, new_value FROM var_history WHERE kvns_id_fk = ?1;
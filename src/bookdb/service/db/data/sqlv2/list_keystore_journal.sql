@@ -0,0 +1,10 @@
+-- src/sql2/list_keystore_journal.sql
+-- Journaled variable values of keystore ?1.?2.var.?3 (for encrypt/decrypt)
+
+SELECT entry_id, entry_key, CAST(old_value AS TEXT)
+FROM journal_entries
+WHERE entry_kind = 'var'
+  AND project_name = ?1
+  AND workspace_name = ?2
+  AND tail_name = ?3
+  AND old_value IS NOT NULL;
@@ -0,0 +1,4 @@
+-- src/sql2/list_keystore_values.sql
+-- Every stored value of keystore ?1, expired or not (for encrypt/decrypt)
+
+SELECT var_id, var_key, var_value FROM vars WHERE kvns_id_fk = ?1;
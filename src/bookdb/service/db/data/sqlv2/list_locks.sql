@@ -0,0 +1,15 @@
+-- src/sql2/list_locks.sql
+-- Locked namespaces as (project, workspace, anchor, keystore or NULL, locked at, UTC)
+
+SELECT pns.pns_name, kvns.workspace_name, 'var', kvns.kvns_name,
+       strftime('%Y-%m-%dT%H:%M:%SZ', l.lock_created, 'unixepoch')
+FROM ns_locks l
+JOIN keyval_ns kvns ON l.kvns_id_fk = kvns.kvns_id
+JOIN project_ns pns ON kvns.pns_id_fk = pns.pns_id
+UNION ALL
+SELECT pns.pns_name, ds.workspace_name, 'doc', NULL,
+       strftime('%Y-%m-%dT%H:%M:%SZ', l.lock_created, 'unixepoch')
+FROM ns_locks l
+JOIN doc_stores ds ON l.ds_id_fk = ds.ds_id
+JOIN project_ns pns ON ds.pns_id_fk = pns.pns_id
+ORDER BY 1, 2, 3, 4;
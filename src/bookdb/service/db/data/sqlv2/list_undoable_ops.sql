@@ -0,0 +1,11 @@
+-- src/sql2/list_undoable_ops.sql
+-- Newest ?1 journal ops not yet undone, same columns as list_journal_ops
+
+SELECT op.op_id, op.op_command, op.op_chain,
+       strftime('%Y-%m-%dT%H:%M:%SZ', op.op_at, 'unixepoch'),
+       op.op_undone,
+       (SELECT COUNT(*) FROM journal_entries e WHERE e.op_id_fk = op.op_id)
+FROM journal_ops op
+WHERE op.op_undone = 0
+ORDER BY op.op_id DESC
+LIMIT ?1;
@@ -0,0 +1,12 @@
+-- src/sql2/list_var_expiries.sql
+-- Remaining lifetime in seconds of every live variable with an expiry in a context
+
+SELECT v.var_key, v.var_expires - CAST(strftime('%s','now') AS INTEGER) 
+FROM vars v 
+JOIN keyval_ns kvns ON v.kvns_id_fk = kvns.kvns_id 
+JOIN project_ns pns ON kvns.pns_id_fk = pns.pns_id 
+WHERE pns.pns_name = ?1 
+  AND kvns.workspace_name = ?2 
+  AND kvns.kvns_name = ?3 
+  AND v.var_expires > CAST(strftime('%s','now') AS INTEGER)
+ORDER BY v.var_key;
@@ -0,0 +1,13 @@
+-- src/sql2/list_var_history.sql
+-- Change history of one variable, oldest first
+
+SELECT h.hist_id, h.hist_op, h.old_value, h.new_value, h.hist_at,
+       strftime('%Y-%m-%dT%H:%M:%SZ', h.hist_at, 'unixepoch')
+FROM var_history h
+JOIN keyval_ns kvns ON h.kvns_id_fk = kvns.kvns_id
+JOIN project_ns pns ON kvns.pns_id_fk = pns.pns_id
+WHERE pns.pns_name = ?1
+  AND kvns.workspace_name = ?2
+  AND kvns.kvns_name = ?3
+  AND h.var_key = ?4
+ORDER BY h.hist_id;
@@ -1,5 +1,5 @@
 -- src/sql2/list_variables.sql
--- List all variables in a context
+-- List all live (unexpired) variables in a context
 
 SELECT v.var_key, v.var_value 
 FROM vars v 
@@ -8,4 +8,5 @@ JOIN project_ns pns ON kvns.pns_id_fk = pns.pns_id
 WHERE pns.pns_name = ?1 
   AND kvns.workspace_name = ?2 
   AND kvns.kvns_name = ?3 
+  AND (v.var_expires IS NULL OR v.var_expires > CAST(strftime('%s','now') AS INTEGER))
 ORDER BY v.var_key;
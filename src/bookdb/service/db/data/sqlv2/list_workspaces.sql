@@ -1,8 +1,13 @@
 -- src/sql2/list_workspaces.sql
--- List workspaces in a project
+-- List workspaces in a project (any workspace holding a keystore or doc store)
 
-SELECT DISTINCT workspace_name 
+SELECT kvns.workspace_name 
 FROM keyval_ns kvns 
 JOIN project_ns pns ON kvns.pns_id_fk = pns.pns_id 
 WHERE pns.pns_name = ?1 
-ORDER BY workspace_name;
+UNION 
+SELECT ds.workspace_name 
+FROM doc_stores ds 
+JOIN project_ns pns ON ds.pns_id_fk = pns.pns_id 
+WHERE pns.pns_name = ?1 
+ORDER BY 1;
@@ -0,0 +1,4 @@
+-- src/sql2/lock_namespace.sql
+-- Lock keystore ?1 or doc store ?2 (the other is NULL)
+
+INSERT OR IGNORE INTO ns_locks (kvns_id_fk, ds_id_fk) VALUES (?1, ?2);
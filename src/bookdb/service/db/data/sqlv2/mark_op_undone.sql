@@ -0,0 +1,4 @@
+-- src/sql2/mark_op_undone.sql
+-- Flag journal op ?1 as undone
+
+UPDATE journal_ops SET op_undone = 1 WHERE op_id = ?1;
@@ -0,0 +1,4 @@
+-- src/sql2/mark_sensitive.sql
+-- Mark key ?1 ('*' = every key) of keystore ?2 as sensitive
+
+INSERT OR IGNORE INTO sensitive_keys (sk_key, kvns_id_fk) VALUES (?1, ?2);
@@ -0,0 +1,13 @@
+-- src/sql2/merge_vars.sql
+-- Copy variables from keystore ?2 into keystore ?1; incoming values win
+
+INSERT INTO vars (var_key, var_value, var_updated, var_type, var_expires, kvns_id_fk) 
+SELECT var_key, var_value, var_updated, var_type, var_expires, ?1 
+FROM vars 
+WHERE kvns_id_fk = ?2 
+ON CONFLICT (var_key, kvns_id_fk) 
+DO UPDATE SET 
+    var_value = excluded.var_value,
+    var_updated = excluded.var_updated,
+    var_type = excluded.var_type,
+    var_expires = excluded.var_expires;
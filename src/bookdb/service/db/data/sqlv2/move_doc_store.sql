@@ -0,0 +1,7 @@
+-- src/sql2/move_doc_store.sql
+-- Relocate a document store; its documents follow by foreign key
+
+UPDATE doc_stores 
+SET pns_id_fk = ?1, 
+    workspace_name = ?2 
+WHERE ds_id = ?3;
@@ -0,0 +1,6 @@
+-- src/sql2/move_docs.sql
+-- Move every document of doc store ?2 into doc store ?1
+
+UPDATE docs 
+SET ds_id_fk = ?1 
+WHERE ds_id_fk = ?2;
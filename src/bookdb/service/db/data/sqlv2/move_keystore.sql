@@ -0,0 +1,8 @@
+-- src/sql2/move_keystore.sql
+-- Rename/relocate a keystore; its variables follow by foreign key
+
+UPDATE keyval_ns 
+SET kvns_name = ?1, 
+    pns_id_fk = ?2, 
+    workspace_name = ?3 
+WHERE kvns_id = ?4;
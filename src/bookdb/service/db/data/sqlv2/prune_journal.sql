@@ -0,0 +1,5 @@
+-- src/sql2/prune_journal.sql
+-- Keep only the newest ?1 journal ops (entries cascade)
+
+DELETE FROM journal_ops
+WHERE op_id <= (SELECT MAX(op_id) FROM journal_ops) - ?1;
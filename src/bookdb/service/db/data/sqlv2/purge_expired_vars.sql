@@ -0,0 +1,4 @@
+-- src/sql2/purge_expired_vars.sql
+-- Delete every expired variable
+
+DELETE FROM vars WHERE var_expires <= CAST(strftime('%s','now') AS INTEGER);
@@ -0,0 +1,5 @@
+-- src/sql2/record_audit.sql
+-- Record one audited change (?1 user, ?2 pid, ?3 argv JSON, ?4 chain, ?5 key, ?6 op, ?7 old hash, ?8 new hash)
+
+INSERT INTO audit (audit_user, audit_pid, audit_argv, audit_chain, audit_key, audit_op, audit_old_hash, audit_new_hash)
+VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8);
@@ -0,0 +1,9 @@
+-- src/sql2/record_journal_entry.sql
+-- Record the inverse of one write
+-- (?1 op, ?2 kind, ?3 project, ?4 workspace, ?5 tail, ?6 key, ?7 seg path, ?8 inverse, ?9 old value, ?10 old mime,
+--  ?11 old var type, ?12 old var expiry)
+
+INSERT INTO journal_entries
+    (op_id_fk, entry_kind, project_name, workspace_name, tail_name, entry_key, seg_path, inverse, old_value, old_mime,
+     old_type, old_expires)
+VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12);
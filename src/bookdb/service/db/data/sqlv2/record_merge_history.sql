@@ -0,0 +1,10 @@
+-- src/sql2/record_merge_history.sql
+-- Record a 'set' in keystore ?1 for every variable merged in from keystore ?2
+-- (run before merge_vars so the replaced values are still there)
+
+INSERT INTO var_history (var_key, hist_op, old_value, new_value, kvns_id_fk)
+SELECT src.var_key, 'set', dst.var_value, src.var_value, ?1
+FROM vars src
+LEFT JOIN vars dst ON dst.kvns_id_fk = ?1 AND dst.var_key = src.var_key
+WHERE src.kvns_id_fk = ?2
+ORDER BY src.var_key;
@@ -0,0 +1,8 @@
+-- src/sql2/record_purge_history.sql
+-- Record a 'delete' for every expired variable (run before purge_expired_vars)
+
+INSERT INTO var_history (var_key, hist_op, old_value, new_value, kvns_id_fk)
+SELECT var_key, 'delete', var_value, NULL, kvns_id_fk
+FROM vars
+WHERE var_expires <= CAST(strftime('%s','now') AS INTEGER)
+ORDER BY kvns_id_fk, var_key;
@@ -0,0 +1,5 @@
+-- src/sql2/record_var_history.sql
+-- Record one variable change (?1 key, ?2 op, ?3 old, ?4 new, ?5 kvns_id)
+
+INSERT INTO var_history (var_key, hist_op, old_value, new_value, kvns_id_fk)
+VALUES (?1, ?2, ?3, ?4, ?5);
@@ -0,0 +1,6 @@
+-- src/sql2/rename_project.sql
+-- Rename a project in place (?1 old name, ?2 new name)
+
+UPDATE project_ns 
+SET pns_name = ?2 
+WHERE pns_name = ?1;
@@ -0,0 +1,17 @@
+-- src/sql2/search_doc_segments.sql
+-- Full-text search over text segments, best match first (?2/?3 wrap highlighted terms)
+
+SELECT 
+    d.doc_key,
+    seg.path,
+    pns.pns_name as project,
+    ds.workspace_name,
+    snippet(doc_segments_fts, 0, ?2, ?3, '...', 12) as snip,
+    bm25(doc_segments_fts) as rank
+FROM doc_segments_fts 
+JOIN doc_segments seg ON seg.seg_id = doc_segments_fts.rowid 
+JOIN docs d ON seg.doc_id_fk = d.doc_id 
+JOIN doc_stores ds ON d.ds_id_fk = ds.ds_id 
+JOIN project_ns pns ON ds.pns_id_fk = pns.pns_id 
+WHERE doc_segments_fts MATCH ?1 
+ORDER BY rank;
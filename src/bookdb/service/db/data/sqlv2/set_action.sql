@@ -0,0 +1,10 @@
+-- src/sql2/set_action.sql
+-- Bind an action to a context (upsert)
+
+INSERT INTO actions (act_project, act_workspace, act_anchor, act_tail, act_kind, act_arg, act_updated) 
+VALUES (?1, ?2, ?3, ?4, ?5, ?6, strftime('%s','now'))
+ON CONFLICT (act_project, act_workspace, act_anchor, act_tail) 
+DO UPDATE SET 
+    act_kind = excluded.act_kind,
+    act_arg = excluded.act_arg,
+    act_updated = excluded.act_updated;
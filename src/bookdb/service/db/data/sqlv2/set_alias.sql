@@ -0,0 +1,9 @@
+-- src/sql2/set_alias.sql
+-- Store a chain alias (upsert)
+
+INSERT INTO aliases (alias_name, alias_chain, alias_updated) 
+VALUES (?1, ?2, strftime('%s','now'))
+ON CONFLICT (alias_name) 
+DO UPDATE SET 
+    alias_chain = excluded.alias_chain,
+    alias_updated = excluded.alias_updated;
@@ -0,0 +1,4 @@
+-- src/sql2/set_audit_secret.sql
+-- Store this base's audit HMAC key (?1); a key already there is kept
+
+INSERT OR IGNORE INTO audit_secret (secret_id, secret_key) VALUES (1, ?1);
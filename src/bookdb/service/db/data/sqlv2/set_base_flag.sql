@@ -0,0 +1,4 @@
+-- src/sql2/set_base_flag.sql
+-- Set a base-wide flag
+
+INSERT OR IGNORE INTO base_flags (flag_name) VALUES (?1);
@@ -0,0 +1,4 @@
+-- src/sql2/set_keystore_crypto.sql
+-- Mark keystore ?1 as encrypted with salt ?2 and check value ?3
+
+INSERT INTO keystore_crypto (kvns_id_fk, kc_salt, kc_check) VALUES (?1, ?2, ?3);
@@ -0,0 +1,4 @@
+-- src/sql2/set_var_attrs.sql
+-- Restore a variable's type and absolute expiry (unix seconds) as journaled
+
+UPDATE vars SET var_type = ?1, var_expires = ?2 WHERE var_key = ?3 AND kvns_id_fk = ?4;
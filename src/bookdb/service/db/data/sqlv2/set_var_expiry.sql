@@ -0,0 +1,6 @@
+-- src/sql2/set_var_expiry.sql
+-- Expire variable ?2 in keystore ?3 after ?1 seconds; NULL clears the expiry
+
+UPDATE vars 
+SET var_expires = CASE WHEN ?1 IS NULL THEN NULL ELSE CAST(strftime('%s','now') AS INTEGER) + ?1 END 
+WHERE var_key = ?2 AND kvns_id_fk = ?3;
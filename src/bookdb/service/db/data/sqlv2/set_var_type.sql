@@ -0,0 +1,4 @@
+-- src/sql2/set_var_type.sql
+-- Set the type of variable ?2 in keystore ?3 (?1 NULL = untyped)
+
+UPDATE vars SET var_type = ?1 WHERE var_key = ?2 AND kvns_id_fk = ?3;
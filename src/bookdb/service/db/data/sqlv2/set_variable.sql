@@ -1,9 +1,11 @@
 -- src/sql2/set_variable.sql
--- Set variable value (upsert)
+-- Set variable value (upsert); a row that had expired comes back untyped and without expiry
 
 INSERT INTO vars (var_key, var_value, kvns_id_fk, var_updated) 
 VALUES (?1, ?2, ?3, strftime('%s','now'))
 ON CONFLICT (var_key, kvns_id_fk) 
 DO UPDATE SET 
     var_value = excluded.var_value,
-    var_updated = excluded.var_updated;
+    var_updated = excluded.var_updated,
+    var_type = CASE WHEN vars.var_expires <= CAST(strftime('%s','now') AS INTEGER) THEN NULL ELSE vars.var_type END,
+    var_expires = CASE WHEN vars.var_expires <= CAST(strftime('%s','now') AS INTEGER) THEN NULL ELSE vars.var_expires END;
@@ -0,0 +1,4 @@
+-- src/sql2/unlock_namespace.sql
+-- Unlock keystore ?1 or doc store ?2 (the other is NULL)
+
+DELETE FROM ns_locks WHERE kvns_id_fk IS ?1 AND ds_id_fk IS ?2;
@@ -0,0 +1,4 @@
+-- src/sql2/unmark_sensitive.sql
+-- Drop the sensitive mark of key ?1 ('*' = the keystore mark) of keystore ?2
+
+DELETE FROM sensitive_keys WHERE sk_key = ?1 AND kvns_id_fk = ?2;
@@ -0,0 +1,4 @@
+-- src/sql2/update_history_values.sql
+-- Replace the old (?1) and new (?2) values of history row ?3 in place
+
+UPDATE var_history SET old_value = ?1, new_value = ?2 WHERE hist_id = ?3;
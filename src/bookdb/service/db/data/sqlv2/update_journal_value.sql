@@ -0,0 +1,4 @@
+-- src/sql2/update_journal_value.sql
+-- Replace the journaled old value of entry ?2 in place
+
+UPDATE journal_entries SET old_value = ?1 WHERE entry_id = ?2;
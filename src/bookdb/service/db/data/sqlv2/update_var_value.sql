@@ -0,0 +1,4 @@
+-- src/sql2/update_var_value.sql
+-- Replace the stored value of row ?2 in place (var_updated untouched)
+
+UPDATE vars SET var_value = ?1 WHERE var_id = ?2;
@@ -0,0 +1,169 @@
+// src/db/action.rs - Action bindings for `#` (Action-mode) context chains
+
+use crate::error::{Result, BookdbError};
+use crate::bookdb::service::ctx::{Anchor, ResolvedContext};
+use crate::sql;
+use rusqlite::params;
+use std::fmt;
+use std::str::FromStr;
+use super::Database;
+use super::lock::WriteTarget;
+
+/// What a bound action does with its context
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActionKind {
+    /// Print every KEY=VALUE (or document) in the context
+    Dump,
+    /// Fill `{{KEY}}` placeholders in a template file from the context
+    Render,
+    /// Write the context to a file
+    Export,
+}
+
+impl ActionKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ActionKind::Dump => "dump",
+            ActionKind::Render => "render",
+            ActionKind::Export => "export",
+        }
+    }
+
+    /// Whether the action needs an argument (template or output path)
+    pub fn requires_arg(&self) -> bool {
+        !matches!(self, ActionKind::Dump)
+    }
+}
+
+impl FromStr for ActionKind {
+    type Err = BookdbError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "dump" => Ok(ActionKind::Dump),
+            "render" => Ok(ActionKind::Render),
+            "export" => Ok(ActionKind::Export),
+            other => Err(BookdbError::Argument(format!(
+                "Unknown action '{}', must be dump, render or export", other
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for ActionKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// An action bound to a project.workspace.anchor.tail context
+#[derive(Debug, Clone)]
+pub struct ActionBinding {
+    pub project: String,
+    pub workspace: String,
+    pub anchor: Anchor,
+    pub tail: String,
+    pub kind: ActionKind,
+    pub arg: Option<String>,
+}
+
+fn anchor_str(anchor: Anchor) -> &'static str {
+    match anchor {
+        Anchor::Var => "var",
+        Anchor::Doc => "doc",
+    }
+}
+
+impl Database {
+    /// Bind (or rebind) an action to a context
+    pub fn set_action(&self, context: &ResolvedContext, kind: ActionKind, arg: Option<&str>) -> Result<()> {
+        self.logger.trace_fn("database", &format!("binding action {} to context: {}", kind, context));
+
+        if kind.requires_arg() && arg.is_none_or(|a| a.is_empty()) {
+            return Err(BookdbError::Argument(format!("Action '{}' requires an argument", kind)));
+        }
+
+        let tx = self.connection.unchecked_transaction()?;
+        self.check_writable(&tx, WriteTarget::Base, context)?;
+        tx.execute(
+            sql::SET_ACTION,
+            params![&context.project, &context.workspace, anchor_str(context.anchor), &context.tail, kind.as_str(), arg],
+        )?;
+        self.audit_tx(&tx, "bind", context, Some(kind.as_str()), None, None)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Get the action bound to a context, if any
+    pub fn get_action(&self, context: &ResolvedContext) -> Result<Option<ActionBinding>> {
+        self.logger.trace_fn("database", &format!("getting action for context: {}", context));
+
+        let mut stmt = self.connection.prepare(sql::GET_ACTION)?;
+        let mut rows = stmt.query_map(
+            params![&context.project, &context.workspace, anchor_str(context.anchor), &context.tail],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?)),
+        )?;
+
+        match rows.next() {
+            Some(Ok((kind, arg))) => Ok(Some(ActionBinding {
+                project: context.project.clone(),
+                workspace: context.workspace.clone(),
+                anchor: context.anchor,
+                tail: context.tail.clone(),
+                kind: kind.parse()?,
+                arg,
+            })),
+            Some(Err(e)) => Err(e.into()),
+            None => Ok(None),
+        }
+    }
+
+    /// Remove the action bound to a context
+    pub fn delete_action(&self, context: &ResolvedContext) -> Result<bool> {
+        self.logger.trace_fn("database", &format!("unbinding action for context: {}", context));
+
+        let tx = self.connection.unchecked_transaction()?;
+        self.check_writable(&tx, WriteTarget::Base, context)?;
+        let changes = tx.execute(
+            sql::DELETE_ACTION,
+            params![&context.project, &context.workspace, anchor_str(context.anchor), &context.tail],
+        )?;
+        if changes > 0 {
+            self.audit_tx(&tx, "unbind", context, None, None, None)?;
+        }
+        tx.commit()?;
+        Ok(changes > 0)
+    }
+
+    /// List every action binding in this base
+    pub fn list_actions(&self) -> Result<Vec<ActionBinding>> {
+        self.logger.trace_fn("database", "listing action bindings");
+
+        let mut stmt = self.connection.prepare(sql::LIST_ACTIONS)?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, String>(4)?,
+                row.get::<_, Option<String>>(5)?,
+            ))
+        })?;
+
+        let mut bindings = Vec::new();
+        for row in rows {
+            let (project, workspace, anchor, tail, kind, arg) = row?;
+            bindings.push(ActionBinding {
+                project,
+                workspace,
+                anchor: if anchor == "doc" { Anchor::Doc } else { Anchor::Var },
+                tail,
+                kind: kind.parse()?,
+                arg,
+            });
+        }
+
+        Ok(bindings)
+    }
+}
@@ -0,0 +1,65 @@
+// src/db/alias.rs - Per-base chain aliases (`:name` bookmarks)
+
+use crate::error::Result;
+use crate::sql;
+use rusqlite::params;
+use super::Database;
+use super::lock::WriteTarget;
+
+impl Database {
+    /// Store (or replace) the chain behind an alias
+    pub fn set_alias(&self, name: &str, chain: &str) -> Result<()> {
+        self.logger.trace_fn("database", &format!("setting alias :{} -> {}", name, chain));
+
+        let tx = self.connection.unchecked_transaction()?;
+        self.check_writable(&tx, WriteTarget::Base, format!(":{}", name))?;
+        tx.execute(sql::SET_ALIAS, params![name, chain])?;
+        self.audit_tx(&tx, "alias", &self.base_name, Some(name), None, Some(chain.as_bytes()))?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Get the chain stored under an alias, if any
+    pub fn get_alias(&self, name: &str) -> Result<Option<String>> {
+        self.logger.trace_fn("database", &format!("getting alias :{}", name));
+
+        let mut stmt = self.connection.prepare(sql::GET_ALIAS)?;
+        let mut rows = stmt.query_map(params![name], |row| row.get::<_, String>(0))?;
+
+        match rows.next() {
+            Some(Ok(chain)) => Ok(Some(chain)),
+            Some(Err(e)) => Err(e.into()),
+            None => Ok(None),
+        }
+    }
+
+    /// Remove an alias
+    pub fn delete_alias(&self, name: &str) -> Result<bool> {
+        self.logger.trace_fn("database", &format!("deleting alias :{}", name));
+
+        let tx = self.connection.unchecked_transaction()?;
+        self.check_writable(&tx, WriteTarget::Base, format!(":{}", name))?;
+        let changes = tx.execute(sql::DELETE_ALIAS, params![name])?;
+        if changes > 0 {
+            self.audit_tx(&tx, "unalias", &self.base_name, Some(name), None, None)?;
+        }
+        tx.commit()?;
+        Ok(changes > 0)
+    }
+
+    /// List every alias in this base as (name, chain)
+    pub fn list_aliases(&self) -> Result<Vec<(String, String)>> {
+        self.logger.trace_fn("database", "listing aliases");
+
+        let mut stmt = self.connection.prepare(sql::LIST_ALIASES)?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?;
+
+        let mut aliases = Vec::new();
+        for row in rows {
+            aliases.push(row?);
+        }
+        Ok(aliases)
+    }
+}
@@ -0,0 +1,303 @@
+// src/db/audit.rs - Audit trail of every mutation: who, from which command line, what
+//
+// Each write appends to `audit` inside the mutation's own transaction, so an entry
+// exists exactly when the change does. Values are never stored: only an HMAC-SHA256
+// of their stored form (the sealed text for encrypted keystores), keyed with a random
+// per-base secret so a hash cannot be checked against guessed values without the
+// base itself. Value-bearing arguments are masked in the recorded argv (`setv KEY=********`). Variable changes are audited
+// from `record_var_change_tx`; other writes call `audit_tx` directly.
+
+use crate::error::{Result, BookdbError};
+use crate::sql;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::OsRng;
+use hmac::{Hmac, Mac};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+use sha2::Sha256;
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+use super::Database;
+use super::history::HistoryPoint;
+use super::sensitive::MASK;
+use super::vartype::parse_duration;
+
+/// One audited change
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AuditEntry {
+    pub id: i64,
+    /// UTC, `2025-06-01T12:00:00Z`
+    pub when: String,
+    /// `$USER` of the writer, if set
+    pub user: Option<String>,
+    pub pid: i64,
+    /// Command line, values masked
+    pub argv: Vec<String>,
+    /// `project.workspace.var.keystore`, `project.workspace.doc`, or the base name for base-wide changes
+    pub chain: String,
+    /// Variable, `doc` or `doc/segment`; for `mv`, the destination
+    pub key: Option<String>,
+    pub op: String,
+    /// HMAC-SHA256 (hex, keyed per base) of the stored value before and after
+    pub old_hash: Option<String>,
+    pub new_hash: Option<String>,
+}
+
+/// Length of the per-base audit key
+const AUDIT_SECRET_LEN: usize = 32;
+
+/// HMAC-SHA256 of a value under the base's audit key, hex-encoded
+fn value_hash(secret: &[u8], value: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC takes keys of any length");
+    mac.update(value);
+    mac.finalize().into_bytes().iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Mask the values in a command line: `KEY=value` operands and `--if-equals OLD`
+fn redact_argv(args: impl IntoIterator<Item = String>) -> Vec<String> {
+    let mut redacted = Vec::new();
+    let mut mask_next = false;
+    for arg in args {
+        let arg = if mask_next {
+            MASK.to_string()
+        } else if let Some(("--if-equals", _)) = arg.split_once('=') {
+            format!("--if-equals={}", MASK)
+        } else if !arg.starts_with('-') && arg.contains('=') {
+            let (key, _) = arg.split_once('=').unwrap_or_default();
+            format!("{}={}", key, MASK)
+        } else {
+            arg
+        };
+        mask_next = arg == "--if-equals";
+        redacted.push(arg);
+    }
+    redacted
+}
+
+/// `--since`: a duration back from now (`24h`, `7d`), `@<unix-seconds>` or a UTC date/time
+pub fn parse_since(raw: &str) -> Result<i64> {
+    let raw = raw.trim();
+    if let Some(ago) = parse_duration(raw) {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+        return Ok(now.saturating_sub(ago.as_secs()) as i64);
+    }
+    match HistoryPoint::parse(raw) {
+        Ok(HistoryPoint::Time(secs)) => Ok(secs),
+        _ => Err(BookdbError::Argument(format!(
+            "Invalid --since '{}': expected a duration (24h, 7d), @<unix-seconds> or YYYY-MM-DD[THH:MM[:SS]]", raw
+        ))),
+    }
+}
+
+impl Database {
+    /// Audit entries since unix time `since`, on `chain` or anything below it, oldest first
+    pub fn list_audit(&self, since: Option<i64>, chain: Option<&str>) -> Result<Vec<AuditEntry>> {
+        self.logger.trace_fn("database", &format!("listing audit since {:?} on {:?}", since, chain));
+
+        let mut stmt = self.connection.prepare(sql::LIST_AUDIT)?;
+        let rows = stmt.query_map(params![since, chain], |row| {
+            let argv: String = row.get(4)?;
+            Ok(AuditEntry {
+                id: row.get(0)?,
+                when: row.get(1)?,
+                user: row.get(2)?,
+                pid: row.get(3)?,
+                argv: serde_json::from_str(&argv).unwrap_or_else(|_| vec![argv]),
+                chain: row.get(5)?,
+                key: row.get(6)?,
+                op: row.get(7)?,
+                old_hash: row.get(8)?,
+                new_hash: row.get(9)?,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Append an audit entry; call inside the transaction that makes the change
+    pub(super) fn audit_tx(
+        &self,
+        conn: &Connection,
+        op: &str,
+        chain: impl fmt::Display,
+        key: Option<&str>,
+        old_value: Option<&[u8]>,
+        new_value: Option<&[u8]>,
+    ) -> Result<()> {
+        let user = std::env::var("USER").ok();
+        let args = std::env::args_os().map(|arg| arg.to_string_lossy().into_owned());
+        let argv = serde_json::to_string(&redact_argv(args))?;
+        let secret = match (old_value, new_value) {
+            (None, None) => Vec::new(),
+            _ => self.audit_secret(conn)?,
+        };
+        conn.execute(sql::RECORD_AUDIT, params![
+            user,
+            std::process::id(),
+            argv,
+            chain.to_string(),
+            key,
+            op,
+            old_value.map(|value| value_hash(&secret, value)),
+            new_value.map(|value| value_hash(&secret, value)),
+        ])?;
+        Ok(())
+    }
+
+    /// This base's audit key, created on first use
+    fn audit_secret(&self, conn: &Connection) -> Result<Vec<u8>> {
+        if let Some(secret) = conn.query_row(sql::GET_AUDIT_SECRET, [], |row| row.get(0)).optional()? {
+            return Ok(secret);
+        }
+        let mut secret = vec![0u8; AUDIT_SECRET_LEN];
+        OsRng.fill_bytes(&mut secret);
+        conn.execute(sql::SET_AUDIT_SECRET, [&secret])?;
+        Ok(secret)
+    }
+
+    /// Audit a variable change of keystore `kvns_id` (values as stored)
+    pub(super) fn audit_var_tx(
+        &self,
+        conn: &Connection,
+        kvns_id: i64,
+        key: &str,
+        op: &str,
+        old_value: Option<&str>,
+        new_value: Option<&str>,
+    ) -> Result<()> {
+        let chain: String = conn.query_row(sql::GET_KEYSTORE_CHAIN, [kvns_id], |row| row.get(0))?;
+        self.audit_tx(conn, op, chain, Some(key), old_value.map(str::as_bytes), new_value.map(str::as_bytes))
+    }
+
+    /// Newest history revision; pass to `audit_history_since_tx` after a bulk SQL change
+    pub(super) fn last_revision(&self, conn: &Connection) -> Result<i64> {
+        Ok(conn.query_row(sql::GET_LAST_REVISION, [], |row| row.get(0))?)
+    }
+
+    /// Audit every history row written after `rev` (purge, merge) as `op`
+    pub(super) fn audit_history_since_tx(&self, conn: &Connection, rev: i64, op: &str) -> Result<()> {
+        let changes: Vec<(i64, String, Option<String>, Option<String>)> = conn.prepare(sql::LIST_HISTORY_SINCE)?
+            .query_map([rev], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))?
+            .collect::<rusqlite::Result<_>>()?;
+        for (kvns_id, key, old, new) in &changes {
+            self.audit_var_tx(conn, *kvns_id, key, op, old.as_deref(), new.as_deref())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bookdb::service::ctx::{Anchor, ChainMode, ResolvedContext};
+    use crate::bookdb::service::db::driver::ActionKind;
+    use tempfile::TempDir;
+
+    fn create_test_context() -> ResolvedContext {
+        ResolvedContext {
+            base: "test".to_string(),
+            project: "infra".to_string(),
+            workspace: "production".to_string(),
+            anchor: Anchor::Var,
+            tail: "credentials".to_string(),
+            prefix_mode: ChainMode::Persistent,
+        }
+    }
+
+    #[test]
+    fn test_redact_argv() {
+        let argv = ["bookdb", "setv", "DB_PASS=hunter2", "--if-equals", "old", "-c", "@infra.production.var.credentials"]
+            .map(String::from);
+        assert_eq!(redact_argv(argv), vec![
+            "bookdb", "setv", "DB_PASS=********", "--if-equals", "********", "-c", "@infra.production.var.credentials",
+        ]);
+        assert_eq!(redact_argv(["--if-equals=old".to_string()]), vec!["--if-equals=********"]);
+    }
+
+    #[test]
+    fn test_parse_since() {
+        assert_eq!(parse_since("@1700000000").unwrap(), 1_700_000_000);
+        assert_eq!(parse_since("2024-01-01").unwrap(), 1_704_067_200);
+        assert!(parse_since("1h").unwrap() > 1_700_000_000);
+        assert!(parse_since("yesterday").is_err());
+    }
+
+    #[test]
+    fn test_mutations_are_audited() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::create_or_open(&temp_dir.path().join("test.sqlite3"))?;
+        let context = create_test_context();
+
+        db.set_variable("DB_PASS", "hunter2", &context)?;
+        db.set_variable("DB_PASS", "hunter3", &context)?;
+        db.delete_variable("DB_PASS", &context)?;
+        db.set_variable("HITS", "1", &context.with_target("app", "ci", "stats"))?;
+
+        let entries = db.list_audit(None, Some("infra.production"))?;
+        let ops: Vec<&str> = entries.iter().map(|e| e.op.as_str()).collect();
+        assert_eq!(ops, vec!["set", "set", "delete"]);
+        assert_eq!(entries[0].chain, "infra.production.var.credentials");
+        assert_eq!(entries[0].key.as_deref(), Some("DB_PASS"));
+        assert_eq!(entries[0].pid, std::process::id() as i64);
+
+        // Hashes chain old to new; no plaintext anywhere
+        assert_eq!(entries[0].old_hash, None);
+        assert_eq!(entries[1].old_hash, entries[0].new_hash);
+        let secret = db.audit_secret(&db.connection)?;
+        assert_eq!(entries[0].new_hash.as_deref(), Some(value_hash(&secret, b"hunter2").as_str()));
+        assert_eq!(entries[2].new_hash, None);
+
+        assert_eq!(db.list_audit(None, None)?.len(), 4);
+        assert!(db.list_audit(Some(i64::MAX), None)?.is_empty());
+
+        // Structure, aliases and bindings; no-op removals leave no entry
+        db.create_project("web")?;
+        db.set_alias("prod", "@infra.production.var.credentials")?;
+        assert!(db.delete_alias("prod")?);
+        assert!(!db.delete_alias("prod")?);
+        db.set_action(&context, ActionKind::Dump, None)?;
+        assert!(db.delete_action(&context)?);
+        assert!(!db.delete_action(&context)?);
+        assert!(db.delete_workspace("app", "ci")?);
+        assert!(!db.delete_workspace("app", "ci")?);
+        assert!(db.delete_project("web")?);
+        assert!(!db.delete_project("web")?);
+
+        let entries = db.list_audit(None, None)?;
+        let ops: Vec<&str> = entries[4..].iter().map(|e| e.op.as_str()).collect();
+        assert_eq!(ops, vec!["mkproject", "alias", "unalias", "bind", "unbind", "rmworkspace", "rmproject"]);
+        assert_eq!(entries[5].key.as_deref(), Some("prod"));
+        assert_eq!(entries[7].chain, "infra.production.var.credentials");
+        assert_eq!(entries[9].chain, "app.ci");
+        Ok(())
+    }
+
+    #[test]
+    fn test_value_hashes_are_keyed_per_base() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let context = create_test_context();
+        let mut hashes = Vec::new();
+        for name in ["one.sqlite3", "two.sqlite3"] {
+            let db = Database::create_or_open(&temp_dir.path().join(name))?;
+            db.set_variable("DB_PASS", "hunter2", &context)?;
+            hashes.push(db.list_audit(None, None)?[0].new_hash.clone().unwrap());
+        }
+
+        // The same value hashes differently per base, and not as its plain SHA-256
+        assert_ne!(hashes[0], hashes[1]);
+        let unkeyed: String = <Sha256 as sha2::Digest>::digest(b"hunter2").iter().map(|b| format!("{:02x}", b)).collect();
+        assert!(!hashes.contains(&unkeyed));
+        Ok(())
+    }
+
+    #[test]
+    fn test_failed_write_leaves_no_audit() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::create_or_open(&temp_dir.path().join("test.sqlite3"))?;
+        let context = create_test_context();
+
+        db.set_namespace_lock(&context, true)?;
+        assert!(db.set_variable("DB_PASS", "hunter2", &context).is_err());
+        assert!(db.list_audit(None, None)?.iter().all(|e| e.op == "lock"));
+        Ok(())
+    }
+}
@@ -0,0 +1,117 @@
+// src/db/condition.rs - Conditional writes: compare-and-swap for setv and inc
+//
+// The check runs on the value read inside the write's own transaction, so two
+// scripts racing on `setv LEADER=me --if-absent` cannot both win.
+
+use crate::error::{Result, BookdbError};
+use super::counter::Number;
+use super::vartype::{parse_duration, VarType};
+
+/// Precondition for `setv`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::enum_variant_names)] // named after the --if-* flags
+pub enum WriteCondition {
+    /// The current value must equal this one (compared in the key's type)
+    IfEquals(String),
+    IfAbsent,
+    IfPresent,
+}
+
+impl WriteCondition {
+    /// Fail with `PreconditionFailed` unless `current` satisfies the condition
+    pub(super) fn check(&self, key: &str, var_type: VarType, current: Option<&str>) -> Result<()> {
+        let failed = |reason: String| Err(BookdbError::PreconditionFailed(format!("'{}' {}", key, reason)));
+
+        match (self, current) {
+            (WriteCondition::IfAbsent, Some(_)) => failed("already exists".to_string()),
+            (WriteCondition::IfPresent, None) => failed("does not exist".to_string()),
+            (WriteCondition::IfEquals(_), None) => failed("does not exist".to_string()),
+            (WriteCondition::IfEquals(expected), Some(value)) => {
+                // `--if-equals 5` matches a float key holding 5.0
+                let expected = var_type.normalize(key, expected).unwrap_or_else(|_| expected.clone());
+                if expected == value {
+                    Ok(())
+                } else {
+                    failed("does not hold the expected value".to_string())
+                }
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Check `inc --expect`: the value the step starts from must equal `expected`
+pub(super) fn check_expected(key: &str, var_type: VarType, start: &str, expected: Number) -> Result<()> {
+    let matches = match (var_type, expected) {
+        (VarType::Duration, _) => parse_duration(start).map(|d| d.as_secs_f64()) == Some(expected.as_f64()),
+        (_, Number::Int(n)) => start.parse::<i64>().ok() == Some(n),
+        (_, Number::Float(n)) => start.parse::<f64>().ok() == Some(n),
+    };
+    if matches {
+        Ok(())
+    } else {
+        Err(BookdbError::PreconditionFailed(format!("'{}' is {}, expected {}", key, start, expected)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bookdb::service::ctx::{Anchor, ChainMode, ResolvedContext};
+    use crate::bookdb::service::db::driver::{CounterOptions, SetOptions};
+    use crate::bookdb::service::db::Database;
+    use tempfile::TempDir;
+
+    fn create_test_context() -> ResolvedContext {
+        ResolvedContext {
+            base: "test".to_string(),
+            project: "app".to_string(),
+            workspace: "ops".to_string(),
+            anchor: Anchor::Var,
+            tail: "locks".to_string(),
+            prefix_mode: ChainMode::Persistent,
+        }
+    }
+
+    #[test]
+    fn test_conditional_setv() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::create_or_open(&temp_dir.path().join("test.sqlite3"))?;
+        let context = create_test_context();
+        let set = |value: &str, condition: WriteCondition| {
+            db.set_variable_with("LEADER", value, &SetOptions { condition: Some(condition), ..Default::default() }, &context)
+        };
+
+        assert!(matches!(set("a", WriteCondition::IfPresent), Err(BookdbError::PreconditionFailed(_))));
+        set("a", WriteCondition::IfAbsent)?;
+        assert!(matches!(set("b", WriteCondition::IfAbsent), Err(BookdbError::PreconditionFailed(_))));
+        assert!(set("b", WriteCondition::IfEquals("x".to_string())).is_err());
+        set("b", WriteCondition::IfEquals("a".to_string()))?;
+        assert_eq!(db.get_variable("LEADER", &context)?, Some("b".to_string()));
+
+        // A failed check writes nothing, not even history
+        assert_eq!(db.var_history("LEADER", &context)?.len(), 2);
+
+        db.set_typed_variable("RATE", "5", Some(VarType::Float), &context)?;
+        let if_five = SetOptions { condition: Some(WriteCondition::IfEquals("5".to_string())), ..Default::default() };
+        db.set_variable_with("RATE", "6", &if_five, &context)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_inc_expect() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::create_or_open(&temp_dir.path().join("test.sqlite3"))?;
+        let context = create_test_context();
+        let expect = |n: i64| CounterOptions { expect: Some(Number::Int(n)), ..Default::default() };
+
+        // A missing key starts from 0 (or --init)
+        assert_eq!(db.step_counter("EPOCH", Number::Int(1), &expect(0), &context)?, "1");
+        assert!(matches!(
+            db.step_counter("EPOCH", Number::Int(1), &expect(0), &context),
+            Err(BookdbError::PreconditionFailed(_))
+        ));
+        assert_eq!(db.step_counter("EPOCH", Number::Int(1), &expect(1), &context)?, "2");
+        Ok(())
+    }
+}
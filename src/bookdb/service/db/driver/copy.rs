@@ -0,0 +1,339 @@
+// src/db/copy.rs - `cp`: copy keystores, workspaces, projects and documents
+//
+// Source and destination may be different bases, so a copy reads through one
+// `Database` and writes through the other. The whole plan is worked out (and
+// conflicts checked) before anything is written, then applied in one destination
+// transaction: a copy that fails partway leaves the destination as it was.
+
+use crate::error::{Result, BookdbError};
+use crate::bookdb::service::ctx::{Anchor, ChainLevel, ResolvedContext, ROOT_SEGMENT};
+use rusqlite::Transaction;
+use std::time::Duration;
+use super::Database;
+use super::keystore::SetOptions;
+use super::sensitive::looks_sensitive;
+use super::vartype::VarType;
+
+/// What to do when the destination already has a variable or document
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictPolicy {
+    Overwrite,
+    Skip,
+    Fail,
+}
+
+/// What a copy does (or, in a dry run, would do) with one item
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyAction {
+    Create,
+    Overwrite,
+    Skip,
+}
+
+/// One planned copy: a variable or a whole document
+#[derive(Debug, Clone)]
+pub struct CopyItem {
+    /// `variable` or `document`
+    pub kind: &'static str,
+    /// Fully-qualified source chain with the key, `base@p.w.var.ks:KEY`
+    pub from: String,
+    pub to: String,
+    pub action: CopyAction,
+    payload: CopyPayload,
+    target: ResolvedContext,
+}
+
+#[derive(Debug, Clone)]
+enum CopyPayload {
+    /// `ttl` is the source's remaining lifetime; `sensitive` an explicit mark
+    /// (key, keystore or encryption), not one the key's name already implies
+    Variable { key: String, value: String, var_type: VarType, ttl: Option<Duration>, sensitive: bool },
+    Document { key: String, segments: Vec<(String, String, Vec<u8>)> },
+}
+
+impl Database {
+    /// Copy what `from` names on this base to `to` on `dst` (which may be this base)
+    ///
+    /// Returns the plan; with `dry_run` nothing is written.
+    pub fn copy_to(
+        &self,
+        from: &ResolvedContext,
+        dst: &Database,
+        to: &ResolvedContext,
+        policy: ConflictPolicy,
+        dry_run: bool,
+    ) -> Result<Vec<CopyItem>> {
+        self.logger.trace_fn("database", &format!("copying {} -> {} ({:?}, dry run: {})", from, to, policy, dry_run));
+
+        let level = from.level()?;
+        if to.level()? != level {
+            return Err(BookdbError::Argument(format!(
+                "Cannot copy {} to {}: both chains must name a {:?}", from, to, level
+            )));
+        }
+        if level == ChainLevel::Tail && from.anchor != to.anchor {
+            return Err(BookdbError::Argument("Cannot copy between a keystore and a document".to_string()));
+        }
+        if from.qualified() == to.qualified() {
+            return Err(BookdbError::Argument(format!("Source and destination are the same: {}", from.qualified())));
+        }
+
+        let mut plan = Vec::new();
+        match level {
+            ChainLevel::Project => {
+                for workspace in self.list_workspaces(&from.project)? {
+                    self.plan_workspace(
+                        &from.with_target(&from.project, &workspace, ""),
+                        dst,
+                        &to.with_target(&to.project, &workspace, ""),
+                        &mut plan,
+                    )?;
+                }
+            }
+            ChainLevel::Workspace => self.plan_workspace(from, dst, to, &mut plan)?,
+            ChainLevel::Tail => match from.anchor {
+                Anchor::Var => self.plan_keystore(from, dst, to, &mut plan)?,
+                Anchor::Doc => self.plan_document(from.doc_key(), from, dst, to, &mut plan)?,
+            },
+        }
+
+        if plan.is_empty() {
+            return Err(BookdbError::Database(format!("Nothing to copy at {}", from.qualified())));
+        }
+
+        for item in plan.iter_mut().filter(|item| item.action == CopyAction::Overwrite) {
+            match policy {
+                ConflictPolicy::Overwrite => {}
+                ConflictPolicy::Skip => item.action = CopyAction::Skip,
+                ConflictPolicy::Fail => {
+                    return Err(BookdbError::Database(format!(
+                        "{} already exists (use --overwrite or --skip)", item.to
+                    )));
+                }
+            }
+        }
+
+        if !dry_run {
+            // One transaction and one undo step for the whole copy
+            let tx = dst.connection.unchecked_transaction()?;
+            dst.begin_journal("cp", to);
+            let applied = plan.iter()
+                .filter(|item| item.action != CopyAction::Skip)
+                .try_for_each(|item| dst.apply_copy_tx(&tx, item));
+            dst.end_journal();
+            applied?;
+            tx.commit()?;
+        }
+        Ok(plan)
+    }
+
+    /// Plan every keystore and document of a workspace
+    fn plan_workspace(&self, from: &ResolvedContext, dst: &Database, to: &ResolvedContext, plan: &mut Vec<CopyItem>) -> Result<()> {
+        for keystore in self.list_keystores(&from.project, &from.workspace)? {
+            let source = ResolvedContext { anchor: Anchor::Var, ..from.with_target(&from.project, &from.workspace, &keystore) };
+            let target = ResolvedContext { anchor: Anchor::Var, ..to.with_target(&to.project, &to.workspace, &keystore) };
+            self.plan_keystore(&source, dst, &target, plan)?;
+        }
+
+        let doc_from = ResolvedContext { anchor: Anchor::Doc, ..from.clone() };
+        let doc_to = ResolvedContext { anchor: Anchor::Doc, ..to.clone() };
+        for doc_key in self.list_documents(&doc_from)? {
+            let source = doc_from.with_target(&from.project, &from.workspace, &doc_key);
+            let target = doc_to.with_target(&to.project, &to.workspace, &doc_key);
+            self.plan_document(&doc_key, &source, dst, &target, plan)?;
+        }
+        Ok(())
+    }
+
+    fn plan_keystore(&self, from: &ResolvedContext, dst: &Database, to: &ResolvedContext, plan: &mut Vec<CopyItem>) -> Result<()> {
+        let mut variables: Vec<(String, String)> = self.list_variables(from)?.into_iter().collect();
+        variables.sort();
+        let expiries = self.list_variable_expiries(from)?;
+
+        for (key, value) in variables {
+            let exists = dst.get_variable(&key, to)?.is_some();
+            let var_type = self.get_variable_type(&key, from)?;
+            let ttl = expiries.get(&key).copied();
+            let sensitive = !looks_sensitive(&key) && self.is_sensitive(&key, from)?;
+            plan.push(CopyItem {
+                kind: "variable",
+                from: format!("{}:{}", from.qualified(), key),
+                to: format!("{}:{}", to.qualified(), key),
+                action: if exists { CopyAction::Overwrite } else { CopyAction::Create },
+                payload: CopyPayload::Variable { key, value, var_type, ttl, sensitive },
+                target: to.clone(),
+            });
+        }
+        Ok(())
+    }
+
+    fn plan_document(&self, doc_key: &str, from: &ResolvedContext, dst: &Database, to: &ResolvedContext, plan: &mut Vec<CopyItem>) -> Result<()> {
+        let mut segments = Vec::new();
+        for info in self.list_doc_segments(doc_key, from)? {
+            if let Some((content, mime)) = self.get_doc_segment(doc_key, &info.path, from)? {
+                segments.push((info.path, mime, content));
+            }
+        }
+        // Documents from before segments only have doc_content (served as _root)
+        if segments.is_empty() {
+            if let Some((content, mime)) = self.get_doc_segment(doc_key, ROOT_SEGMENT, from)? {
+                segments.push((ROOT_SEGMENT.to_string(), mime, content));
+            }
+        }
+        if segments.is_empty() {
+            return Ok(());
+        }
+
+        let to_key = to.doc_key().to_string();
+        let exists = dst.list_documents(to)?.contains(&to_key);
+        plan.push(CopyItem {
+            kind: "document",
+            from: from.qualified(),
+            to: to.qualified(),
+            action: if exists { CopyAction::Overwrite } else { CopyAction::Create },
+            payload: CopyPayload::Document { key: to_key, segments },
+            target: to.clone(),
+        });
+        Ok(())
+    }
+
+    /// Write one planned item; overwritten documents are replaced as a whole
+    fn apply_copy_tx(&self, tx: &Transaction, item: &CopyItem) -> Result<()> {
+        match &item.payload {
+            // Copies carry their type and expiry; an untyped source un-types the destination
+            CopyPayload::Variable { key, value, var_type, ttl, sensitive } => {
+                let options = SetOptions { var_type: Some(*var_type), ttl: *ttl, ..Default::default() };
+                self.set_variable_with_tx(tx, key, value, &options, &item.target)?;
+                if *sensitive {
+                    self.mark_sensitive_tx(tx, Some(key), true, &item.target)?;
+                }
+            }
+            CopyPayload::Document { key, segments } => {
+                if item.action == CopyAction::Overwrite {
+                    self.delete_document_tx(tx, key, &item.target)?;
+                }
+                for (path, mime, content) in segments {
+                    self.set_doc_segment_tx(tx, key, path, mime, content, &item.target)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bookdb::service::ctx::ChainMode;
+    use tempfile::TempDir;
+
+    fn create_test_context(base: &str, workspace: &str, anchor: Anchor, tail: &str) -> ResolvedContext {
+        ResolvedContext {
+            base: base.to_string(),
+            project: "app".to_string(),
+            workspace: workspace.to_string(),
+            anchor,
+            tail: tail.to_string(),
+            prefix_mode: ChainMode::Persistent,
+        }
+    }
+
+    fn create_test_db(temp_dir: &TempDir, base: &str) -> Database {
+        Database::create_or_open(&temp_dir.path().join(format!("{}.sqlite3", base))).unwrap()
+    }
+
+    #[test]
+    fn test_copy_workspace_with_policies() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let db = create_test_db(&temp_dir, "home");
+        db.set_variable("DB_URL", "prod-db", &create_test_context("home", "production", Anchor::Var, "config"))?;
+        db.set_variable("LOG", "info", &create_test_context("home", "production", Anchor::Var, "config"))?;
+        db.set_doc_segment("README", "_root", "text/plain", b"prod notes", &create_test_context("home", "production", Anchor::Doc, "README"))?;
+        db.set_variable("LOG", "debug", &create_test_context("home", "staging", Anchor::Var, "config"))?;
+
+        let from = create_test_context("home", "production", Anchor::Var, "*");
+        let to = create_test_context("home", "staging", Anchor::Var, "*");
+        let staging_config = create_test_context("home", "staging", Anchor::Var, "config");
+
+        // LOG collides: fail by default, nothing written
+        assert!(db.copy_to(&from, &db, &to, ConflictPolicy::Fail, false).is_err());
+        assert_eq!(db.get_variable("DB_URL", &staging_config)?, None);
+
+        // Dry run plans without writing
+        let plan = db.copy_to(&from, &db, &to, ConflictPolicy::Skip, true)?;
+        assert_eq!(plan.len(), 3);
+        assert_eq!(db.get_variable("DB_URL", &staging_config)?, None);
+
+        let plan = db.copy_to(&from, &db, &to, ConflictPolicy::Skip, false)?;
+        assert_eq!(plan.iter().filter(|i| i.action == CopyAction::Skip).count(), 1);
+        assert_eq!(db.get_variable("DB_URL", &staging_config)?, Some("prod-db".to_string()));
+        assert_eq!(db.get_variable("LOG", &staging_config)?, Some("debug".to_string()));
+        assert!(db.get_doc_segment("README", "_root", &create_test_context("home", "staging", Anchor::Doc, "README"))?.is_some());
+
+        db.copy_to(&from, &db, &to, ConflictPolicy::Overwrite, false)?;
+        assert_eq!(db.get_variable("LOG", &staging_config)?, Some("info".to_string()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_failed_copy_writes_nothing() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let db = create_test_db(&temp_dir, "home");
+        db.set_variable("DB_URL", "prod-db", &create_test_context("home", "production", Anchor::Var, "config"))?;
+        db.set_variable("API_TOKEN", "t", &create_test_context("home", "production", Anchor::Var, "secrets"))?;
+        db.set_namespace_lock(&create_test_context("home", "staging", Anchor::Var, "secrets"), true)?;
+
+        // config is applied before the locked keystore fails; it must not stick
+        let journal = db.list_journal(10)?.len();
+        let from = create_test_context("home", "production", Anchor::Var, "*");
+        let to = create_test_context("home", "staging", Anchor::Var, "*");
+        assert!(matches!(db.copy_to(&from, &db, &to, ConflictPolicy::Fail, false), Err(BookdbError::Locked(_))));
+        assert_eq!(db.get_variable("DB_URL", &create_test_context("home", "staging", Anchor::Var, "config"))?, None);
+        assert_eq!(db.list_journal(10)?.len(), journal);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_copy_keeps_expiry_and_sensitive_marks() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let db = create_test_db(&temp_dir, "home");
+        let from = create_test_context("home", "prod", Anchor::Var, "config");
+        let to = create_test_context("home", "staging", Anchor::Var, "config");
+        let ttl = SetOptions { ttl: Some(Duration::from_secs(3600)), ..Default::default() };
+        db.set_variable_with("SESSION", "s1", &ttl, &from)?;
+        db.set_variable("DB_HOST", "db1", &from)?;
+        db.set_variable("LOG", "info", &from)?;
+        db.mark_sensitive(Some("DB_HOST"), true, &from)?;
+
+        db.copy_to(&from, &db, &to, ConflictPolicy::Fail, false)?;
+        let expiries = db.list_variable_expiries(&to)?;
+        assert!(expiries["SESSION"] > Duration::from_secs(3500));
+        assert!(!expiries.contains_key("LOG"));
+        assert!(db.is_sensitive("DB_HOST", &to)?);
+        assert!(!db.is_sensitive("LOG", &to)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_copy_across_bases() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let home = create_test_db(&temp_dir, "home");
+        let work = create_test_db(&temp_dir, "work");
+        home.set_doc_segment("README", "api/v1", "application/json", b"{}", &create_test_context("home", "prod", Anchor::Doc, "README"))?;
+        home.set_variable("TOKEN", "t", &create_test_context("home", "prod", Anchor::Var, "secrets"))?;
+
+        let to_doc = create_test_context("work", "prod", Anchor::Doc, "GUIDE");
+        home.copy_to(&create_test_context("home", "prod", Anchor::Doc, "README"), &work, &to_doc, ConflictPolicy::Fail, false)?;
+        let (content, mime) = work.get_doc_segment("GUIDE", "api/v1", &to_doc)?.unwrap();
+        assert_eq!((content.as_slice(), mime.as_str()), (&b"{}"[..], "application/json"));
+
+        let to_ks = create_test_context("work", "prod", Anchor::Var, "secrets");
+        home.copy_to(&create_test_context("home", "prod", Anchor::Var, "secrets"), &work, &to_ks, ConflictPolicy::Fail, false)?;
+        assert_eq!(work.get_variable("TOKEN", &to_ks)?, Some("t".to_string()));
+
+        Ok(())
+    }
+}
@@ -1,26 +1,54 @@
 // src/db/core.rs - Core database functionality
 
+use std::cell::{Cell, RefCell};
 use std::path::Path;
 use stderr::{Stderr, StderrConfig};
-use rusqlite::{Connection, Transaction};
+use rusqlite::{Connection, OpenFlags, Transaction};
 
 use crate::error::{Result, BookdbError};
 
 use crate::sql;
+use super::journal::JournalScope;
+use super::crypto::Vault;
 
 
 
+/// Trace output for `&self` database methods
+pub struct DbLogger(RefCell<Stderr>);
+
+impl DbLogger {
+    pub fn new() -> Self {
+        Self(RefCell::new(Stderr::new()))
+    }
+
+    pub fn trace_fn(&self, func_name: &str, msg: &str) {
+        self.0.borrow_mut().trace_fn(func_name, msg);
+    }
+}
+
+impl Default for DbLogger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Core database connection and schema management
 pub struct Database {
     pub connection: Connection,
-    pub logger: Stderr,
+    pub logger: DbLogger,
     pub base_name: String,
+    /// Open `begin_journal` scope, if any
+    pub(super) journal: RefCell<Option<JournalScope>>,
+    /// Passphrase and keys for encrypted keystores
+    pub(super) vault: RefCell<Vault>,
+    /// Write through locked namespaces (`--force`)
+    pub(super) force: Cell<bool>,
 }
 
 impl Database {
     /// Create or open a database at the specified path
     pub fn create_or_open(path: &Path) -> Result<Self> {
-        let mut logger = Stderr::new();
+        let logger = DbLogger::new();
         logger.trace_fn("database", &format!("opening database: {:?}", path));
         
         let connection = Connection::open(path)?;
@@ -37,10 +65,13 @@ impl Database {
             connection,
             logger,
             base_name,
+            journal: RefCell::new(None),
+            vault: RefCell::new(Vault::default()),
+            force: Cell::new(false),
         };
         
         db.setup_schema()?;
-        logger.trace_fn("database", "database ready");
+        db.logger.trace_fn("database", "database ready");
         
         Ok(db)
     }
@@ -53,27 +84,79 @@ impl Database {
         Self::create_or_open(path)
     }
     
+    /// Open an existing base read-only, without touching its schema
+    ///
+    /// Used for cross-base (`*@`) reads, where no base should be written to.
+    pub fn open_readonly(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Err(BookdbError::Database(format!("Database not found: {:?}", path)));
+        }
+        
+        let logger = DbLogger::new();
+        logger.trace_fn("database", &format!("opening database read-only: {:?}", path));
+        
+        let connection = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        let base_name = path.file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("unknown")
+            .to_string();
+        
+        Ok(Self {
+            connection,
+            logger,
+            base_name,
+            journal: RefCell::new(None),
+            vault: RefCell::new(Vault::default()),
+            force: Cell::new(false),
+        })
+    }
+    
     /// Set up database schema using external SQL files
-    fn setup_schema(&mut self) ->  Result<(), E> {
+    fn setup_schema(&mut self) -> Result<()> {
         self.logger.trace_fn("database", "setting up schema from external files");
         
-        // Use external SQL files via sql.rs
-        self.connection.execute(sql::V1_CREATE_TABLES, [])?;
-        self.connection.execute(sql::V2_CREATE_DOCS, [])?;
+        // Use external SQL files via sql.rs (each file holds several statements)
+        self.connection.execute_batch(sql::V1__CREATE_TABLES)?;
+        self.connection.execute_batch(sql::V2__CREATE_DOCS)?;
+        self.connection.execute_batch(sql::V3__CREATE_ACTIONS)?;
+        self.connection.execute_batch(sql::V4__CREATE_ALIASES)?;
+        self.connection.execute_batch(sql::V5__CREATE_DOC_FTS)?;
+        self.connection.execute_batch(sql::V6__CREATE_VAR_HISTORY)?;
+        self.connection.execute_batch(sql::V7__CREATE_JOURNAL)?;
+        if !self.column_exists("vars", "var_type")? {
+            self.connection.execute_batch(sql::V8__ADD_VAR_TYPE)?;
+        }
+        if !self.column_exists("vars", "var_expires")? {
+            self.connection.execute_batch(sql::V9__ADD_VAR_EXPIRY)?;
+        }
+        self.connection.execute_batch(sql::V10__CREATE_KEYSTORE_CRYPTO)?;
+        self.connection.execute_batch(sql::V11__CREATE_SENSITIVE_KEYS)?;
+        self.connection.execute_batch(sql::V12__CREATE_LOCKS)?;
+        self.connection.execute_batch(sql::V13__CREATE_AUDIT)?;
+        if !self.column_exists("journal_entries", "old_type")? {
+            self.connection.execute_batch(sql::V14__ADD_JOURNAL_VAR_ATTRS)?;
+        }
+        self.connection.execute_batch(sql::V15__CREATE_AUDIT_SECRET)?;
         
         self.logger.trace_fn("database", "schema setup complete");
         Ok(())
     }
     
+    /// Whether `table` has `column` (for migrations that ALTER TABLE)
+    fn column_exists(&self, table: &str, column: &str) -> Result<bool> {
+        let count: i64 = self.connection.query_row(sql::COLUMN_EXISTS, [table, column], |row| row.get(0))?;
+        Ok(count > 0)
+    }
+    
     /// Execute raw SQL (for installation and setup)
-    pub fn execute_sql(&self, sql: &str) ->  Result<(), E> {
+    pub fn execute_sql(&self, sql: &str) -> Result<()> {
         self.logger.trace_fn("database", "executing raw SQL");
-        self.connection.execute(sql, [])?;
+        self.connection.execute_batch(sql)?;
         Ok(())
     }
     
     /// Begin a transaction
-    pub fn transaction(&self) -> Result<Transaction> {
-        Ok(self.connection.transaction()?)
+    pub fn transaction(&self) -> Result<Transaction<'_>> {
+        Ok(self.connection.unchecked_transaction()?)
     }
 }
@@ -0,0 +1,291 @@
+// src/db/counter.rs - Counters: integer, float and duration steps with bounds
+//
+// `inc`/`dec` go through `step_counter`. The mode comes from the key's type
+// (float, duration, else integer) or `--float`; bounds either clamp the result
+// or, with `--wrap`, fold it back into the range.
+
+use crate::error::{Result, BookdbError};
+use crate::bookdb::service::ctx::ResolvedContext;
+use crate::sql;
+use rusqlite::params;
+use super::Database;
+use super::history::VarOp;
+use super::journal::Inverse;
+use super::condition::check_expected;
+use super::lock::WriteTarget;
+use super::vartype::{format_duration, format_float, parse_duration, VarType};
+use std::fmt;
+use std::time::Duration;
+
+/// A counter operand as given on the command line
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Number {
+    Int(i64),
+    Float(f64),
+}
+
+impl Number {
+    pub fn parse(raw: &str) -> Result<Self> {
+        let raw = raw.trim();
+        if let Ok(n) = raw.parse::<i64>() {
+            return Ok(Number::Int(n));
+        }
+        match raw.parse::<f64>() {
+            Ok(n) if n.is_finite() => Ok(Number::Float(n)),
+            _ => Err(BookdbError::Argument(format!("Not a number: '{}'", raw))),
+        }
+    }
+
+    /// The step for `dec`
+    pub fn negated(self) -> Result<Self> {
+        match self {
+            Number::Int(n) => n.checked_neg().map(Number::Int).ok_or(BookdbError::NumericOverflow),
+            Number::Float(n) => Ok(Number::Float(-n)),
+        }
+    }
+
+    pub(super) fn as_f64(self) -> f64 {
+        match self {
+            Number::Int(n) => n as f64,
+            Number::Float(n) => n,
+        }
+    }
+
+    fn as_int(self, what: &str) -> Result<i64> {
+        match self {
+            Number::Int(n) => Ok(n),
+            Number::Float(n) => Err(BookdbError::Argument(format!(
+                "{} {} is fractional; use --float for a float counter", what, n
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Number::Int(n) => write!(f, "{}", n),
+            Number::Float(n) => write!(f, "{}", format_float(*n)),
+        }
+    }
+}
+
+/// Options for `inc`/`dec` beyond a plain integer step
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CounterOptions {
+    /// Count in floats even though the key is untyped
+    pub float: bool,
+    pub min: Option<Number>,
+    pub max: Option<Number>,
+    /// Fold results back into the range instead of clamping (needs min and max)
+    ///
+    /// Integer ranges are inclusive (`--min 1 --max 12`: 12 + 1 is 1); float
+    /// ranges are half-open (`--min 0 --max 360`: 360.0 is 0.0).
+    pub wrap: bool,
+    /// Value a missing key starts from before the step (default 0)
+    pub init: Option<Number>,
+    /// Only step if the counter currently holds this value (`inc --expect`)
+    pub expect: Option<Number>,
+}
+
+impl CounterOptions {
+    fn is_bounded(&self) -> bool {
+        self.min.is_some() || self.max.is_some()
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.wrap && (self.min.is_none() || self.max.is_none()) {
+            return Err(BookdbError::Argument("--wrap needs both --min and --max".to_string()));
+        }
+        if let (Some(min), Some(max)) = (self.min, self.max) {
+            if min.as_f64() > max.as_f64() || (self.wrap && self.float && min.as_f64() == max.as_f64()) {
+                return Err(BookdbError::Argument("--min must be below --max".to_string()));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Fit an integer result into the bounds
+fn bound_int(value: i128, min: Option<i64>, max: Option<i64>, wrap: bool) -> Result<i64> {
+    let value = match (min, max) {
+        (Some(min), Some(max)) if wrap => {
+            let span = max as i128 - min as i128 + 1;
+            min as i128 + (value - min as i128).rem_euclid(span)
+        }
+        _ => value
+            .max(min.map_or(i128::MIN, i128::from))
+            .min(max.map_or(i128::MAX, i128::from)),
+    };
+    i64::try_from(value).map_err(|_| BookdbError::NumericOverflow)
+}
+
+/// Fit a float result into the bounds
+fn bound_float(value: f64, min: Option<f64>, max: Option<f64>, wrap: bool) -> f64 {
+    match (min, max) {
+        (Some(min), Some(max)) if wrap => min + (value - min).rem_euclid(max - min),
+        _ => value.max(min.unwrap_or(f64::NEG_INFINITY)).min(max.unwrap_or(f64::INFINITY)),
+    }
+}
+
+impl Database {
+    /// Atomically add `amount` to a counter; returns the new stored value
+    pub fn step_counter(&self, key: &str, amount: Number, options: &CounterOptions, context: &ResolvedContext) -> Result<String> {
+        self.logger.trace_fn("database", &format!("stepping counter {} by {:?} ({:?}) in context: {}", key, amount, options, context));
+        options.validate()?;
+
+        let tx = self.connection.unchecked_transaction()?;
+        let kvns_id = self.ensure_keystore_context_exists(&tx, context)?;
+        self.check_writable(&tx, WriteTarget::Keystore(kvns_id), context)?;
+
+        let stored_old = self.get_variable_in_tx(&tx, key, context)?;
+        let old_attrs = self.var_attrs_tx(&tx, key, context)?;
+        let old_value = stored_old.as_deref().map(|old| self.open_value(&tx, kvns_id, key, old)).transpose()?;
+        let var_type = self.get_variable_type_in_tx(&tx, key, context)?;
+        let non_numeric = |val: &String| BookdbError::NonNumericValue(key.to_string(), val.clone());
+
+        // A missing key is compared as the value it would start from
+        if let Some(expected) = options.expect {
+            let start = old_value.clone()
+                .unwrap_or_else(|| options.init.unwrap_or(Number::Int(0)).to_string());
+            check_expected(key, var_type, &start, expected)?;
+        }
+
+        let new_text = match var_type {
+            VarType::Bool | VarType::Json => {
+                return Err(non_numeric(&old_value.unwrap_or_default()));
+            }
+            VarType::Int if options.float => {
+                return Err(BookdbError::Argument(format!("'{}' is typed int; --float would break its type", key)));
+            }
+            VarType::Duration => {
+                if options.is_bounded() || options.float {
+                    return Err(BookdbError::Argument(format!(
+                        "'{}' is a duration; --float, --min and --max do not apply", key
+                    )));
+                }
+                let current = match &old_value {
+                    Some(val) => parse_duration(val).ok_or_else(|| non_numeric(val))?,
+                    None => Duration::from_secs(options.init.map_or(Ok(0), |n| n.as_int("--init"))?.max(0) as u64),
+                };
+                let step = amount.as_int("Duration step")?;
+                let delta = Duration::from_secs(step.unsigned_abs());
+                let new_value = if step < 0 { current.checked_sub(delta) } else { current.checked_add(delta) };
+                format_duration(new_value.ok_or(BookdbError::NumericOverflow)?)
+            }
+            VarType::Float => self.step_float(&old_value, amount, options, non_numeric)?,
+            VarType::Str if options.float => self.step_float(&old_value, amount, options, non_numeric)?,
+            VarType::Int | VarType::Str => {
+                let current = match &old_value {
+                    Some(val) => val.parse::<i64>().map_err(|_| non_numeric(val))?,
+                    None => options.init.map_or(Ok(0), |n| n.as_int("--init"))?,
+                };
+                let raw = current as i128 + amount.as_int("Step")? as i128;
+                let min = options.min.map(|n| n.as_int("--min")).transpose()?;
+                let max = options.max.map(|n| n.as_int("--max")).transpose()?;
+                bound_int(raw, min, max, options.wrap)?.to_string()
+            }
+        };
+
+        // Update the variable; history and journal keep the stored (possibly sealed) forms
+        let stored_new = self.seal_value(&tx, kvns_id, key, &new_text)?;
+        tx.execute(sql::SET_VARIABLE, params![key, &stored_new, kvns_id])?;
+        self.record_var_change_tx(&tx, kvns_id, key, VarOp::Inc, stored_old.as_deref(), Some(&stored_new))?;
+        let command = if amount.as_f64() < 0.0 { "dec" } else { "inc" };
+        self.journal_tx(&tx, command, context, Inverse::Var { key, old: stored_old.as_deref(), attrs: old_attrs })?;
+
+        tx.commit()?;
+        Ok(new_text)
+    }
+
+    fn step_float(
+        &self,
+        old_value: &Option<String>,
+        amount: Number,
+        options: &CounterOptions,
+        non_numeric: impl Fn(&String) -> BookdbError,
+    ) -> Result<String> {
+        let current = match old_value {
+            Some(val) => val.parse::<f64>().map_err(|_| non_numeric(val))?,
+            None => options.init.map_or(0.0, Number::as_f64),
+        };
+        let value = bound_float(
+            current + amount.as_f64(),
+            options.min.map(Number::as_f64),
+            options.max.map(Number::as_f64),
+            options.wrap,
+        );
+        if !value.is_finite() {
+            return Err(BookdbError::NumericOverflow);
+        }
+        Ok(format_float(value))
+    }
+
+    /// Set a counter back to `to` (default 0), in the key's type
+    pub fn reset_counter(&self, key: &str, to: Option<Number>, context: &ResolvedContext) -> Result<String> {
+        self.logger.trace_fn("database", &format!("resetting counter {} to {:?} in context: {}", key, to, context));
+
+        let value = match to.unwrap_or(Number::Int(0)) {
+            Number::Int(n) => n.to_string(),
+            Number::Float(n) => format_float(n),
+        };
+        self.set_typed_variable(key, &value, None, context)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bookdb::service::ctx::{Anchor, ChainMode};
+    use tempfile::TempDir;
+
+    fn create_test_context() -> ResolvedContext {
+        ResolvedContext {
+            base: "test".to_string(),
+            project: "app".to_string(),
+            workspace: "ci".to_string(),
+            anchor: Anchor::Var,
+            tail: "counters".to_string(),
+            prefix_mode: ChainMode::Persistent,
+        }
+    }
+
+    #[test]
+    fn test_bounds() {
+        assert_eq!(bound_int(15, Some(0), Some(10), false).unwrap(), 10);
+        assert_eq!(bound_int(-3, Some(0), None, false).unwrap(), 0);
+        assert_eq!(bound_int(13, Some(1), Some(12), true).unwrap(), 1);
+        assert_eq!(bound_int(0, Some(1), Some(12), true).unwrap(), 12);
+        assert!(bound_int(i64::MAX as i128 + 1, None, None, false).is_err());
+        assert_eq!(bound_float(370.0, Some(0.0), Some(360.0), true), 10.0);
+        assert_eq!(bound_float(-0.5, Some(0.0), Some(1.0), false), 0.0);
+    }
+
+    #[test]
+    fn test_counter_options() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::create_or_open(&temp_dir.path().join("test.sqlite3"))?;
+        let context = create_test_context();
+
+        let init = CounterOptions { init: Some(Number::Int(100)), ..Default::default() };
+        assert_eq!(db.step_counter("BUILD", Number::Int(1), &init, &context)?, "101");
+        assert_eq!(db.step_counter("BUILD", Number::Int(1), &init, &context)?, "102");
+
+        let budget = CounterOptions { min: Some(Number::Int(0)), max: Some(Number::Int(5)), ..Default::default() };
+        db.step_counter("BUDGET", Number::Int(4), &budget, &context)?;
+        assert_eq!(db.step_counter("BUDGET", Number::Int(4), &budget, &context)?, "5");
+        assert_eq!(db.step_counter("BUDGET", Number::Int(-9), &budget, &context)?, "0");
+
+        let slot = CounterOptions { min: Some(Number::Int(0)), max: Some(Number::Int(2)), wrap: true, ..Default::default() };
+        let slots: Vec<String> = (0..4).map(|_| db.step_counter("SLOT", Number::Int(1), &slot, &context)).collect::<Result<_>>()?;
+        assert_eq!(slots, vec!["1", "2", "0", "1"]);
+
+        let float = CounterOptions { float: true, ..Default::default() };
+        assert_eq!(db.step_counter("RATE", Number::Float(0.25), &float, &context)?, "0.25");
+        assert!(db.step_counter("BUILD", Number::Float(0.5), &CounterOptions::default(), &context).is_err());
+        assert!(db.step_counter("X", Number::Int(1), &CounterOptions { wrap: true, ..Default::default() }, &context).is_err());
+
+        assert_eq!(db.reset_counter("BUILD", None, &context)?, "0");
+        Ok(())
+    }
+}
@@ -0,0 +1,334 @@
+// src/db/crypto.rs - Opt-in keystore encryption (ChaCha20-Poly1305, Argon2id key)
+//
+// An encrypted keystore has a keystore_crypto row holding its salt and a sealed
+// check value. Its var_value cells, and the history and journal copies of them,
+// hold `enc:v1:<base64 nonce + ciphertext>` with the variable key as associated
+// data, so a sealed value cannot be moved to another key unnoticed. Values are
+// sealed on write and opened on read inside the driver; callers see plaintext.
+
+use crate::error::{Result, BookdbError};
+use crate::bookdb::service::ctx::ResolvedContext;
+use crate::sql;
+use argon2::Argon2;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::HashMap;
+use std::io;
+use super::Database;
+use super::lock::WriteTarget;
+
+/// Environment variable that unlocks encrypted keystores without a prompt
+pub const PASSPHRASE_ENV: &str = "BOOKDB_PASSPHRASE";
+
+const SEALED_PREFIX: &str = "enc:v1:";
+const NONCE_LEN: usize = 12;
+const SALT_LEN: usize = 16;
+/// Sealed into keystore_crypto.kc_check to tell a wrong passphrase from corrupt data
+const CHECK_TEXT: &str = "bookdb-keystore";
+
+/// Passphrase and derived keys for one open base
+#[derive(Default)]
+pub struct Vault {
+    passphrase: Option<String>,
+    /// Asked on first use when no passphrase was given up front
+    prompt: Option<Box<dyn Fn() -> io::Result<String>>>,
+    ciphers: HashMap<i64, ChaCha20Poly1305>,
+}
+
+fn seal(cipher: &ChaCha20Poly1305, key: &str, value: &str) -> Result<String> {
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let sealed = cipher
+        .encrypt(&nonce, Payload { msg: value.as_bytes(), aad: key.as_bytes() })
+        .map_err(|_| BookdbError::Crypto(format!("could not encrypt '{}'", key)))?;
+
+    let mut bytes = nonce.to_vec();
+    bytes.extend_from_slice(&sealed);
+    Ok(format!("{}{}", SEALED_PREFIX, STANDARD.encode(bytes)))
+}
+
+fn open(cipher: &ChaCha20Poly1305, key: &str, stored: &str) -> Result<String> {
+    let corrupt = || BookdbError::Crypto(format!("'{}' cannot be decrypted (wrong passphrase or corrupt value)", key));
+
+    let encoded = stored.strip_prefix(SEALED_PREFIX).ok_or_else(corrupt)?;
+    let bytes = STANDARD.decode(encoded).map_err(|_| corrupt())?;
+    if bytes.len() < NONCE_LEN {
+        return Err(corrupt());
+    }
+    let (nonce, sealed) = bytes.split_at(NONCE_LEN);
+    let plain = cipher
+        .decrypt(Nonce::from_slice(nonce), Payload { msg: sealed, aad: key.as_bytes() })
+        .map_err(|_| corrupt())?;
+    String::from_utf8(plain).map_err(|_| corrupt())
+}
+
+fn derive_cipher(passphrase: &str, salt: &[u8]) -> Result<ChaCha20Poly1305> {
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| BookdbError::Crypto(format!("key derivation failed: {}", e)))?;
+    Ok(ChaCha20Poly1305::new(&key.into()))
+}
+
+impl Database {
+    /// Use `passphrase` for encrypted keystores in this base
+    pub fn unlock(&self, passphrase: &str) {
+        let mut vault = self.vault.borrow_mut();
+        vault.passphrase = Some(passphrase.to_string());
+        vault.ciphers.clear();
+    }
+
+    /// Ask for the passphrase with `prompt` the first time an encrypted value is touched
+    pub fn set_passphrase_prompt(&self, prompt: impl Fn() -> io::Result<String> + 'static) {
+        self.vault.borrow_mut().prompt = Some(Box::new(prompt));
+    }
+
+    /// Whether the keystore of `context` is encrypted
+    pub fn is_keystore_encrypted(&self, context: &ResolvedContext) -> Result<bool> {
+        match self.keystore_id(&self.connection, context)? {
+            Some(kvns_id) => Ok(self.connection
+                .query_row(sql::GET_KEYSTORE_CRYPTO, [kvns_id], |_| Ok(()))
+                .optional()?
+                .is_some()),
+            None => Ok(false),
+        }
+    }
+
+    /// Encrypt a keystore: its values, their history and their journal entries
+    ///
+    /// Needs a passphrase (`unlock` or the prompt). Returns the number of variables.
+    pub fn encrypt_keystore(&self, context: &ResolvedContext) -> Result<usize> {
+        self.logger.trace_fn("database", &format!("encrypting keystore: {}", context));
+
+        let tx = self.connection.unchecked_transaction()?;
+        let kvns_id = self.keystore_id(&tx, context)?
+            .ok_or_else(|| BookdbError::KeyNotFound(format!("keystore {}", context)))?;
+        self.check_writable(&tx, WriteTarget::Keystore(kvns_id), context)?;
+        if self.keystore_cipher(&tx, kvns_id)?.is_some() {
+            return Err(BookdbError::Argument(format!("{} is already encrypted", context)));
+        }
+
+        let mut salt = [0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        let cipher = derive_cipher(&self.passphrase()?, &salt)?;
+        let check = seal(&cipher, CHECK_TEXT, CHECK_TEXT)?;
+
+        let count = self.rewrite_keystore_tx(&tx, kvns_id, context, |key, value| seal(&cipher, key, value))?;
+        tx.execute(sql::SET_KEYSTORE_CRYPTO, params![kvns_id, &salt[..], check])?;
+        self.audit_tx(&tx, "encrypt", context, None, None, None)?;
+        tx.commit()?;
+
+        self.vault.borrow_mut().ciphers.insert(kvns_id, cipher);
+        Ok(count)
+    }
+
+    /// Store a keystore in plaintext again; returns the number of variables
+    pub fn decrypt_keystore(&self, context: &ResolvedContext) -> Result<usize> {
+        self.logger.trace_fn("database", &format!("decrypting keystore: {}", context));
+
+        let tx = self.connection.unchecked_transaction()?;
+        let kvns_id = self.keystore_id(&tx, context)?
+            .ok_or_else(|| BookdbError::KeyNotFound(format!("keystore {}", context)))?;
+        self.check_writable(&tx, WriteTarget::Keystore(kvns_id), context)?;
+        let cipher = self.keystore_cipher(&tx, kvns_id)?
+            .ok_or_else(|| BookdbError::Argument(format!("{} is not encrypted", context)))?;
+
+        let count = self.rewrite_keystore_tx(&tx, kvns_id, context, |key, value| {
+            if value.starts_with(SEALED_PREFIX) { open(&cipher, key, value) } else { Ok(value.to_string()) }
+        })?;
+        tx.execute(sql::DELETE_KEYSTORE_CRYPTO, [kvns_id])?;
+        self.audit_tx(&tx, "decrypt", context, None, None, None)?;
+        tx.commit()?;
+
+        self.vault.borrow_mut().ciphers.remove(&kvns_id);
+        Ok(count)
+    }
+
+    /// Stored form of `value` for keystore `kvns_id` (sealed if it is encrypted)
+    pub(super) fn seal_value(&self, conn: &Connection, kvns_id: i64, key: &str, value: &str) -> Result<String> {
+        match self.keystore_cipher(conn, kvns_id)? {
+            Some(cipher) => seal(&cipher, key, value),
+            None => Ok(value.to_string()),
+        }
+    }
+
+    /// Plaintext of a stored value of keystore `kvns_id`
+    pub(super) fn open_value(&self, conn: &Connection, kvns_id: i64, key: &str, stored: &str) -> Result<String> {
+        // Only values that look sealed need the keystore lookup
+        if !stored.starts_with(SEALED_PREFIX) {
+            return Ok(stored.to_string());
+        }
+        match self.keystore_cipher(conn, kvns_id)? {
+            Some(cipher) => open(&cipher, key, stored),
+            None => Ok(stored.to_string()),
+        }
+    }
+
+    /// Plaintext of a stored value of the keystore of `context`
+    pub(super) fn open_in_context(&self, context: &ResolvedContext, key: &str, stored: String) -> Result<String> {
+        if !stored.starts_with(SEALED_PREFIX) {
+            return Ok(stored);
+        }
+        match self.keystore_id(&self.connection, context)? {
+            Some(kvns_id) => self.open_value(&self.connection, kvns_id, key, &stored),
+            None => Ok(stored),
+        }
+    }
+
+    /// Cipher of an encrypted keystore (None for plaintext ones)
+    fn keystore_cipher(&self, conn: &Connection, kvns_id: i64) -> Result<Option<ChaCha20Poly1305>> {
+        if let Some(cipher) = self.vault.borrow().ciphers.get(&kvns_id) {
+            return Ok(Some(cipher.clone()));
+        }
+        let row = conn.query_row(sql::GET_KEYSTORE_CRYPTO, [kvns_id], |row| {
+            Ok((row.get::<_, Vec<u8>>(0)?, row.get::<_, String>(1)?))
+        }).optional()?;
+        let (salt, check) = match row {
+            Some(row) => row,
+            None => return Ok(None),
+        };
+
+        let cipher = derive_cipher(&self.passphrase()?, &salt)?;
+        if open(&cipher, CHECK_TEXT, &check).is_err() {
+            return Err(BookdbError::Crypto("wrong passphrase".to_string()));
+        }
+        self.vault.borrow_mut().ciphers.insert(kvns_id, cipher.clone());
+        Ok(Some(cipher))
+    }
+
+    /// The passphrase, asking for it once if there is a prompt
+    fn passphrase(&self) -> Result<String> {
+        if let Some(passphrase) = &self.vault.borrow().passphrase {
+            return Ok(passphrase.clone());
+        }
+        let asked = match &self.vault.borrow().prompt {
+            Some(prompt) => prompt()?,
+            None => return Err(BookdbError::PassphraseRequired(format!(
+                "encrypted keystore in base '{}'; set {} to unlock it", self.base_name, PASSPHRASE_ENV
+            ))),
+        };
+        self.vault.borrow_mut().passphrase = Some(asked.clone());
+        Ok(asked)
+    }
+
+    pub(super) fn keystore_id(&self, conn: &Connection, context: &ResolvedContext) -> Result<Option<i64>> {
+        Ok(conn.query_row(
+            sql::GET_KEYSTORE_ID,
+            params![&context.project, &context.workspace, &context.tail],
+            |row| row.get::<_, i64>(0),
+        ).optional()?)
+    }
+
+    /// Map every stored copy of a keystore's values through `rewrite`
+    fn rewrite_keystore_tx(
+        &self,
+        conn: &Connection,
+        kvns_id: i64,
+        context: &ResolvedContext,
+        rewrite: impl Fn(&str, &str) -> Result<String>,
+    ) -> Result<usize> {
+        let values: Vec<(i64, String, Option<String>)> = conn.prepare(sql::LIST_KEYSTORE_VALUES)?
+            .query_map([kvns_id], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+            .collect::<rusqlite::Result<_>>()?;
+        for (var_id, key, value) in &values {
+            if let Some(value) = value {
+                conn.execute(sql::UPDATE_VAR_VALUE, params![rewrite(key, value)?, var_id])?;
+            }
+        }
+
+        let history: Vec<(i64, String, Option<String>, Option<String>)> = conn.prepare(sql::LIST_KEYSTORE_HISTORY)?
+            .query_map([kvns_id], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))?
+            .collect::<rusqlite::Result<_>>()?;
+        for (hist_id, key, old, new) in &history {
+            let old = old.as_deref().map(|value| rewrite(key, value)).transpose()?;
+            let new = new.as_deref().map(|value| rewrite(key, value)).transpose()?;
+            conn.execute(sql::UPDATE_HISTORY_VALUES, params![old, new, hist_id])?;
+        }
+
+        let journal: Vec<(i64, String, String)> = conn.prepare(sql::LIST_KEYSTORE_JOURNAL)?
+            .query_map(params![&context.project, &context.workspace, &context.tail], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+            .collect::<rusqlite::Result<_>>()?;
+        for (entry_id, key, old) in &journal {
+            conn.execute(sql::UPDATE_JOURNAL_VALUE, params![rewrite(key, old)?, entry_id])?;
+        }
+
+        Ok(values.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bookdb::service::ctx::{Anchor, ChainMode};
+    use tempfile::TempDir;
+
+    fn create_test_context() -> ResolvedContext {
+        ResolvedContext {
+            base: "test".to_string(),
+            project: "app".to_string(),
+            workspace: "prod".to_string(),
+            anchor: Anchor::Var,
+            tail: "secrets".to_string(),
+            prefix_mode: ChainMode::Persistent,
+        }
+    }
+
+    fn stored_value(db: &Database, key: &str) -> String {
+        db.connection
+            .query_row("SELECT var_value FROM vars WHERE var_key = ?1", [key], |row| row.get(0))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_seal_and_open() -> Result<()> {
+        let cipher = derive_cipher("fixed passphrase", &[7u8; SALT_LEN])?;
+        let sealed = seal(&cipher, "API_KEY", "s3cret")?;
+        assert!(sealed.starts_with(SEALED_PREFIX));
+        assert_eq!(open(&cipher, "API_KEY", &sealed)?, "s3cret");
+
+        // The key is associated data: a value moved to another key does not open
+        assert!(open(&cipher, "OTHER_KEY", &sealed).is_err());
+        let wrong = derive_cipher("other passphrase", &[7u8; SALT_LEN])?;
+        assert!(open(&wrong, "API_KEY", &sealed).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_encrypted_keystore_is_transparent() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("test.sqlite3");
+        let context = create_test_context();
+        {
+            let db = Database::create_or_open(&path)?;
+            db.set_variable("API_KEY", "before", &context)?;
+            db.unlock("fixed passphrase");
+            assert_eq!(db.encrypt_keystore(&context)?, 1);
+            db.set_variable("DB_PASS", "hunter2", &context)?;
+
+            // Nothing readable at rest, everything readable through the driver
+            assert!(stored_value(&db, "DB_PASS").starts_with(SEALED_PREFIX));
+            assert!(stored_value(&db, "API_KEY").starts_with(SEALED_PREFIX));
+            assert_eq!(db.get_variable("API_KEY", &context)?, Some("before".to_string()));
+            assert_eq!(db.list_variables(&context)?["DB_PASS"], "hunter2");
+            assert_eq!(db.var_history("API_KEY", &context)?[0].new_value.as_deref(), Some("before"));
+        }
+
+        // A fresh handle needs the passphrase again, and the right one
+        let db = Database::create_or_open(&path)?;
+        assert!(db.is_keystore_encrypted(&context)?);
+        assert!(matches!(db.get_variable("DB_PASS", &context), Err(BookdbError::PassphraseRequired(_))));
+        db.unlock("wrong");
+        assert!(matches!(db.get_variable("DB_PASS", &context), Err(BookdbError::Crypto(_))));
+        db.unlock("fixed passphrase");
+        assert_eq!(db.increment_variable("COUNT", 2, &context)?, "2");
+        assert_eq!(db.get_variable("COUNT", &context)?, Some("2".to_string()));
+
+        assert_eq!(db.decrypt_keystore(&context)?, 3);
+        assert_eq!(stored_value(&db, "DB_PASS"), "hunter2");
+        assert!(!db.is_keystore_encrypted(&context)?);
+        Ok(())
+    }
+}
@@ -1,6 +1,6 @@
 // src/db/base.rs - Base-level operations (export/import/migration)
 
-use crate::error::Result;
+use crate::error::{Result, BookdbError};
 use crate::bookdb::service::ctx::ResolvedContext;
 use crate::sql;
 use std::collections::HashMap;
@@ -24,16 +24,20 @@ impl Database {
                 row.get::<_, String>(2)?,  // project
                 row.get::<_, String>(3)?,  // workspace
                 row.get::<_, String>(4)?,  // keystore
+                row.get::<_, i64>(5)?,     // kvns_id
             ))
         })?;
         
         for var_result in var_iter {
-            let (key, value, project, workspace, keystore) = var_result?;
+            let (key, value, project, workspace, keystore, kvns_id) = var_result?;
+            let value = self.open_value(&self.connection, kvns_id, &key, &value)?;
+            let sensitive = self.is_sensitive_in(&self.connection, kvns_id, &key)?;
             items.push(ExportItem {
                 item_type: "variable".to_string(),
                 key,
                 value,
                 context: format!("{}.{}.{}", project, workspace, keystore),
+                sensitive,
             });
         }
         
@@ -55,6 +59,7 @@ impl Database {
                 key,
                 value: content.unwrap_or_default(),
                 context: format!("{}.{}", project, workspace),
+                sensitive: false,
             });
         }
         
@@ -62,7 +67,7 @@ impl Database {
     }
     
     /// Import variables into context
-    pub fn import_variables(&self, variables: HashMap<String, String>, context: &ResolvedContext) ->  Result<(), E> {
+    pub fn import_variables(&self, variables: HashMap<String, String>, context: &ResolvedContext) -> Result<()> {
         self.logger.trace_fn("database", &format!("importing {} variables into context: {}", variables.len(), context));
         
         for (key, value) in variables {
@@ -73,9 +78,8 @@ impl Database {
     }
     
     /// Import documents into context (stub - not implemented)
-    pub fn import_documents(&self, _documents: HashMap<String, String>, _context: &ResolvedContext) ->  Result<(), E> {
-        // TODO: Implement document import
-        todo!("Document import not yet implemented")
+    pub fn import_documents(&self, _documents: HashMap<String, String>, _context: &ResolvedContext) -> Result<()> {
+        Err(BookdbError::NotImplemented("document import".to_string()))
     }
     
     /// Migrate legacy data (placeholder)
@@ -88,27 +92,23 @@ impl Database {
     }
     
     /// Backup base to file (stub - not implemented)
-    pub fn backup_to_file(&self, _path: &std::path::Path) ->  Result<(), E> {
-        // TODO: Implement database backup
-        todo!("Database backup not yet implemented")
+    pub fn backup_to_file(&self, _path: &std::path::Path) -> Result<()> {
+        Err(BookdbError::NotImplemented("database backup".to_string()))
     }
     
     /// Restore base from file (stub - not implemented)
-    pub fn restore_from_file(&self, _path: &std::path::Path) ->  Result<(), E> {
-        // TODO: Implement database restore
-        todo!("Database restore not yet implemented")
+    pub fn restore_from_file(&self, _path: &std::path::Path) -> Result<()> {
+        Err(BookdbError::NotImplemented("database restore".to_string()))
     }
     
     /// Vacuum database (stub - not implemented)
-    pub fn vacuum(&self) ->  Result<(), E> {
-        // TODO: Implement database vacuum/cleanup
-        todo!("Database vacuum not yet implemented")
+    pub fn vacuum(&self) -> Result<()> {
+        Err(BookdbError::NotImplemented("database vacuum".to_string()))
     }
     
     /// Get base statistics (stub - not implemented)
     pub fn get_base_stats(&self) -> Result<BaseStats> {
-        // TODO: Implement base statistics
-        todo!("Base statistics not yet implemented")
+        Err(BookdbError::NotImplemented("base statistics".to_string()))
     }
 }
 
@@ -119,6 +119,8 @@ pub struct ExportItem {
     pub key: String,
     pub value: String,
     pub context: String,
+    /// Mask `value` in previews; never serialized, exports carry real values
+    pub sensitive: bool,
 }
 
 impl serde::Serialize for ExportItem {
@@ -1,10 +1,21 @@
 // src/db/docstore.rs - Document store database operations
 
-use crate::error::Result;
-use crate::bookdb::service::ctx::ResolvedContext;
+use crate::error::{Result, BookdbError};
+use crate::bookdb::service::ctx::{ResolvedContext, ROOT_SEGMENT};
 use crate::sql;
-use rusqlite::{params, Transaction};
+use rusqlite::{params, OptionalExtension, Transaction};
 use super::Database;
+use super::journal::Inverse;
+use super::lock::WriteTarget;
+
+/// One row of a document's segment tree
+#[derive(Debug, Clone)]
+pub struct DocSegmentInfo {
+    pub path: String,
+    pub mime: String,
+    pub size_bytes: u64,
+    pub updated_at: i64,
+}
 
 impl Database {
     /// List documents in a workspace
@@ -13,7 +24,7 @@ impl Database {
         
         let mut stmt = self.connection.prepare(sql::LIST_DOCUMENTS)?;
         let doc_iter = stmt.query_map([&context.project, &context.workspace], |row| {
-            Ok(row.get::<_, String>(0)?)
+            row.get::<_, String>(0)
         })?;
         
         let mut documents = Vec::new();
@@ -30,7 +41,7 @@ impl Database {
         
         let mut stmt = self.connection.prepare(sql::GET_DOCUMENT)?;
         let mut rows = stmt.query_map([&context.project, &context.workspace, key], |row| {
-            Ok(row.get::<_, Option<String>>(0)?)
+            row.get::<_, Option<String>>(0)
         })?;
         
         match rows.next() {
@@ -41,30 +52,75 @@ impl Database {
     }
     
     /// Set document content (upsert operation)
-    pub fn set_document(&self, key: &str, content: &str, context: &ResolvedContext) ->  Result<(), E> {
+    pub fn set_document(&self, key: &str, content: &str, context: &ResolvedContext) -> Result<()> {
         self.logger.trace_fn("database", &format!("setting document {} in context: {}", key, context));
         
-        let tx = self.connection.transaction()?;
+        let tx = self.connection.unchecked_transaction()?;
         
         // Ensure doc store exists
         let ds_id = self.ensure_doc_store_exists(&tx, context)?;
+        self.check_writable(&tx, WriteTarget::Docstore(ds_id), context)?;
+        let old = self.document_tx(&tx, key, context)?;
         
         // Upsert the document
         tx.execute(sql::SET_DOCUMENT, params![key, content, ds_id])?;
+        self.audit_tx(&tx, "setd", doc_chain(context), Some(key), old.as_deref().map(str::as_bytes), Some(content.as_bytes()))?;
         
         tx.commit()?;
         Ok(())
     }
     
     /// Delete document by key and context
-    pub fn delete_document(&self, key: &str, context: &ResolvedContext) -> Result<bool, E> {
+    pub fn delete_document(&self, key: &str, context: &ResolvedContext) -> Result<bool> {
         self.logger.trace_fn("database", &format!("deleting document {} in context: {}", key, context));
         
-        let changes = self.connection.execute(
+        let tx = self.connection.unchecked_transaction()?;
+        let deleted = self.delete_document_tx(&tx, key, context)?;
+        tx.commit()?;
+        Ok(deleted)
+    }
+    
+    /// `delete_document` inside a caller's transaction
+    pub(super) fn delete_document_tx(&self, tx: &Transaction, key: &str, context: &ResolvedContext) -> Result<bool> {
+        let target = self.docstore_id(tx, context)?.map_or(WriteTarget::Base, WriteTarget::Docstore);
+        self.check_writable(tx, target, context)?;
+        let old = self.document_tx(tx, key, context)?;
+        
+        // Journal every segment, so undo rebuilds the document as it was
+        let doc_id = tx.query_row(
+            sql::GET_DOC_ID,
+            params![&context.project, &context.workspace, key],
+            |row| row.get::<_, i64>(0)
+        ).optional()?;
+        let mut segments = Vec::new();
+        if let Some(doc_id) = doc_id {
+            let mut stmt = tx.prepare(sql::LIST_DOC_SEGMENTS)?;
+            let paths = stmt.query_map(params![&context.project, &context.workspace, key], |row| row.get::<_, String>(0))?;
+            for path in paths {
+                let path = path?;
+                let (content, mime) = tx.query_row(
+                    sql::GET_DOC_SEGMENT_RAW,
+                    params![doc_id, &path],
+                    |row| Ok((row.get::<_, Vec<u8>>(0)?, row.get::<_, String>(1)?))
+                )?;
+                segments.push((path, content, mime));
+            }
+        }
+        
+        let changes = tx.execute(
             sql::DELETE_DOCUMENT,
             params![&context.project, &context.workspace, key]
         )?;
-        
+        if changes > 0 {
+            self.audit_tx(tx, "deld", doc_chain(context), Some(key), old.as_deref().map(str::as_bytes), None)?;
+            if !segments.is_empty() {
+                self.journal_all_tx(tx, "deld", context, segments.iter().map(|(path, content, mime)| Inverse::Segment {
+                    doc_key: key,
+                    path,
+                    old: Some((content.as_slice(), mime.as_str())),
+                }))?;
+            }
+        }
         Ok(changes > 0)
     }
     
@@ -75,63 +131,135 @@ impl Database {
         let count: i64 = self.connection.query_row(
             sql::COUNT_DOCUMENTS,
             params![&context.project, &context.workspace],
-            |row| Ok(row.get(0)?)
+            |row| row.get(0)
         )?;
         
         Ok(count as usize)
     }
     
-    /// Get document segment content
-    pub fn get_doc_segment(&self, doc_key: &str, path: &str) -> Result<Option<Vec<u8>>> {
-        self.logger.trace_fn("database", &format!("getting doc segment {}.{}", doc_key, path));
+    /// Get document segment content and mime type
+    ///
+    /// Documents written before segments existed only have `doc_content`; that
+    /// is served as the `_root` segment.
+    pub fn get_doc_segment(&self, doc_key: &str, path: &str, context: &ResolvedContext) -> Result<Option<(Vec<u8>, String)>> {
+        self.logger.trace_fn("database", &format!("getting doc segment {}/{} in context: {}", doc_key, path, context));
         
         let mut stmt = self.connection.prepare(sql::GET_DOC_SEGMENT)?;
-        let mut rows = stmt.query_map([doc_key, path], |row| {
-            Ok(row.get::<_, Vec<u8>>(0)?)
-        })?;
+        let mut rows = stmt.query_map(
+            params![&context.project, &context.workspace, doc_key, path],
+            |row| Ok((row.get::<_, Vec<u8>>(0)?, row.get::<_, String>(1)?))
+        )?;
         
         match rows.next() {
-            Some(Ok(content)) => Ok(Some(content)),
+            Some(Ok(segment)) => Ok(Some(segment)),
             Some(Err(e)) => Err(e.into()),
+            None if path == ROOT_SEGMENT => Ok(self.get_document(doc_key, context)?
+                .map(|content| (content.into_bytes(), "text/plain".to_string()))),
             None => Ok(None),
         }
     }
     
-    /// Set document segment content
-    pub fn set_doc_segment(&self, doc_key: &str, path: &str, mime: &str, content: &[u8], ds_id: i64) ->  Result<(), E> {
-        self.logger.trace_fn("database", &format!("setting doc segment {}.{}", doc_key, path));
+    /// Set document segment content, creating the doc store and document as needed
+    pub fn set_doc_segment(&self, doc_key: &str, path: &str, mime: &str, content: &[u8], context: &ResolvedContext) -> Result<()> {
+        self.logger.trace_fn("database", &format!("setting doc segment {}/{} in context: {}", doc_key, path, context));
         
-        let tx = self.connection.transaction()?;
+        let tx = self.connection.unchecked_transaction()?;
+        self.set_doc_segment_tx(&tx, doc_key, path, mime, content, context)?;
+        tx.commit()?;
+        Ok(())
+    }
+    
+    /// `set_doc_segment` inside a caller's transaction
+    pub(super) fn set_doc_segment_tx(&self, tx: &Transaction, doc_key: &str, path: &str, mime: &str, content: &[u8], context: &ResolvedContext) -> Result<()> {
+        let ds_id = self.ensure_doc_store_exists(tx, context)?;
+        self.check_writable(tx, WriteTarget::Docstore(ds_id), context)?;
+        
+        // Capture what the write replaces for the undo journal
+        let existing_doc = tx.query_row(
+            sql::GET_DOC_ID,
+            params![&context.project, &context.workspace, doc_key],
+            |row| row.get::<_, i64>(0)
+        ).optional()?;
+        let old_segment = match existing_doc {
+            Some(doc_id) => tx.query_row(
+                sql::GET_DOC_SEGMENT_RAW,
+                params![doc_id, path],
+                |row| Ok((row.get::<_, Vec<u8>>(0)?, row.get::<_, String>(1)?))
+            ).optional()?,
+            None => None,
+        };
+        
+        let doc_id = self.ensure_document_tx(tx, doc_key, context)?;
+        
+        // Set the segment
+        tx.execute(sql::SET_DOC_SEGMENT, params![path, mime, content, doc_id])?;
+        
+        let old = old_segment.as_ref().map(|(content, mime)| (content.as_slice(), mime.as_str()));
+        let new_document = existing_doc.is_none().then_some(Inverse::NewDocument { doc_key });
+        self.journal_all_tx(tx, "setd", context, new_document.into_iter().chain([Inverse::Segment { doc_key, path, old }]))?;
+        let segment_key = format!("{}/{}", doc_key, path);
+        self.audit_tx(tx, "setd", doc_chain(context), Some(segment_key.as_str()), old.map(|(content, _)| content), Some(content))?;
+        Ok(())
+    }
+    
+    /// Ensure the document (and its doc store) exists, return its doc ID
+    pub(super) fn ensure_document_tx(&self, tx: &Transaction, doc_key: &str, context: &ResolvedContext) -> Result<i64> {
+        let ds_id = self.ensure_doc_store_exists(tx, context)?;
         
-        // Ensure document exists
         tx.execute(
             "INSERT OR IGNORE INTO docs (doc_key, ds_id_fk, doc_updated) VALUES (?1, ?2, strftime('%s','now'))",
             params![doc_key, ds_id]
         )?;
         
-        // Get document ID
         let doc_id: i64 = tx.query_row(
             "SELECT doc_id FROM docs WHERE doc_key = ?1 AND ds_id_fk = ?2",
             params![doc_key, ds_id],
-            |row| Ok(row.get(0)?)
+            |row| row.get(0)
         )?;
+        Ok(doc_id)
+    }
+    
+    /// Document content as seen inside `tx`, for auditing what a write replaces
+    pub(super) fn document_tx(&self, tx: &Transaction, key: &str, context: &ResolvedContext) -> Result<Option<String>> {
+        Ok(tx.query_row(
+            sql::GET_DOCUMENT,
+            params![&context.project, &context.workspace, key],
+            |row| row.get::<_, Option<String>>(0)
+        ).optional()?.flatten())
+    }
+    
+    /// List the segments of a document, ordered by path
+    pub fn list_doc_segments(&self, doc_key: &str, context: &ResolvedContext) -> Result<Vec<DocSegmentInfo>> {
+        self.logger.trace_fn("database", &format!("listing segments of {} in context: {}", doc_key, context));
         
-        // Set the segment
-        tx.execute(sql::SET_DOC_SEGMENT, params![path, mime, content, doc_id])?;
+        let mut stmt = self.connection.prepare(sql::LIST_DOC_SEGMENTS)?;
+        let segment_iter = stmt.query_map(
+            params![&context.project, &context.workspace, doc_key],
+            |row| Ok(DocSegmentInfo {
+                path: row.get(0)?,
+                mime: row.get(1)?,
+                size_bytes: row.get::<_, i64>(2)? as u64,
+                updated_at: row.get(3)?,
+            })
+        )?;
         
-        tx.commit()?;
-        Ok(())
+        let mut segments = Vec::new();
+        for segment in segment_iter {
+            segments.push(segment?);
+        }
+        
+        Ok(segments)
     }
     
     /// Ensure doc store exists, return doc store ID
-    fn ensure_doc_store_exists(&self, tx: &Transaction, context: &ResolvedContext) -> Result<i64> {
+    pub(super) fn ensure_doc_store_exists(&self, tx: &Transaction, context: &ResolvedContext) -> Result<i64> {
         // First ensure project exists
         let project_id = self.ensure_project_exists_tx(tx, &context.project)?;
         
         // Try to get existing doc store
-        let mut stmt = tx.prepare(sql::GET_DOC_STORE_ID)?;
+        let mut stmt = tx.prepare(sql::GET_DOC_STORE)?;
         let mut rows = stmt.query_map([&context.project, &context.workspace], |row| {
-            Ok(row.get::<_, i64>(0)?)
+            row.get::<_, i64>(0)
         })?;
         
         match rows.next() {
@@ -145,21 +273,65 @@ impl Database {
         }
     }
     
-    /// Create a new document store (stub - implicit through first document)
-    pub fn create_docstore(&self, _project: &str, _workspace: &str) ->  Result<(), E> {
-        // TODO: Implement explicit docstore creation if needed
-        todo!("Explicit docstore creation not yet implemented")
+    /// Create the document store of a workspace; fails if it already exists
+    pub fn create_docstore(&self, project: &str, workspace: &str) -> Result<()> {
+        self.logger.trace_fn("database", &format!("creating docstore in {}.{}", project, workspace));
+        
+        if !self.list_docstores(project, workspace)?.is_empty() {
+            return Err(BookdbError::Database(format!(
+                "Docstore already exists in {}.{}", project, workspace
+            )));
+        }
+        
+        let tx = self.connection.unchecked_transaction()?;
+        self.check_writable(&tx, WriteTarget::Base, format!("{}.{}.doc", project, workspace))?;
+        let project_id = self.ensure_project_exists_tx(&tx, project)?;
+        tx.execute(sql::ENSURE_DOC_STORE, params![project_id, workspace])?;
+        self.audit_tx(&tx, "mkdocstore", format!("{}.{}.doc", project, workspace), None, None, None)?;
+        tx.commit()?;
+        Ok(())
     }
     
-    /// Delete a document store (stub - not implemented)
-    pub fn delete_docstore(&self, _project: &str, _workspace: &str) ->  Result<(), E> {
-        // TODO: Implement docstore deletion with cascade
-        todo!("Docstore deletion not yet implemented")
+    /// Delete a workspace's document store with its documents and segments
+    ///
+    /// Returns false if the workspace had no document store.
+    pub fn delete_docstore(&self, project: &str, workspace: &str) -> Result<bool> {
+        self.logger.trace_fn("database", &format!("deleting docstore in {}.{}", project, workspace));
+        
+        let tx = self.connection.unchecked_transaction()?;
+        let ds_id = tx.query_row(sql::GET_DOC_STORE, params![project, workspace], |row| row.get::<_, i64>(0))
+            .optional()?;
+        let target = ds_id.map_or(WriteTarget::Base, WriteTarget::Docstore);
+        self.check_writable(&tx, target, format!("{}.{}.doc", project, workspace))?;
+        
+        let changes = tx.execute(sql::DELETE_DOC_STORE, params![project, workspace])?;
+        if changes > 0 {
+            self.audit_tx(&tx, "rmdocstore", format!("{}.{}.doc", project, workspace), None, None, None)?;
+            self.journal_irreversible_tx(&tx, "del docstore", &format!("{}@{}.{}.doc", self.base_name, project, workspace))?;
+        }
+        tx.commit()?;
+        Ok(changes > 0)
     }
     
-    /// List document stores in a workspace (stub - not implemented)
-    pub fn list_docstores(&self, _project: &str, _workspace: &str) -> Result<Vec<String>> {
-        // TODO: Implement docstore listing if multiple docstores per workspace are supported
-        todo!("Docstore listing not yet implemented")
+    /// List document stores in a workspace
+    pub fn list_docstores(&self, project: &str, workspace: &str) -> Result<Vec<String>> {
+        self.logger.trace_fn("database", &format!("listing docstores in {}.{}", project, workspace));
+        
+        let mut stmt = self.connection.prepare(sql::LIST_DOC_STORES)?;
+        let docstore_iter = stmt.query_map([project, workspace], |row| {
+            row.get::<_, String>(0)
+        })?;
+        
+        let mut docstores = Vec::new();
+        for docstore in docstore_iter {
+            docstores.push(docstore?);
+        }
+        
+        Ok(docstores)
     }
 }
+
+/// Audit chain of a workspace's doc store
+fn doc_chain(context: &ResolvedContext) -> String {
+    format!("{}.{}.doc", context.project, context.workspace)
+}
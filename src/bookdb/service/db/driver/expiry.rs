@@ -0,0 +1,117 @@
+// src/db/expiry.rs - Variable expiry (`setv --ttl`) and `purge`
+//
+// Expired rows stay in `vars` until purged, but every read filters them out
+// (vars.var_expires <= now), so they behave as deleted from the moment they expire.
+
+use crate::error::Result;
+use crate::bookdb::service::ctx::ResolvedContext;
+use crate::sql;
+use std::collections::HashMap;
+use std::time::Duration;
+use super::Database;
+use super::lock::WriteTarget;
+
+impl Database {
+    /// Remaining lifetime of every live variable that has an expiry
+    pub fn list_variable_expiries(&self, context: &ResolvedContext) -> Result<HashMap<String, Duration>> {
+        self.logger.trace_fn("database", &format!("listing variable expiries in context: {}", context));
+
+        let mut stmt = self.connection.prepare(sql::LIST_VAR_EXPIRIES)?;
+        let rows = stmt.query_map([&context.project, &context.workspace, &context.tail], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+        })?;
+
+        let mut expiries = HashMap::new();
+        for row in rows {
+            let (key, remaining) = row?;
+            expiries.insert(key, Duration::from_secs(remaining.max(0) as u64));
+        }
+        Ok(expiries)
+    }
+
+    /// Delete every expired variable in the base; returns how many went
+    ///
+    /// Each deletion is recorded in var_history, so `history` still shows the
+    /// last value a token had.
+    pub fn purge_expired(&self, dry_run: bool) -> Result<usize> {
+        self.logger.trace_fn("database", &format!("purging expired variables (dry run: {})", dry_run));
+
+        let tx = self.connection.unchecked_transaction()?;
+        // Expired values are already gone to readers, so only a read-only base stops a purge
+        self.check_writable(&tx, WriteTarget::Base, &self.base_name)?;
+        let rev = self.last_revision(&tx)?;
+        let purged = tx.execute(sql::RECORD_PURGE_HISTORY, [])?;
+        if dry_run {
+            // The history insert counted the rows; dropping tx undoes it
+            return Ok(purged);
+        }
+        self.audit_history_since_tx(&tx, rev, "purge")?;
+        tx.execute(sql::PURGE_EXPIRED_VARS, [])?;
+        tx.commit()?;
+        Ok(purged)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bookdb::service::ctx::{Anchor, ChainMode};
+    use crate::bookdb::service::db::driver::SetOptions;
+    use tempfile::TempDir;
+
+    fn create_test_context() -> ResolvedContext {
+        ResolvedContext {
+            base: "test".to_string(),
+            project: "app".to_string(),
+            workspace: "ci".to_string(),
+            anchor: Anchor::Var,
+            tail: "tokens".to_string(),
+            prefix_mode: ChainMode::Persistent,
+        }
+    }
+
+    #[test]
+    fn test_expired_values_are_hidden_and_purged() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::create_or_open(&temp_dir.path().join("test.sqlite3"))?;
+        let context = create_test_context();
+        let ttl = |secs: u64| SetOptions { ttl: Some(Duration::from_secs(secs)), ..Default::default() };
+
+        db.set_variable_with("TOKEN", "abc", &ttl(3600), &context)?;
+        db.set_variable_with("STALE", "old", &ttl(3600), &context)?;
+        db.set_variable("KEEP", "yes", &context)?;
+        assert!(db.list_variable_expiries(&context)?["TOKEN"] > Duration::from_secs(3500));
+        assert!(!db.list_variable_expiries(&context)?.contains_key("KEEP"));
+
+        // Age STALE past its expiry
+        db.connection.execute("UPDATE vars SET var_expires = 1 WHERE var_key = 'STALE'", [])?;
+        assert_eq!(db.get_variable("STALE", &context)?, None);
+        assert!(!db.list_variables(&context)?.contains_key("STALE"));
+        assert_eq!(db.count_variables(&context)?, 2);
+
+        assert_eq!(db.purge_expired(true)?, 1);
+        assert_eq!(db.purge_expired(false)?, 1);
+        assert_eq!(db.purge_expired(false)?, 0);
+        assert_eq!(db.get_variable("TOKEN", &context)?, Some("abc".to_string()));
+
+        // A plain set makes the value permanent again
+        db.set_variable("TOKEN", "def", &context)?;
+        assert!(db.list_variable_expiries(&context)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_expired_row_comes_back_fresh() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::create_or_open(&temp_dir.path().join("test.sqlite3"))?;
+        let context = create_test_context();
+
+        db.set_variable_with("HITS", "5", &SetOptions { ttl: Some(Duration::from_secs(60)), ..Default::default() }, &context)?;
+        db.connection.execute("UPDATE vars SET var_expires = 1 WHERE var_key = 'HITS'", [])?;
+
+        // An expired counter starts over and loses its expiry
+        assert_eq!(db.increment_variable("HITS", 1, &context)?, "1");
+        assert!(db.list_variable_expiries(&context)?.is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,226 @@
+// src/db/find.rs - `find`: search variables, documents and segments in a base
+//
+// Keys are matched exactly, by glob or by regex. Values are only searched when
+// asked for (`--values`), since they are often secrets.
+
+use crate::error::{Result, BookdbError};
+use crate::bookdb::service::ctx::glob_match;
+use crate::sql;
+use regex::Regex;
+use serde::Serialize;
+use super::Database;
+
+/// How `find` matches its pattern
+#[derive(Debug, Clone)]
+pub enum FindMatcher {
+    /// Key equals the pattern
+    KeyExact(String),
+    /// Key matches a glob (`*_KEY`, `DB?`)
+    KeyGlob(String),
+    /// Key matches a regex
+    KeyRegex(Regex),
+    /// Value contains the pattern
+    ValueSubstring(String),
+    /// Value matches a regex
+    ValueRegex(Regex),
+}
+
+impl FindMatcher {
+    /// Build a matcher from the `find` flags
+    pub fn from_flags(pattern: &str, glob: bool, regex: bool, values: bool) -> Result<Self> {
+        if pattern.is_empty() {
+            return Err(BookdbError::Argument("Search pattern cannot be empty".to_string()));
+        }
+        let compile = |p: &str| Regex::new(p)
+            .map_err(|e| BookdbError::Argument(format!("Invalid regex '{}': {}", p, e)));
+
+        match (glob, regex, values) {
+            (true, true, _) => Err(BookdbError::Argument("--glob and --regex cannot be combined".to_string())),
+            (true, false, true) => Err(BookdbError::Argument("--glob only applies to keys".to_string())),
+            (true, false, false) => Ok(FindMatcher::KeyGlob(pattern.to_string())),
+            (false, true, false) => Ok(FindMatcher::KeyRegex(compile(pattern)?)),
+            (false, true, true) => Ok(FindMatcher::ValueRegex(compile(pattern)?)),
+            (false, false, false) => Ok(FindMatcher::KeyExact(pattern.to_string())),
+            (false, false, true) => Ok(FindMatcher::ValueSubstring(pattern.to_string())),
+        }
+    }
+
+    /// Whether hits carry the matched value
+    pub fn searches_values(&self) -> bool {
+        matches!(self, FindMatcher::ValueSubstring(_) | FindMatcher::ValueRegex(_))
+    }
+
+    fn matches(&self, key: &str, value: &str) -> bool {
+        match self {
+            FindMatcher::KeyExact(pattern) => key == pattern,
+            FindMatcher::KeyGlob(pattern) => glob_match(pattern, key),
+            FindMatcher::KeyRegex(re) => re.is_match(key),
+            FindMatcher::ValueSubstring(pattern) => value.contains(pattern.as_str()),
+            FindMatcher::ValueRegex(re) => re.is_match(value),
+        }
+    }
+}
+
+/// One `find` result, tagged with the fully-qualified chain it lives under
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FindHit {
+    /// `variable`, `document` or `segment`
+    pub kind: &'static str,
+    /// `base@project.workspace.var.keystore` or `base@project.workspace.doc.doc_key[/segment]`
+    pub chain: String,
+    pub key: String,
+    /// Only filled in for value searches
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+}
+
+impl Database {
+    /// Search every variable, document and document segment in the base
+    pub fn find(&self, matcher: &FindMatcher) -> Result<Vec<FindHit>> {
+        self.logger.trace_fn("database", &format!("find: {:?}", matcher));
+
+        let with_value = |value: &str| matcher.searches_values().then(|| value.to_string());
+        let mut hits = Vec::new();
+
+        // Variables (export queries with no filters list everything in order)
+        let none: Option<&str> = None;
+        let mut stmt = self.connection.prepare(sql::EXPORT_VARIABLES)?;
+        let rows = stmt.query_map([none, none, none, none], |row| {
+            Ok((
+                row.get::<_, String>(0)?,                           // var_key
+                row.get::<_, Option<String>>(1)?.unwrap_or_default(), // var_value
+                row.get::<_, String>(2)?,                           // project
+                row.get::<_, String>(3)?,                           // workspace
+                row.get::<_, String>(4)?,                           // keystore
+                row.get::<_, i64>(5)?,                              // kvns_id
+            ))
+        })?;
+        for row in rows {
+            let (key, value, project, workspace, keystore, kvns_id) = row?;
+            // Key-only searches never need the passphrase
+            let value = if matcher.searches_values() { self.open_value(&self.connection, kvns_id, &key, &value)? } else { value };
+            if matcher.matches(&key, &value) {
+                hits.push(FindHit {
+                    kind: "variable",
+                    chain: format!("{}@{}.{}.var.{}", self.base_name, project, workspace, keystore),
+                    value: with_value(&value),
+                    key,
+                });
+            }
+        }
+
+        // Documents
+        let mut stmt = self.connection.prepare(sql::EXPORT_DOCUMENTS)?;
+        let rows = stmt.query_map([none, none, none], |row| {
+            Ok((
+                row.get::<_, String>(0)?,                           // doc_key
+                row.get::<_, Option<String>>(1)?.unwrap_or_default(), // doc_content
+                row.get::<_, String>(2)?,                           // project
+                row.get::<_, String>(3)?,                           // workspace
+            ))
+        })?;
+        for row in rows {
+            let (key, content, project, workspace) = row?;
+            if matcher.matches(&key, &content) {
+                hits.push(FindHit {
+                    kind: "document",
+                    chain: format!("{}@{}.{}.doc.{}", self.base_name, project, workspace, key),
+                    value: with_value(&content),
+                    key,
+                });
+            }
+        }
+
+        // Document segments, keyed as doc_key/segment/path
+        let mut stmt = self.connection.prepare(sql::LIST_ALL_DOC_SEGMENTS)?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,  // doc_key
+                row.get::<_, String>(1)?,  // path
+                row.get::<_, Vec<u8>>(2)?, // content
+                row.get::<_, String>(3)?,  // project
+                row.get::<_, String>(4)?,  // workspace
+            ))
+        })?;
+        for row in rows {
+            let (doc_key, path, content, project, workspace) = row?;
+            let key = format!("{}/{}", doc_key, path);
+            let content = String::from_utf8_lossy(&content);
+            if matcher.matches(&key, &content) {
+                hits.push(FindHit {
+                    kind: "segment",
+                    chain: format!("{}@{}.{}.doc.{}", self.base_name, project, workspace, key),
+                    value: with_value(&content),
+                    key,
+                });
+            }
+        }
+
+        self.logger.trace_fn("database", &format!("find: {} hits", hits.len()));
+        Ok(hits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bookdb::service::ctx::{Anchor, ChainMode, ResolvedContext};
+    use tempfile::TempDir;
+
+    fn create_test_context(workspace: &str, keystore: &str) -> ResolvedContext {
+        ResolvedContext {
+            base: "test".to_string(),
+            project: "myapp".to_string(),
+            workspace: workspace.to_string(),
+            anchor: Anchor::Var,
+            tail: keystore.to_string(),
+            prefix_mode: ChainMode::Persistent,
+        }
+    }
+
+    fn create_test_db() -> (Database, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::create_or_open(&temp_dir.path().join("test.sqlite3")).unwrap();
+        db.set_variable("DB_PASSWORD", "hunter2", &create_test_context("prod", "secrets")).unwrap();
+        db.set_variable("DB_PASSWORD", "staging-pw", &create_test_context("staging", "secrets")).unwrap();
+        db.set_variable("API_KEY", "abc123", &create_test_context("prod", "config")).unwrap();
+        (db, temp_dir)
+    }
+
+    #[test]
+    fn test_find_key_modes() -> Result<()> {
+        let (db, _temp) = create_test_db();
+
+        let hits = db.find(&FindMatcher::from_flags("DB_PASSWORD", false, false, false)?)?;
+        let chains: Vec<&str> = hits.iter().map(|h| h.chain.as_str()).collect();
+        assert_eq!(chains, vec!["test@myapp.prod.var.secrets", "test@myapp.staging.var.secrets"]);
+        assert!(hits.iter().all(|h| h.value.is_none()));
+
+        assert_eq!(db.find(&FindMatcher::from_flags("*_KEY", true, false, false)?)?.len(), 1);
+        assert_eq!(db.find(&FindMatcher::from_flags("^(DB|API)_", false, true, false)?)?.len(), 3);
+        assert!(db.find(&FindMatcher::from_flags("DB_", false, false, false)?)?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_value_modes() -> Result<()> {
+        let (db, _temp) = create_test_db();
+
+        let hits = db.find(&FindMatcher::from_flags("hunter", false, false, true)?)?;
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].value.as_deref(), Some("hunter2"));
+
+        assert_eq!(db.find(&FindMatcher::from_flags(r"\d{3}$", false, true, true)?)?.len(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_flag_errors() {
+        assert!(FindMatcher::from_flags("x", true, true, false).is_err());
+        assert!(FindMatcher::from_flags("x", true, false, true).is_err());
+        assert!(FindMatcher::from_flags("(", false, true, false).is_err());
+        assert!(FindMatcher::from_flags("", false, false, false).is_err());
+    }
+}
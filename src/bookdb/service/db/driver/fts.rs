@@ -0,0 +1,133 @@
+// src/db/fts.rs - Full-text search over document segments (`grepd`)
+//
+// `doc_segments_fts` indexes text/* segments; triggers in V5__create_doc_fts.sql
+// keep it in sync with `doc_segments`, so writers need no extra work.
+
+use crate::error::Result;
+use crate::bookdb::service::ctx::{glob_match, Anchor, ResolvedContext};
+use crate::sql;
+use rusqlite::params;
+use serde::Serialize;
+use super::Database;
+
+/// A ranked `grepd` match
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DocSearchHit {
+    /// `base@project.workspace.doc.doc_key/segment`
+    pub chain: String,
+    /// Matching text with the hit terms wrapped in the highlight markers
+    pub snippet: String,
+    /// bm25 score; lower is a better match
+    pub rank: f64,
+}
+
+impl Database {
+    /// Search text segments with an FTS5 query, best match first
+    ///
+    /// With a scope, only documents under its project and workspace (and doc
+    /// key, for doc chains) are returned; each part may be a glob.
+    pub fn search_documents(
+        &self,
+        query: &str,
+        scope: Option<&ResolvedContext>,
+        highlight: (&str, &str),
+        limit: usize,
+    ) -> Result<Vec<DocSearchHit>> {
+        self.logger.trace_fn("database", &format!("searching documents for '{}'", query));
+
+        let mut stmt = self.connection.prepare(sql::SEARCH_DOC_SEGMENTS)?;
+        let rows = stmt.query_map(params![query, highlight.0, highlight.1], |row| {
+            Ok((
+                row.get::<_, String>(0)?,  // doc_key
+                row.get::<_, String>(1)?,  // path
+                row.get::<_, String>(2)?,  // project
+                row.get::<_, String>(3)?,  // workspace
+                row.get::<_, String>(4)?,  // snippet
+                row.get::<_, f64>(5)?,     // rank
+            ))
+        })?;
+
+        let mut hits = Vec::new();
+        for row in rows {
+            let (doc_key, path, project, workspace, snippet, rank) = row?;
+            if let Some(scope) = scope {
+                let in_scope = glob_match(&scope.project, &project)
+                    && glob_match(&scope.workspace, &workspace)
+                    && (scope.anchor == Anchor::Var || glob_match(scope.doc_key(), &doc_key));
+                if !in_scope {
+                    continue;
+                }
+            }
+            hits.push(DocSearchHit {
+                chain: format!("{}@{}.{}.doc.{}/{}", self.base_name, project, workspace, doc_key, path),
+                snippet,
+                rank,
+            });
+            if hits.len() == limit {
+                break;
+            }
+        }
+
+        Ok(hits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bookdb::service::ctx::ChainMode;
+    use tempfile::TempDir;
+
+    fn create_test_context(workspace: &str, doc_key: &str) -> ResolvedContext {
+        ResolvedContext {
+            base: "test".to_string(),
+            project: "myapp".to_string(),
+            workspace: workspace.to_string(),
+            anchor: Anchor::Doc,
+            tail: doc_key.to_string(),
+            prefix_mode: ChainMode::Persistent,
+        }
+    }
+
+    fn create_test_db() -> (Database, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::create_or_open(&temp_dir.path().join("test.sqlite3")).unwrap();
+        (db, temp_dir)
+    }
+
+    #[test]
+    fn test_search_ranks_and_highlights() -> Result<()> {
+        let (db, _temp) = create_test_db();
+        let readme = create_test_context("prod", "README");
+        db.set_doc_segment("README", "_root", "text/plain", b"rotate the database password", &readme)?;
+        db.set_doc_segment("README", "ops/runbook", "text/markdown", b"password password password", &readme)?;
+        db.set_doc_segment("README", "blob", "application/octet-stream", b"password", &readme)?;
+
+        let hits = db.search_documents("password", None, ("[", "]"), 10)?;
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].chain, "test@myapp.prod.doc.README/ops/runbook");
+        assert!(hits[1].snippet.contains("[password]"));
+
+        // Updates replace the indexed text
+        db.set_doc_segment("README", "_root", "text/plain", b"nothing to see", &readme)?;
+        assert_eq!(db.search_documents("password", None, ("[", "]"), 10)?.len(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_search_scope_and_limit() -> Result<()> {
+        let (db, _temp) = create_test_db();
+        db.set_doc_segment("README", "_root", "text/plain", b"deploy notes", &create_test_context("prod", "README"))?;
+        db.set_doc_segment("NOTES", "_root", "text/plain", b"deploy steps", &create_test_context("staging", "NOTES"))?;
+
+        let scope = create_test_context("staging", "*");
+        let hits = db.search_documents("deploy", Some(&scope), ("", ""), 10)?;
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].chain, "test@myapp.staging.doc.NOTES/_root");
+
+        assert_eq!(db.search_documents("deploy", None, ("", ""), 1)?.len(), 1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,263 @@
+// src/db/history.rs - Variable history, point-in-time reads and revert
+//
+// Every set/delete/inc of a variable appends a row to var_history in the same
+// transaction as the write. A row's hist_id is its revision; revisions are
+// shared by all variables of a base and only ever grow.
+
+use crate::error::{Result, BookdbError};
+use crate::bookdb::service::ctx::ResolvedContext;
+use crate::sql;
+use rusqlite::{params, OptionalExtension, Transaction};
+use super::Database;
+use super::journal::Inverse;
+use super::lock::WriteTarget;
+
+/// What a history row records
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VarOp {
+    Set,
+    Delete,
+    Inc,
+    Revert,
+}
+
+impl VarOp {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VarOp::Set => "set",
+            VarOp::Delete => "delete",
+            VarOp::Inc => "inc",
+            VarOp::Revert => "revert",
+        }
+    }
+}
+
+/// One recorded change; `new_value` is None when the change deleted the key
+#[derive(Debug, Clone, PartialEq)]
+pub struct VarChange {
+    pub rev: i64,
+    pub op: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    /// Unix seconds
+    pub at: i64,
+    /// `at` as UTC, `2025-06-01T12:00:00Z`
+    pub when: String,
+}
+
+/// Where a point-in-time read looks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryPoint {
+    Rev(i64),
+    /// Unix seconds
+    Time(i64),
+}
+
+impl HistoryPoint {
+    /// `12` is revision 12, `@1700000000` a unix time, `2025-06-01[THH:MM[:SS]]` a UTC time
+    pub fn parse(raw: &str) -> Result<Self> {
+        let raw = raw.trim();
+        let invalid = || BookdbError::Argument(format!(
+            "Invalid point in history '{}': expected a revision, @<unix-seconds> or YYYY-MM-DD[THH:MM[:SS]]", raw
+        ));
+
+        if let Some(secs) = raw.strip_prefix('@') {
+            return secs.parse().map(HistoryPoint::Time).map_err(|_| invalid());
+        }
+        if let Ok(rev) = raw.parse::<i64>() {
+            return if rev > 0 { Ok(HistoryPoint::Rev(rev)) } else { Err(invalid()) };
+        }
+        parse_utc(raw).map(HistoryPoint::Time).ok_or_else(invalid)
+    }
+}
+
+/// `YYYY-MM-DD`, optionally followed by `THH:MM[:SS]` (or a space) and a `Z`, as unix seconds
+fn parse_utc(raw: &str) -> Option<i64> {
+    let (date, time) = match raw.split_once(['T', ' ']) {
+        Some((date, time)) => (date, Some(time.trim_end_matches('Z'))),
+        None => (raw, None),
+    };
+
+    let parts: Vec<i64> = date.split('-').map(|p| p.parse().ok()).collect::<Option<_>>()?;
+    let (year, month, day) = match parts.as_slice() {
+        [y, m, d] if (1..=12).contains(m) && (1..=31).contains(d) => (*y, *m, *d),
+        _ => return None,
+    };
+
+    let seconds = match time {
+        None => 0,
+        Some(time) => {
+            let parts: Vec<i64> = time.split(':').map(|p| p.parse().ok()).collect::<Option<_>>()?;
+            match parts.as_slice() {
+                [h, m] if *h < 24 && *m < 60 => h * 3600 + m * 60,
+                [h, m, s] if *h < 24 && *m < 60 && *s < 60 => h * 3600 + m * 60 + s,
+                _ => return None,
+            }
+        }
+    };
+
+    // Days since 1970-01-01 in the proleptic Gregorian calendar
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    Some((era * 146_097 + day_of_era - 719_468) * 86_400 + seconds)
+}
+
+impl Database {
+    /// Every recorded change of a variable, oldest first
+    pub fn var_history(&self, key: &str, context: &ResolvedContext) -> Result<Vec<VarChange>> {
+        self.logger.trace_fn("database", &format!("listing history of {} in context: {}", key, context));
+
+        let mut stmt = self.connection.prepare(sql::LIST_VAR_HISTORY)?;
+        let rows = stmt.query_map(params![&context.project, &context.workspace, &context.tail, key], |row| {
+            Ok(VarChange {
+                rev: row.get(0)?,
+                op: row.get(1)?,
+                old_value: row.get(2)?,
+                new_value: row.get(3)?,
+                at: row.get(4)?,
+                when: row.get(5)?,
+            })
+        })?;
+
+        let mut changes = Vec::new();
+        for change in rows {
+            let mut change = change?;
+            change.old_value = change.old_value.map(|value| self.open_in_context(context, key, value)).transpose()?;
+            change.new_value = change.new_value.map(|value| self.open_in_context(context, key, value)).transpose()?;
+            changes.push(change);
+        }
+        Ok(changes)
+    }
+
+    /// Value of a variable as of a revision or time; None if it did not exist then
+    pub fn get_variable_at(&self, key: &str, context: &ResolvedContext, point: HistoryPoint) -> Result<Option<String>> {
+        self.logger.trace_fn("database", &format!("getting variable {} at {:?} in context: {}", key, point, context));
+        self.change_at(key, context, point)?
+            .and_then(|(_, value)| value)
+            .map(|value| self.open_in_context(context, key, value))
+            .transpose()
+    }
+
+    /// Set a variable back to its value as of `rev` (deleting it if it did not exist then)
+    ///
+    /// The revert is itself recorded, so it can be reverted in turn, and journaled for `undo`.
+    pub fn revert_variable(&self, key: &str, context: &ResolvedContext, rev: i64) -> Result<Option<String>> {
+        self.logger.trace_fn("database", &format!("reverting variable {} to rev {} in context: {}", key, rev, context));
+
+        if self.var_history(key, context)?.is_empty() {
+            return Err(BookdbError::KeyNotFound(format!("{} has no history in {}", key, context)));
+        }
+        let target = self.change_at(key, context, HistoryPoint::Rev(rev))?.and_then(|(_, value)| value);
+
+        let tx = self.connection.unchecked_transaction()?;
+        let kvns_id = tx.query_row(sql::GET_KEYSTORE_ID, params![&context.project, &context.workspace, &context.tail], |row| row.get::<_, i64>(0))
+            .optional()?
+            .ok_or_else(|| BookdbError::Database(format!("Keystore not found: {}", context)))?;
+        self.check_writable(&tx, WriteTarget::Keystore(kvns_id), context)?;
+        let current = tx.query_row(sql::GET_VARIABLES, params![&context.project, &context.workspace, &context.tail, key], |row| row.get::<_, String>(0))
+            .optional()?;
+        let current_attrs = self.var_attrs_tx(&tx, key, context)?;
+
+        if current != target {
+            match &target {
+                Some(value) => { tx.execute(sql::SET_VARIABLE, params![key, value, kvns_id])?; }
+                None => { tx.execute(sql::DELETE_VARIABLE, params![&context.project, &context.workspace, &context.tail, key])?; }
+            }
+            self.record_var_change_tx(&tx, kvns_id, key, VarOp::Revert, current.as_deref(), target.as_deref())?;
+            self.journal_tx(&tx, "revert", context, Inverse::Var { key, old: current.as_deref(), attrs: current_attrs })?;
+        }
+
+        tx.commit()?;
+        target.map(|value| self.open_in_context(context, key, value)).transpose()
+    }
+
+    /// Append a history row; call inside the transaction that makes the change
+    pub(super) fn record_var_change_tx(
+        &self,
+        tx: &Transaction,
+        kvns_id: i64,
+        key: &str,
+        op: VarOp,
+        old_value: Option<&str>,
+        new_value: Option<&str>,
+    ) -> Result<()> {
+        tx.execute(sql::RECORD_VAR_HISTORY, params![key, op.as_str(), old_value, new_value, kvns_id])?;
+        self.audit_var_tx(tx, kvns_id, key, op.as_str(), old_value, new_value)
+    }
+
+    /// Latest change at or before `point`, as (rev, new value)
+    fn change_at(&self, key: &str, context: &ResolvedContext, point: HistoryPoint) -> Result<Option<(i64, Option<String>)>> {
+        let (query, bound) = match point {
+            HistoryPoint::Rev(rev) => (sql::GET_VAR_AT_REV, rev),
+            HistoryPoint::Time(secs) => (sql::GET_VAR_AT_TIME, secs),
+        };
+        Ok(self.connection.query_row(
+            query,
+            params![&context.project, &context.workspace, &context.tail, key, bound],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        ).optional()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bookdb::service::ctx::{Anchor, ChainMode};
+    use tempfile::TempDir;
+
+    fn create_test_context() -> ResolvedContext {
+        ResolvedContext {
+            base: "test".to_string(),
+            project: "app".to_string(),
+            workspace: "prod".to_string(),
+            anchor: Anchor::Var,
+            tail: "config".to_string(),
+            prefix_mode: ChainMode::Persistent,
+        }
+    }
+
+    #[test]
+    fn test_history_point_parse() {
+        assert_eq!(HistoryPoint::parse("12").unwrap(), HistoryPoint::Rev(12));
+        assert_eq!(HistoryPoint::parse("@1700000000").unwrap(), HistoryPoint::Time(1_700_000_000));
+        assert_eq!(HistoryPoint::parse("1970-01-01").unwrap(), HistoryPoint::Time(0));
+        assert_eq!(HistoryPoint::parse("2000-03-01T00:00:00Z").unwrap(), HistoryPoint::Time(951_868_800));
+        assert_eq!(HistoryPoint::parse("2023-11-14 22:13:20").unwrap(), HistoryPoint::Time(1_700_000_000));
+        assert!(HistoryPoint::parse("0").is_err());
+        assert!(HistoryPoint::parse("2023-13-01").is_err());
+        assert!(HistoryPoint::parse("yesterday").is_err());
+    }
+
+    #[test]
+    fn test_history_records_and_reverts() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::create_or_open(&temp_dir.path().join("test.sqlite3"))?;
+        let context = create_test_context();
+
+        db.set_variable("LEVEL", "1", &context)?;
+        db.increment_variable("LEVEL", 4, &context)?;
+        db.set_variable("LEVEL", "broken", &context)?;
+        db.delete_variable("LEVEL", &context)?;
+
+        let history = db.var_history("LEVEL", &context)?;
+        let ops: Vec<&str> = history.iter().map(|c| c.op.as_str()).collect();
+        assert_eq!(ops, vec!["set", "inc", "set", "delete"]);
+        assert_eq!(history[1].old_value.as_deref(), Some("1"));
+        assert_eq!(history[3].new_value, None);
+
+        let good = history[1].rev;
+        assert_eq!(db.get_variable_at("LEVEL", &context, HistoryPoint::Rev(good))?, Some("5".to_string()));
+        assert_eq!(db.get_variable_at("LEVEL", &context, HistoryPoint::Rev(history[3].rev))?, None);
+        assert_eq!(db.get_variable_at("LEVEL", &context, HistoryPoint::Time(0))?, None);
+
+        assert_eq!(db.revert_variable("LEVEL", &context, good)?, Some("5".to_string()));
+        assert_eq!(db.get_variable("LEVEL", &context)?, Some("5".to_string()));
+        assert_eq!(db.var_history("LEVEL", &context)?.last().unwrap().op, "revert");
+
+        assert!(db.revert_variable("MISSING", &context, good).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,526 @@
+// src/db/journal.rs - Undo journal for mutating commands
+//
+// Every variable or segment write records its inverse in journal_entries,
+// inside the transaction that makes the write. A variable's inverse carries
+// its type and expiry along with its value. Entries are grouped into ops:
+// one per write by default, or one per `begin_journal`/`end_journal` scope
+// (how `import` becomes a single undo step). `undo` replays the inverses of
+// the newest ops, newest entry first.
+//
+// Deleting a keystore, docstore, workspace or project, and moving one with
+// `mv`, has no inverse: it is journaled as an op without entries, and `undo`
+// refuses to go past it unless forced (`-f`), which skips it and leaves the
+// deletion or move in place.
+
+use crate::error::{Result, BookdbError};
+use crate::bookdb::service::ctx::{Anchor, ChainMode, ResolvedContext};
+use crate::sql;
+use rusqlite::{params, OptionalExtension, Row, Transaction};
+use super::Database;
+use super::history::VarOp;
+use super::lock::WriteTarget;
+
+/// Journal ops kept per base; older ones are pruned when a new op starts
+pub const JOURNAL_LIMIT: i64 = 100;
+
+/// Op the next writes are grouped under
+#[derive(Debug, Clone)]
+pub struct JournalScope {
+    command: String,
+    chain: String,
+    op_id: Option<i64>,
+}
+
+/// One journaled command
+#[derive(Debug, Clone, PartialEq)]
+pub struct JournalOp {
+    pub id: i64,
+    pub command: String,
+    /// Fully-qualified chain the command ran against
+    pub chain: String,
+    /// UTC, `2025-06-01T12:00:00Z`
+    pub when: String,
+    pub undone: bool,
+    pub entries: usize,
+}
+
+impl JournalOp {
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(JournalOp {
+            id: row.get(0)?,
+            command: row.get(1)?,
+            chain: row.get(2)?,
+            when: row.get(3)?,
+            undone: row.get::<_, i64>(4)? != 0,
+            entries: row.get::<_, i64>(5)? as usize,
+        })
+    }
+}
+
+/// Type and expiry of a live variable, as stored
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(super) struct VarAttrs {
+    /// `var_type` column; None is untyped
+    pub var_type: Option<String>,
+    /// `var_expires` in unix seconds; None never expires
+    pub expires: Option<i64>,
+}
+
+/// The inverse of one write, captured before the write happens
+pub(super) enum Inverse<'a> {
+    /// Variable `key` in `context`'s keystore; None means it did not exist
+    Var { key: &'a str, old: Option<&'a str>, attrs: VarAttrs },
+    /// Segment `path` of document `doc_key`; None means it did not exist
+    Segment { doc_key: &'a str, path: &'a str, old: Option<(&'a [u8], &'a str)> },
+    /// Document `doc_key` was created by the write
+    NewDocument { doc_key: &'a str },
+}
+
+impl Database {
+    /// Group the journal entries of the following writes into one op until `end_journal`
+    pub fn begin_journal(&self, command: &str, context: &ResolvedContext) {
+        self.logger.trace_fn("database", &format!("journaling '{}' on {}", command, context));
+        *self.journal.borrow_mut() = Some(JournalScope {
+            command: command.to_string(),
+            chain: context.qualified(),
+            op_id: None,
+        });
+    }
+
+    /// Close the scope opened by `begin_journal`
+    pub fn end_journal(&self) {
+        *self.journal.borrow_mut() = None;
+    }
+
+    /// Record the inverse of a write inside its transaction
+    ///
+    /// `command` labels the op when no `begin_journal` scope is open.
+    pub(super) fn journal_tx(&self, tx: &Transaction, command: &str, context: &ResolvedContext, inverse: Inverse) -> Result<()> {
+        self.journal_all_tx(tx, command, context, [inverse])
+    }
+
+    /// Record the inverses of one write under a single op, so one undo reverts them together
+    pub(super) fn journal_all_tx<'a>(
+        &self,
+        tx: &Transaction,
+        command: &str,
+        context: &ResolvedContext,
+        inverses: impl IntoIterator<Item = Inverse<'a>>,
+    ) -> Result<()> {
+        let op_id = self.journal_op_tx(tx, command, context)?;
+        let (p, w, t) = (&context.project, &context.workspace, &context.tail);
+
+        for inverse in inverses {
+            self.record_inverse_tx(tx, op_id, (p, w, t), inverse)?;
+        }
+        Ok(())
+    }
+
+    fn record_inverse_tx(&self, tx: &Transaction, op_id: i64, (p, w, t): (&str, &str, &str), inverse: Inverse) -> Result<()> {
+        match inverse {
+            Inverse::Var { key, old, attrs } => tx.execute(sql::RECORD_JOURNAL_ENTRY, params![
+                op_id, "var", p, w, t, key, None::<&str>, inverse_of(old.is_some()), old, None::<&str>,
+                attrs.var_type, attrs.expires
+            ])?,
+            Inverse::Segment { doc_key, path, old } => tx.execute(sql::RECORD_JOURNAL_ENTRY, params![
+                op_id, "seg", p, w, doc_key, doc_key, path, inverse_of(old.is_some()),
+                old.map(|(content, _)| content), old.map(|(_, mime)| mime), None::<&str>, None::<i64>
+            ])?,
+            Inverse::NewDocument { doc_key } => tx.execute(sql::RECORD_JOURNAL_ENTRY, params![
+                op_id, "doc", p, w, doc_key, doc_key, None::<&str>, "delete", None::<&[u8]>, None::<&str>,
+                None::<&str>, None::<i64>
+            ])?,
+        };
+        Ok(())
+    }
+
+    /// Type and expiry of variable `key`, to journal before a write changes them
+    pub(super) fn var_attrs_tx(&self, tx: &Transaction, key: &str, context: &ResolvedContext) -> Result<VarAttrs> {
+        Ok(tx.query_row(
+            sql::GET_VAR_ATTRS,
+            params![&context.project, &context.workspace, &context.tail, key],
+            |row| Ok(VarAttrs { var_type: row.get(0)?, expires: row.get(1)? }),
+        ).optional()?.unwrap_or_default())
+    }
+
+    /// Record a write that cannot be undone, so `undo` stops at it
+    pub(super) fn journal_irreversible_tx(&self, tx: &Transaction, command: &str, chain: &str) -> Result<()> {
+        tx.execute(sql::CREATE_JOURNAL_OP, params![command, chain])?;
+        tx.execute(sql::PRUNE_JOURNAL, params![JOURNAL_LIMIT])?;
+        Ok(())
+    }
+
+    /// Op id for the current write, starting (and pruning) an op when needed
+    fn journal_op_tx(&self, tx: &Transaction, command: &str, context: &ResolvedContext) -> Result<i64> {
+        let mut scope = self.journal.borrow_mut();
+        if let Some(JournalScope { op_id: Some(op_id), .. }) = scope.as_ref() {
+            return Ok(*op_id);
+        }
+
+        let (command, chain) = match scope.as_ref() {
+            Some(scope) => (scope.command.clone(), scope.chain.clone()),
+            None => (command.to_string(), context.qualified()),
+        };
+        tx.execute(sql::CREATE_JOURNAL_OP, params![command, chain])?;
+        let op_id = tx.last_insert_rowid();
+        tx.execute(sql::PRUNE_JOURNAL, params![JOURNAL_LIMIT])?;
+
+        if let Some(scope) = scope.as_mut() {
+            scope.op_id = Some(op_id);
+        }
+        Ok(op_id)
+    }
+
+    /// Newest `limit` journal ops, newest first
+    pub fn list_journal(&self, limit: usize) -> Result<Vec<JournalOp>> {
+        self.logger.trace_fn("database", &format!("listing last {} journal ops", limit));
+
+        let mut stmt = self.connection.prepare(sql::LIST_JOURNAL_OPS)?;
+        let rows = stmt.query_map(params![limit as i64], JournalOp::from_row)?;
+
+        let mut ops = Vec::new();
+        for op in rows {
+            ops.push(op?);
+        }
+        Ok(ops)
+    }
+
+    /// Undo the newest `count` ops that are not undone yet, in one transaction
+    ///
+    /// Returns the undone ops, newest first. An undo is not journaled itself,
+    /// but restored variables show up as `revert` in their history. An
+    /// irreversible op (a deleted store, workspace or project, or a move) fails
+    /// the whole undo unless forced, which marks it undone without restoring anything.
+    pub fn undo(&self, count: usize) -> Result<Vec<JournalOp>> {
+        self.logger.trace_fn("database", &format!("undoing last {} journal ops", count));
+
+        let tx = self.connection.unchecked_transaction()?;
+
+        let ops: Vec<JournalOp> = {
+            let mut stmt = tx.prepare(sql::LIST_UNDOABLE_OPS)?;
+            let rows = stmt.query_map(params![count as i64], JournalOp::from_row)?;
+            rows.collect::<rusqlite::Result<_>>()?
+        };
+
+        for op in &ops {
+            if op.entries == 0 {
+                if !self.force.get() {
+                    return Err(BookdbError::Argument(format!(
+                        "Cannot undo #{} {} on {}: deleted or moved namespaces cannot be restored \
+                         (use --force to skip past it)", op.id, op.command, op.chain
+                    )));
+                }
+                self.logger.trace_fn("database", &format!("skipping irreversible op #{} (forced)", op.id));
+                tx.execute(sql::MARK_OP_UNDONE, params![op.id])?;
+                continue;
+            }
+            let entries: Vec<JournalEntry> = {
+                let mut stmt = tx.prepare(sql::LIST_JOURNAL_ENTRIES)?;
+                let rows = stmt.query_map(params![op.id], |row| Ok(JournalEntry {
+                    kind: row.get(0)?,
+                    project: row.get(1)?,
+                    workspace: row.get(2)?,
+                    tail: row.get(3)?,
+                    key: row.get(4)?,
+                    path: row.get(5)?,
+                    inverse: row.get(6)?,
+                    old_value: row.get(7)?,
+                    old_mime: row.get(8)?,
+                    old_attrs: VarAttrs { var_type: row.get(9)?, expires: row.get(10)? },
+                }))?;
+                rows.collect::<rusqlite::Result<_>>()?
+            };
+
+            for entry in &entries {
+                self.replay_tx(&tx, entry)?;
+            }
+            tx.execute(sql::MARK_OP_UNDONE, params![op.id])?;
+        }
+
+        tx.commit()?;
+        Ok(ops)
+    }
+
+    /// Apply one inverse
+    fn replay_tx(&self, tx: &Transaction, entry: &JournalEntry) -> Result<()> {
+        let context = entry.context(&self.base_name);
+        let (p, w) = (&context.project, &context.workspace);
+
+        // Undo is a write like any other: locks apply to what it restores, and it is audited
+        if entry.kind != "var" {
+            let target = self.docstore_id(tx, &context)?.map_or(WriteTarget::Base, WriteTarget::Docstore);
+            self.check_writable(tx, target, &context)?;
+
+            let (key, current) = match &entry.path {
+                Some(path) => (
+                    format!("{}/{}", entry.key, path),
+                    tx.query_row(sql::GET_DOC_SEGMENT, params![p, w, &entry.key, path], |row| row.get::<_, Vec<u8>>(0))
+                        .optional()?,
+                ),
+                None => (entry.key.clone(), self.document_tx(tx, &entry.key, &context)?.map(String::into_bytes)),
+            };
+            let restored = if entry.inverse == "set" { entry.old_value.as_deref() } else { None };
+            self.audit_tx(tx, VarOp::Revert.as_str(), format!("{}.{}.doc", p, w), Some(key.as_str()), current.as_deref(), restored)?;
+        }
+
+        match (entry.kind.as_str(), entry.inverse.as_str()) {
+            ("var", inverse) => {
+                let current = self.get_variable_in_tx(tx, &entry.key, &context)?;
+                let restored = match inverse {
+                    "set" => Some(String::from_utf8_lossy(entry.old_value.as_deref().unwrap_or_default()).into_owned()),
+                    _ => None,
+                };
+                let same_attrs = restored.is_none() || self.var_attrs_tx(tx, &entry.key, &context)? == entry.old_attrs;
+                if current == restored && same_attrs {
+                    return Ok(());
+                }
+
+                let kvns_id = self.ensure_keystore_context_exists(tx, &context)?;
+                self.check_writable(tx, WriteTarget::Keystore(kvns_id), &context)?;
+                match &restored {
+                    Some(value) => {
+                        tx.execute(sql::SET_VARIABLE, params![&entry.key, value, kvns_id])?;
+                        let VarAttrs { var_type, expires } = &entry.old_attrs;
+                        tx.execute(sql::SET_VAR_ATTRS, params![var_type, expires, &entry.key, kvns_id])?;
+                    }
+                    None => { tx.execute(sql::DELETE_VARIABLE, params![p, w, &context.tail, &entry.key])?; }
+                }
+                if current != restored {
+                    self.record_var_change_tx(tx, kvns_id, &entry.key, VarOp::Revert, current.as_deref(), restored.as_deref())?;
+                }
+            }
+            ("seg", "set") => {
+                let doc_id = self.ensure_document_tx(tx, &entry.key, &context)?;
+                let mime = entry.old_mime.as_deref().unwrap_or("text/plain");
+                tx.execute(sql::SET_DOC_SEGMENT, params![&entry.path, mime, entry.old_value.as_deref().unwrap_or_default(), doc_id])?;
+            }
+            ("seg", _) => {
+                let doc_id = tx.query_row(sql::GET_DOC_ID, params![p, w, &entry.key], |row| row.get::<_, i64>(0)).optional()?;
+                if let Some(doc_id) = doc_id {
+                    tx.execute(sql::DELETE_DOC_SEGMENT, params![doc_id, &entry.path])?;
+                }
+            }
+            _ => {
+                tx.execute(sql::DELETE_DOCUMENT, params![p, w, &entry.key])?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A stored inverse, as read back for replay
+struct JournalEntry {
+    kind: String,
+    project: String,
+    workspace: String,
+    tail: String,
+    key: String,
+    path: Option<String>,
+    inverse: String,
+    old_value: Option<Vec<u8>>,
+    old_mime: Option<String>,
+    old_attrs: VarAttrs,
+}
+
+impl JournalEntry {
+    fn context(&self, base: &str) -> ResolvedContext {
+        ResolvedContext {
+            base: base.to_string(),
+            project: self.project.clone(),
+            workspace: self.workspace.clone(),
+            anchor: if self.kind == "var" { Anchor::Var } else { Anchor::Doc },
+            tail: self.tail.clone(),
+            prefix_mode: ChainMode::Ephemeral,
+        }
+    }
+}
+
+fn inverse_of(existed: bool) -> &'static str {
+    if existed { "set" } else { "delete" }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn create_test_context(anchor: Anchor, tail: &str) -> ResolvedContext {
+        ResolvedContext {
+            base: "test".to_string(),
+            project: "app".to_string(),
+            workspace: "prod".to_string(),
+            anchor,
+            tail: tail.to_string(),
+            prefix_mode: ChainMode::Persistent,
+        }
+    }
+
+    #[test]
+    fn test_undo_variable_writes() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::create_or_open(&temp_dir.path().join("test.sqlite3"))?;
+        let context = create_test_context(Anchor::Var, "config");
+
+        db.set_variable("PORT", "80", &context)?;
+        db.set_variable("PORT", "8080", &context)?;
+        db.increment_variable("PORT", 1, &context)?;
+        db.delete_variable("PORT", &context)?;
+        assert_eq!(db.list_journal(10)?.len(), 4);
+
+        let undone = db.undo(2)?;
+        assert_eq!(undone.iter().map(|op| op.command.as_str()).collect::<Vec<_>>(), vec!["delv", "inc"]);
+        assert_eq!(db.get_variable("PORT", &context)?, Some("8080".to_string()));
+
+        db.undo(5)?;
+        assert_eq!(db.get_variable("PORT", &context)?, None);
+        assert!(db.list_journal(10)?.iter().all(|op| op.undone));
+        assert!(db.undo(1)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_undo_stops_at_deleted_workspace() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::create_or_open(&temp_dir.path().join("test.sqlite3"))?;
+        let context = create_test_context(Anchor::Var, "config");
+        let other = context.with_target("app", "staging", "config");
+
+        db.set_variable("PORT", "80", &other)?;
+        db.set_variable("PORT", "80", &context)?;
+        assert!(db.delete_workspace("app", "prod")?);
+        assert_eq!(db.list_journal(1)?[0].command, "del workspace");
+
+        // The earlier write to staging is not undone in the deletion's place
+        assert!(matches!(db.undo(1), Err(BookdbError::Argument(_))));
+        assert_eq!(db.get_variable("PORT", &other)?, Some("80".to_string()));
+
+        db.set_force(true);
+        let undone = db.undo(2)?;
+        assert_eq!(undone.iter().map(|op| op.command.as_str()).collect::<Vec<_>>(), vec!["del workspace", "setv"]);
+        assert!(!db.list_workspaces("app")?.contains(&"prod".to_string()));
+        assert_eq!(db.get_variable("PORT", &other)?, Some("80".to_string()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_scoped_import_undoes_in_one_step() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::create_or_open(&temp_dir.path().join("test.sqlite3"))?;
+        let context = create_test_context(Anchor::Var, "config");
+        db.set_variable("KEEP", "old", &context)?;
+
+        let variables: HashMap<String, String> = [("KEEP", "new"), ("A", "1"), ("B", "2")]
+            .iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        db.begin_journal("import", &context);
+        db.import_variables(variables, &context)?;
+        db.end_journal();
+
+        let journal = db.list_journal(10)?;
+        assert_eq!((journal[0].command.as_str(), journal[0].entries), ("import", 3));
+
+        db.undo(1)?;
+        assert_eq!(db.list_variables(&context)?.len(), 1);
+        assert_eq!(db.get_variable("KEEP", &context)?, Some("old".to_string()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_undo_document_segments() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::create_or_open(&temp_dir.path().join("test.sqlite3"))?;
+        let context = create_test_context(Anchor::Doc, "README");
+
+        db.set_doc_segment("README", "_root", "text/plain", b"v1", &context)?;
+        db.set_doc_segment("README", "_root", "text/markdown", b"v2", &context)?;
+        db.set_doc_segment("README", "notes", "text/plain", b"n", &context)?;
+
+        db.undo(2)?;
+        assert_eq!(db.get_doc_segment("README", "_root", &context)?, Some((b"v1".to_vec(), "text/plain".to_string())));
+        assert_eq!(db.get_doc_segment("README", "notes", &context)?, None);
+
+        db.undo(1)?;
+        assert!(db.list_documents(&context)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_undo_deleted_document() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::create_or_open(&temp_dir.path().join("test.sqlite3"))?;
+        let context = create_test_context(Anchor::Doc, "README");
+
+        db.set_doc_segment("README", "_root", "text/markdown", b"# hi", &context)?;
+        db.set_doc_segment("README", "notes", "text/plain", b"n", &context)?;
+        assert!(db.delete_document("README", &context)?);
+
+        db.undo(1)?;
+        assert_eq!(db.get_doc_segment("README", "_root", &context)?, Some((b"# hi".to_vec(), "text/markdown".to_string())));
+        assert_eq!(db.get_doc_segment("README", "notes", &context)?, Some((b"n".to_vec(), "text/plain".to_string())));
+        Ok(())
+    }
+
+    #[test]
+    fn test_undo_restores_type_and_expiry() -> Result<()> {
+        use super::super::{SetOptions, VarType};
+        use std::time::Duration;
+
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::create_or_open(&temp_dir.path().join("test.sqlite3"))?;
+        let context = create_test_context(Anchor::Var, "config");
+
+        let options = SetOptions { var_type: Some(VarType::Int), ttl: Some(Duration::from_secs(3600)), ..Default::default() };
+        db.set_variable_with("PORT", "80", &options, &context)?;
+        // A plain set drops the TTL; the delete drops the type too
+        db.set_variable("PORT", "8080", &context)?;
+        assert!(db.list_variable_expiries(&context)?.is_empty());
+        db.delete_variable("PORT", &context)?;
+
+        db.undo(2)?;
+        assert_eq!(db.get_variable("PORT", &context)?, Some("80".to_string()));
+        assert_eq!(db.get_variable_type("PORT", &context)?, VarType::Int);
+        assert!(db.list_variable_expiries(&context)?.contains_key("PORT"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_undo_revert() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::create_or_open(&temp_dir.path().join("test.sqlite3"))?;
+        let context = create_test_context(Anchor::Var, "config");
+
+        db.set_variable("PORT", "80", &context)?;
+        db.set_variable("PORT", "8080", &context)?;
+        db.revert_variable("PORT", &context, 1)?;
+        assert_eq!(db.list_journal(1)?[0].command, "revert");
+
+        db.undo(1)?;
+        assert_eq!(db.get_variable("PORT", &context)?, Some("8080".to_string()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_undo_stops_at_deleted_stores_and_moves() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::create_or_open(&temp_dir.path().join("test.sqlite3"))?;
+        let context = create_test_context(Anchor::Var, "config");
+        let moved = context.with_target("app", "prod", "settings");
+
+        db.set_variable("PORT", "80", &context)?;
+        db.move_namespace(&context, &moved, false)?;
+        db.set_doc_segment("README", "_root", "text/plain", b"v1", &create_test_context(Anchor::Doc, "README"))?;
+        assert!(db.delete_docstore("app", "prod")?);
+        assert!(db.delete_keystore("app", "prod", "settings")?);
+
+        let commands: Vec<String> = db.list_journal(5)?.into_iter().map(|op| op.command).collect();
+        assert_eq!(commands, vec!["del keystore", "del docstore", "setd", "mv", "setv"]);
+        assert!(matches!(db.undo(1), Err(BookdbError::Argument(_))));
+
+        // Forced, undo skips past each of them without recreating anything
+        db.set_force(true);
+        db.undo(5)?;
+        assert!(db.list_keystores("app", "prod")?.is_empty());
+        assert_eq!(db.get_variable("PORT", &context)?, None);
+        Ok(())
+    }
+}
@@ -3,9 +3,27 @@
 use crate::error::{Result, BookdbError};
 use crate::bookdb::service::ctx::ResolvedContext;
 use crate::sql;
-use rusqlite::{params, Transaction};
+use rusqlite::{params, OptionalExtension, Transaction};
 use std::collections::HashMap;
+use std::time::Duration;
 use super::Database;
+use super::history::VarOp;
+use super::journal::Inverse;
+use super::vartype::VarType;
+use super::counter::{CounterOptions, Number};
+use super::condition::WriteCondition;
+use super::lock::WriteTarget;
+
+/// Options for `setv` beyond key and value
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SetOptions {
+    /// Explicit type; None keeps the key's current type, `Str` drops it
+    pub var_type: Option<VarType>,
+    /// Precondition on the current value (`--if-equals`, `--if-absent`, `--if-present`)
+    pub condition: Option<WriteCondition>,
+    /// Expire the value after this long (`--ttl`)
+    pub ttl: Option<Duration>,
+}
 
 impl Database {
     /// List all keystores in a workspace within a project  
@@ -14,7 +32,7 @@ impl Database {
         
         let mut stmt = self.connection.prepare(sql::LIST_KEYSTORES)?;
         let keystore_iter = stmt.query_map([project, workspace], |row| {
-            Ok(row.get::<_, String>(0)?)
+            row.get::<_, String>(0)
         })?;
         
         let mut keystores = Vec::new();
@@ -37,6 +55,7 @@ impl Database {
         let mut variables = HashMap::new();
         for var_result in var_iter {
             let (key, value) = var_result?;
+            let value = self.open_in_context(context, &key, value)?;
             variables.insert(key, value);
         }
         
@@ -47,78 +66,133 @@ impl Database {
     pub fn get_variable(&self, key: &str, context: &ResolvedContext) -> Result<Option<String>> {
         self.logger.trace_fn("database", &format!("getting variable {} in context: {}", key, context));
         
-        let mut stmt = self.connection.prepare(sql::GET_VARIABLE)?;
+        let mut stmt = self.connection.prepare(sql::GET_VARIABLES)?;
         let mut rows = stmt.query_map([&context.project, &context.workspace, &context.tail, key], |row| {
-            Ok(row.get::<_, String>(0)?)
+            row.get::<_, String>(0)
         })?;
         
         match rows.next() {
-            Some(Ok(value)) => Ok(Some(value)),
+            Some(Ok(value)) => Ok(Some(self.open_in_context(context, key, value)?)),
             Some(Err(e)) => Err(e.into()),
             None => Ok(None),
         }
     }
     
-    /// Set variable value (upsert operation)
-    pub fn set_variable(&self, key: &str, value: &str, context: &ResolvedContext) ->  Result<(), E> {
-        self.logger.trace_fn("database", &format!("setting variable {} = {} in context: {}", key, value, context));
-        
-        let tx = self.connection.transaction()?;
+    /// Set variable value (upsert operation); a typed key validates the value against its type
+    pub fn set_variable(&self, key: &str, value: &str, context: &ResolvedContext) -> Result<()> {
+        self.set_typed_variable(key, value, None, context).map(|_| ())
+    }
+    
+    /// Set variable value, optionally (re)typing it; returns the canonical stored value
+    ///
+    /// Without `var_type` the key keeps its current type. `VarType::Str` drops the type.
+    pub fn set_typed_variable(&self, key: &str, value: &str, var_type: Option<VarType>, context: &ResolvedContext) -> Result<String> {
+        self.set_variable_with(key, value, &SetOptions { var_type, ..Default::default() }, context)
+    }
+    
+    /// Set a variable with a type, precondition and/or expiry
+    ///
+    /// A precondition is checked inside the write's transaction; a failed check
+    /// leaves the keystore untouched and returns `BookdbError::PreconditionFailed`.
+    /// Every set replaces the expiry: without `ttl` the new value is permanent.
+    pub fn set_variable_with(&self, key: &str, value: &str, options: &SetOptions, context: &ResolvedContext) -> Result<String> {
+        // Values (and `--if-equals` operands) stay out of traces; they may be secrets
+        self.logger.trace_fn("database", &format!(
+            "setting variable {} (type: {:?}, ttl: {:?}) in context: {}", key, options.var_type, options.ttl, context
+        ));
+        let tx = self.connection.unchecked_transaction()?;
+        let value = self.set_variable_with_tx(&tx, key, value, options, context)?;
+        tx.commit()?;
+        Ok(value)
+    }
+    
+    /// `set_variable_with` inside a caller's transaction
+    pub(super) fn set_variable_with_tx(&self, tx: &Transaction, key: &str, value: &str, options: &SetOptions, context: &ResolvedContext) -> Result<String> {
+        let SetOptions { var_type, condition, ttl } = options;
         
         // Ensure context exists
-        let kvns_id = self.ensure_keystore_context_exists(&tx, context)?;
+        let kvns_id = self.ensure_keystore_context_exists(tx, context)?;
+        self.check_writable(tx, WriteTarget::Keystore(kvns_id), context)?;
         
-        // Set the variable
-        tx.execute(sql::SET_VARIABLE, params![key, value, kvns_id])?;
+        // Validate against the explicit type, else the one the key already has
+        let existing = self.get_variable_type_in_tx(tx, key, context)?;
+        let effective = var_type.unwrap_or(existing);
+        let value = effective.normalize(key, value)?;
         
-        tx.commit()?;
-        Ok(())
+        // Set the variable, keeping the old value in history (both as stored)
+        let old_value = self.get_variable_in_tx(tx, key, context)?;
+        let old_attrs = self.var_attrs_tx(tx, key, context)?;
+        if let Some(condition) = condition {
+            let current = old_value.as_deref().map(|old| self.open_value(tx, kvns_id, key, old)).transpose()?;
+            condition.check(key, existing, current.as_deref())?;
+        }
+        let stored = self.seal_value(tx, kvns_id, key, &value)?;
+        tx.execute(sql::SET_VARIABLE, params![key, &stored, kvns_id])?;
+        if var_type.is_some() {
+            tx.execute(sql::SET_VAR_TYPE, params![effective.to_column(), key, kvns_id])?;
+        }
+        // Whole seconds, rounded up so a TTL never ends early
+        let ttl_secs = ttl.map(|ttl| ttl.as_secs() as i64 + i64::from(ttl.subsec_nanos() > 0));
+        tx.execute(sql::SET_VAR_EXPIRY, params![ttl_secs, key, kvns_id])?;
+        self.record_var_change_tx(tx, kvns_id, key, VarOp::Set, old_value.as_deref(), Some(&stored))?;
+        self.journal_tx(tx, "setv", context, Inverse::Var { key, old: old_value.as_deref(), attrs: old_attrs })?;
+        Ok(value)
     }
     
-    /// Delete variable by key and context
-    pub fn delete_variable(&self, key: &str, context: &ResolvedContext) -> Result<bool, E> {
-        self.logger.trace_fn("database", &format!("deleting variable {} in context: {}", key, context));
-        
-        let changes = self.connection.execute(
-            sql::DELETE_VARIABLE,
-            params![&context.project, &context.workspace, &context.tail, key]
-        )?;
+    /// Type of a variable; untyped and missing keys are `VarType::Str`
+    pub fn get_variable_type(&self, key: &str, context: &ResolvedContext) -> Result<VarType> {
+        self.logger.trace_fn("database", &format!("getting type of {} in context: {}", key, context));
         
-        Ok(changes > 0)
+        let column = self.connection.query_row(
+            sql::GET_VAR_TYPE,
+            params![&context.project, &context.workspace, &context.tail, key],
+            |row| row.get::<_, Option<String>>(0)
+        ).optional()?.flatten();
+        VarType::from_column(column.as_deref())
     }
     
-    /// Atomically increment a numeric variable
-    pub fn increment_variable(&self, key: &str, amount: i64, context: &ResolvedContext) -> Result<i64> {
-        self.logger.trace_fn("database", &format!("incrementing variable {} by {} in context: {}", key, amount, context));
+    /// Delete variable by key and context
+    pub fn delete_variable(&self, key: &str, context: &ResolvedContext) -> Result<bool> {
+        self.logger.trace_fn("database", &format!("deleting variable {} in context: {}", key, context));
         
-        let tx = self.connection.transaction()?;
+        let tx = self.connection.unchecked_transaction()?;
         
-        // Ensure context exists
-        let kvns_id = self.ensure_keystore_context_exists(&tx, context)?;
+        let kvns_id = tx.query_row(
+            sql::GET_KEYSTORE_ID,
+            params![&context.project, &context.workspace, &context.tail],
+            |row| row.get::<_, i64>(0)
+        ).optional()?;
+        let old_value = self.get_variable_in_tx(&tx, key, context)?;
         
-        // Get current value
-        let current_value = match self.get_variable_in_tx(&tx, key, context)? {
-            Some(val) => {
-                val.parse::<i64>()
-                   .map_err(|_| BookdbError::NonNumericValue(key.to_string(), val))?
-            }
-            None => 0, // Initialize to 0 if key doesn't exist
+        let (kvns_id, old_value) = match (kvns_id, old_value) {
+            (Some(kvns_id), Some(old_value)) => (kvns_id, old_value),
+            _ => return Ok(false),
         };
+        self.check_writable(&tx, WriteTarget::Keystore(kvns_id), context)?;
+        let old_attrs = self.var_attrs_tx(&tx, key, context)?;
         
-        // Calculate new value with overflow check
-        let new_value = current_value
-            .checked_add(amount)
-            .ok_or_else(|| BookdbError::NumericOverflow(key.to_string(), current_value, amount))?;
-        
-        // Update the variable
-        tx.execute(sql::SET_VARIABLE, params![key, new_value.to_string(), kvns_id])?;
+        tx.execute(
+            sql::DELETE_VARIABLE,
+            params![&context.project, &context.workspace, &context.tail, key]
+        )?;
+        self.record_var_change_tx(&tx, kvns_id, key, VarOp::Delete, Some(&old_value), None)?;
+        self.journal_tx(&tx, "delv", context, Inverse::Var { key, old: Some(&old_value), attrs: old_attrs })?;
         
         tx.commit()?;
-        Ok(new_value)
+        Ok(true)
+    }
+    
+    /// Atomically increment a numeric variable; returns the new stored value
+    ///
+    /// Float keys add `amount` as a float and duration keys add it as seconds;
+    /// everything else is an integer counter. See `step_counter` for bounds.
+    pub fn increment_variable(&self, key: &str, amount: i64, context: &ResolvedContext) -> Result<String> {
+        self.logger.trace_fn("database", &format!("incrementing variable {} by {} in context: {}", key, amount, context));
+        self.step_counter(key, Number::Int(amount), &CounterOptions::default(), context)
     }
     
     /// Atomically decrement a numeric variable
-    pub fn decrement_variable(&self, key: &str, amount: i64, context: &ResolvedContext) -> Result<i64> {
+    pub fn decrement_variable(&self, key: &str, amount: i64, context: &ResolvedContext) -> Result<String> {
         self.logger.trace_fn("database", &format!("decrementing variable {} by {} in context: {}", key, amount, context));
         
         // Use increment with negative amount
@@ -132,17 +206,17 @@ impl Database {
         let count: i64 = self.connection.query_row(
             sql::COUNT_VARIABLES,
             params![&context.project, &context.workspace, &context.tail],
-            |row| Ok(row.get(0)?)
+            |row| row.get(0)
         )?;
         
         Ok(count as usize)
     }
     
     /// Get variable value within a transaction
-    fn get_variable_in_tx(&self, tx: &Transaction, key: &str, context: &ResolvedContext) -> Result<Option<String>> {
-        let mut stmt = tx.prepare(sql::GET_VARIABLE)?;
+    pub(super) fn get_variable_in_tx(&self, tx: &Transaction, key: &str, context: &ResolvedContext) -> Result<Option<String>> {
+        let mut stmt = tx.prepare(sql::GET_VARIABLES)?;
         let mut rows = stmt.query_map([&context.project, &context.workspace, &context.tail, key], |row| {
-            Ok(row.get::<_, String>(0)?)
+            row.get::<_, String>(0)
         })?;
         
         match rows.next() {
@@ -152,15 +226,25 @@ impl Database {
         }
     }
     
+    /// Get variable type within a transaction
+    pub(super) fn get_variable_type_in_tx(&self, tx: &Transaction, key: &str, context: &ResolvedContext) -> Result<VarType> {
+        let column = tx.query_row(
+            sql::GET_VAR_TYPE,
+            params![&context.project, &context.workspace, &context.tail, key],
+            |row| row.get::<_, Option<String>>(0)
+        ).optional()?.flatten();
+        VarType::from_column(column.as_deref())
+    }
+    
     /// Ensure keystore context (project.workspace.keystore) exists, return keyval_ns ID
-    fn ensure_keystore_context_exists(&self, tx: &Transaction, context: &ResolvedContext) -> Result<i64> {
+    pub(super) fn ensure_keystore_context_exists(&self, tx: &Transaction, context: &ResolvedContext) -> Result<i64> {
         // First ensure project exists
         let project_id = self.ensure_project_exists_tx(tx, &context.project)?;
         
         // Then ensure keyval namespace exists
-        let mut stmt = tx.prepare(sql::RESOLVE_KEYVAL_NS_ID)?;
+        let mut stmt = tx.prepare(sql::GET_KEYSTORE_ID)?;
         let mut rows = stmt.query_map([&context.project, &context.workspace, &context.tail], |row| {
-            Ok(row.get::<_, i64>(0)?)
+            row.get::<_, i64>(0)
         })?;
         
         match rows.next() {
@@ -174,15 +258,41 @@ impl Database {
         }
     }
     
-    /// Create a new keystore (stub - implicit through first variable)
-    pub fn create_keystore(&self, _project: &str, _workspace: &str, _name: &str) ->  Result<(), E> {
-        // TODO: Implement explicit keystore creation if needed
-        todo!("Explicit keystore creation not yet implemented")
+    /// Create an empty keystore; fails if it already exists
+    pub fn create_keystore(&self, project: &str, workspace: &str, name: &str) -> Result<()> {
+        self.logger.trace_fn("database", &format!("creating keystore {}.{}.var.{}", project, workspace, name));
+        
+        if self.list_keystores(project, workspace)?.iter().any(|k| k == name) {
+            return Err(BookdbError::Database(format!(
+                "Keystore already exists: {}.{}.var.{}", project, workspace, name
+            )));
+        }
+        
+        let tx = self.connection.unchecked_transaction()?;
+        self.check_writable(&tx, WriteTarget::Base, format!("{}.{}.var.{}", project, workspace, name))?;
+        let project_id = self.ensure_project_exists_tx(&tx, project)?;
+        tx.execute(sql::CREATE_KEYVAL_NS, params![name, project_id, workspace])?;
+        self.audit_tx(&tx, "mkkeystore", format!("{}.{}.var.{}", project, workspace, name), None, None, None)?;
+        tx.commit()?;
+        Ok(())
     }
     
-    /// Delete a keystore (stub - not implemented)
-    pub fn delete_keystore(&self, _project: &str, _workspace: &str, _name: &str) ->  Result<(), E> {
-        // TODO: Implement keystore deletion with cascade
-        todo!("Keystore deletion not yet implemented")
+    /// Delete a keystore and all its variables; false if it did not exist
+    pub fn delete_keystore(&self, project: &str, workspace: &str, name: &str) -> Result<bool> {
+        self.logger.trace_fn("database", &format!("deleting keystore {}.{}.var.{}", project, workspace, name));
+        
+        let tx = self.connection.unchecked_transaction()?;
+        let kvns_id = tx.query_row(sql::GET_KEYSTORE_ID, params![project, workspace, name], |row| row.get::<_, i64>(0))
+            .optional()?;
+        let target = kvns_id.map_or(WriteTarget::Base, WriteTarget::Keystore);
+        self.check_writable(&tx, target, format!("{}.{}.var.{}", project, workspace, name))?;
+        
+        let changes = tx.execute(sql::DELETE_KEYSTORE, params![project, workspace, name])?;
+        if changes > 0 {
+            self.audit_tx(&tx, "rmkeystore", format!("{}.{}.var.{}", project, workspace, name), None, None, None)?;
+            self.journal_irreversible_tx(&tx, "del keystore", &format!("{}@{}.{}.var.{}", self.base_name, project, workspace, name))?;
+        }
+        tx.commit()?;
+        Ok(changes > 0)
     }
 }
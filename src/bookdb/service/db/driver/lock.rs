@@ -0,0 +1,260 @@
+// src/db/lock.rs - Locked namespaces and read-only bases
+//
+// `bookdb lock` protects a keystore (or a workspace's doc store) from writes;
+// every mutating path calls `check_writable` inside its transaction and fails
+// with `BookdbError::Locked` unless the caller forced the write (`-f`, which
+// follows the BashFX bypass-safety rule). A read-only base refuses all writes,
+// forced or not, until `bookdb unlock --base`.
+
+use crate::error::{Result, BookdbError};
+use crate::bookdb::service::ctx::{Anchor, ResolvedContext};
+use crate::sql;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::fmt;
+use super::Database;
+
+/// base_flags row that makes a base read-only
+const READ_ONLY: &str = "read_only";
+
+/// What a write touches, for the lock check
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum WriteTarget {
+    /// Base-level structure (projects, new keystores); only read-only applies
+    Base,
+    Keystore(i64),
+    Docstore(i64),
+}
+
+/// A locked namespace, as listed by `ls locks`
+#[derive(Debug, Clone)]
+pub struct LockInfo {
+    /// `project.workspace.var.keystore` or `project.workspace.doc`
+    pub chain: String,
+    /// UTC, `2024-05-01T12:00:00Z`
+    pub locked_at: String,
+}
+
+impl Database {
+    /// Let writes through locked namespaces (never through a read-only base)
+    pub fn set_force(&self, force: bool) {
+        self.force.set(force);
+    }
+
+    /// Lock (or with `locked = false`, unlock) the keystore or doc store of `context`
+    pub fn set_namespace_lock(&self, context: &ResolvedContext, locked: bool) -> Result<()> {
+        self.logger.trace_fn("database", &format!("setting lock: {} on {}", locked, context));
+        if context.is_wildcard() {
+            return Err(BookdbError::Argument(format!("lock needs a single namespace, not {}", context)));
+        }
+
+        let tx = self.connection.unchecked_transaction()?;
+        self.check_writable(&tx, WriteTarget::Base, context)?;
+        let (kvns_id, ds_id) = match (context.anchor, locked) {
+            // Locking creates the namespace, so it is protected before its first write
+            (Anchor::Var, true) => (Some(self.ensure_keystore_context_exists(&tx, context)?), None),
+            (Anchor::Doc, true) => (None, Some(self.ensure_doc_store_exists(&tx, context)?)),
+            (Anchor::Var, false) => (self.keystore_id(&tx, context)?, None),
+            (Anchor::Doc, false) => (None, self.docstore_id(&tx, context)?),
+        };
+        if kvns_id.is_none() && ds_id.is_none() {
+            return Err(BookdbError::KeyNotFound(format!("namespace {}", context)));
+        }
+        let query = if locked { sql::LOCK_NAMESPACE } else { sql::UNLOCK_NAMESPACE };
+        let changes = tx.execute(query, params![kvns_id, ds_id])?;
+        if changes > 0 {
+            let chain = match context.anchor {
+                Anchor::Var => context.to_string(),
+                Anchor::Doc => format!("{}.{}.doc", context.project, context.workspace),
+            };
+            self.audit_tx(&tx, if locked { "lock" } else { "unlock" }, chain, None, None, None)?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Make the whole base read-only, or writable again
+    pub fn set_read_only(&self, read_only: bool) -> Result<()> {
+        self.logger.trace_fn("database", &format!("setting base {} read-only: {}", self.base_name, read_only));
+        let query = if read_only { sql::SET_BASE_FLAG } else { sql::CLEAR_BASE_FLAG };
+        let tx = self.connection.unchecked_transaction()?;
+        tx.execute(query, [READ_ONLY])?;
+        self.audit_tx(&tx, if read_only { "read-only" } else { "read-write" }, &self.base_name, None, None, None)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Whether the base is read-only
+    pub fn is_read_only(&self) -> Result<bool> {
+        Ok(self.write_locks(&self.connection, WriteTarget::Base)?.0)
+    }
+
+    /// Whether the keystore or doc store of `context` is locked
+    pub fn is_locked(&self, context: &ResolvedContext) -> Result<bool> {
+        let target = match context.anchor {
+            Anchor::Var => self.keystore_id(&self.connection, context)?.map(WriteTarget::Keystore),
+            Anchor::Doc => self.docstore_id(&self.connection, context)?.map(WriteTarget::Docstore),
+        };
+        match target {
+            Some(target) => Ok(self.write_locks(&self.connection, target)?.1),
+            None => Ok(false),
+        }
+    }
+
+    /// Every locked namespace in the base
+    pub fn list_locks(&self) -> Result<Vec<LockInfo>> {
+        self.logger.trace_fn("database", "listing locks");
+
+        let mut stmt = self.connection.prepare(sql::LIST_LOCKS)?;
+        let rows = stmt.query_map([], |row| {
+            let (project, workspace, anchor): (String, String, String) = (row.get(0)?, row.get(1)?, row.get(2)?);
+            let chain = match row.get::<_, Option<String>>(3)? {
+                Some(keystore) => format!("{}.{}.{}.{}", project, workspace, anchor, keystore),
+                None => format!("{}.{}.{}", project, workspace, anchor),
+            };
+            Ok(LockInfo { chain, locked_at: row.get(4)? })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Fail with `BookdbError::Locked` if a write to `target` is not allowed
+    ///
+    /// Call on the write's own transaction so a lock taken meanwhile still applies.
+    pub(super) fn check_writable(&self, conn: &Connection, target: WriteTarget, what: impl fmt::Display) -> Result<()> {
+        let (read_only, locked) = self.write_locks(conn, target)?;
+        if read_only {
+            return Err(BookdbError::Locked(format!(
+                "base '{}' is read-only (bookdb unlock --base to allow writes)", self.base_name
+            )));
+        }
+        if locked {
+            return self.forced_past_lock(what);
+        }
+        Ok(())
+    }
+
+    /// `check_writable` for a whole subtree: `scope` (`project`, `project.workspace`
+    /// or a keystore chain) and every locked namespace under it
+    pub(super) fn check_scope_writable(&self, conn: &Connection, scope: &str) -> Result<()> {
+        self.check_writable(conn, WriteTarget::Base, scope)?;
+        let under = format!("{}.", scope);
+        match self.list_locks()?.into_iter().find(|lock| lock.chain == scope || lock.chain.starts_with(&under)) {
+            Some(lock) => self.forced_past_lock(lock.chain),
+            None => Ok(()),
+        }
+    }
+
+    fn forced_past_lock(&self, what: impl fmt::Display) -> Result<()> {
+        if !self.force.get() {
+            return Err(BookdbError::Locked(format!("{} is locked (use --force to write anyway)", what)));
+        }
+        self.logger.trace_fn("database", &format!("writing to locked {} (forced)", what));
+        Ok(())
+    }
+
+    fn write_locks(&self, conn: &Connection, target: WriteTarget) -> Result<(bool, bool)> {
+        let (kvns_id, ds_id) = match target {
+            WriteTarget::Base => (None, None),
+            WriteTarget::Keystore(kvns_id) => (Some(kvns_id), None),
+            WriteTarget::Docstore(ds_id) => (None, Some(ds_id)),
+        };
+        Ok(conn.query_row(sql::GET_WRITE_LOCKS, params![kvns_id, ds_id, READ_ONLY], |row| {
+            Ok((row.get::<_, bool>(0)?, row.get::<_, bool>(1)?))
+        })?)
+    }
+
+    /// ID of the doc store of `context`'s workspace, if it exists
+    pub(super) fn docstore_id(&self, conn: &Connection, context: &ResolvedContext) -> Result<Option<i64>> {
+        Ok(conn.query_row(
+            sql::GET_DOC_STORE,
+            params![&context.project, &context.workspace],
+            |row| row.get::<_, i64>(0),
+        ).optional()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bookdb::service::ctx::ChainMode;
+    use crate::bookdb::service::db::driver::ActionKind;
+    use tempfile::TempDir;
+
+    fn create_test_context() -> ResolvedContext {
+        ResolvedContext {
+            base: "test".to_string(),
+            project: "infra".to_string(),
+            workspace: "production".to_string(),
+            anchor: Anchor::Var,
+            tail: "credentials".to_string(),
+            prefix_mode: ChainMode::Persistent,
+        }
+    }
+
+    #[test]
+    fn test_locked_keystore() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::create_or_open(&temp_dir.path().join("test.sqlite3"))?;
+        let context = create_test_context();
+        db.set_variable("DB_HOST", "db1", &context)?;
+
+        db.set_namespace_lock(&context, true)?;
+        assert!(db.is_locked(&context)?);
+        assert!(matches!(db.set_variable("DB_HOST", "db2", &context), Err(BookdbError::Locked(_))));
+        assert!(matches!(db.delete_variable("DB_HOST", &context), Err(BookdbError::Locked(_))));
+        assert!(db.increment_variable("EPOCH", 1, &context).is_err());
+        assert_eq!(db.get_variable("DB_HOST", &context)?, Some("db1".to_string()));
+        assert_eq!(db.list_locks()?[0].chain, "infra.production.var.credentials");
+
+        // Other keystores are unaffected; --force gets through
+        db.set_variable("DB_HOST", "db2", &context.with_target("infra", "staging", "credentials"))?;
+        db.set_force(true);
+        db.set_variable("DB_HOST", "db2", &context)?;
+        db.set_force(false);
+
+        db.set_namespace_lock(&context, false)?;
+        db.set_variable("DB_HOST", "db3", &context)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_locked_keystore_blocks_deletes_above_it() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::create_or_open(&temp_dir.path().join("test.sqlite3"))?;
+        let context = create_test_context();
+        db.set_variable("DB_HOST", "db1", &context)?;
+        db.set_namespace_lock(&context, true)?;
+
+        assert!(matches!(db.delete_workspace("infra", "production"), Err(BookdbError::Locked(_))));
+        assert!(matches!(db.delete_project("infra"), Err(BookdbError::Locked(_))));
+        assert_eq!(db.get_variable("DB_HOST", &context)?, Some("db1".to_string()));
+
+        db.set_force(true);
+        assert!(db.delete_workspace("infra", "production")?);
+        db.set_variable("DB_HOST", "db1", &context.with_target("infra", "staging", "credentials"))?;
+        assert!(db.delete_project("infra")?);
+        assert!(db.list_projects()?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_only_base() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::create_or_open(&temp_dir.path().join("test.sqlite3"))?;
+        let context = create_test_context();
+        let docs = ResolvedContext { anchor: Anchor::Doc, ..context.with_target("infra", "production", "README") };
+
+        db.set_read_only(true)?;
+        db.set_force(true);
+        assert!(matches!(db.set_variable("A", "1", &context), Err(BookdbError::Locked(_))));
+        assert!(matches!(db.set_doc_segment("README", "/", "text/plain", b"hi", &docs), Err(BookdbError::Locked(_))));
+        assert!(db.create_keystore("infra", "production", "extra").is_err());
+        assert!(matches!(db.create_project("web"), Err(BookdbError::Locked(_))));
+        assert!(matches!(db.set_alias("prod", "@infra.production.var.credentials"), Err(BookdbError::Locked(_))));
+        assert!(matches!(db.set_action(&context, ActionKind::Dump, None), Err(BookdbError::Locked(_))));
+        assert!(matches!(db.set_namespace_lock(&context, true), Err(BookdbError::Locked(_))));
+
+        db.set_read_only(false)?;
+        db.set_variable("A", "1", &context)?;
+        Ok(())
+    }
+}
@@ -1,25 +1,48 @@
 // src/bookdb/service/db/driver/manager.rs - Multi-base database manager
 
-use crate::bookdb::app::sup::error::Result;
+use crate::bookdb::app::sup::error::{Result, BookdbError};
 use super::core::Database;
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+/// Registry of open bases; each base is a `$data_dir/<name>.sqlite3` file
 pub struct DatabaseManager {
+    pub data_dir: PathBuf,
     pub databases: HashMap<String, Database>,
     pub active_base: Option<String>,
+    /// Prepares every base opened here for encrypted keystores, like the working base
+    pub(super) unlocker: Option<Box<dyn Fn(&Database)>>,
 }
 
 impl DatabaseManager {
-    pub fn new() -> Self {
+    pub fn new(data_dir: PathBuf) -> Self {
         Self {
+            data_dir,
             databases: HashMap::new(),
             active_base: None,
+            unlocker: None,
         }
     }
 
+    /// Run `unlock` on each base as it is opened (passphrase or prompt)
+    pub fn set_unlocker(&mut self, unlock: impl Fn(&Database) + 'static) {
+        self.unlocker = Some(Box::new(unlock));
+    }
+
+    /// Open a base read-only, unlocked the same way as the working base
+    pub fn open_readonly(&self, path: &Path) -> Result<Database> {
+        let db = Database::open_readonly(path)?;
+        if let Some(unlock) = &self.unlocker {
+            unlock(&db);
+        }
+        Ok(db)
+    }
+
     pub fn add_base(&mut self, name: String, path: PathBuf) -> Result<()> {
-        let db = Database::connect(&path)?;
+        let db = Database::open(&path)?;
+        if let Some(unlock) = &self.unlocker {
+            unlock(&db);
+        }
         self.databases.insert(name, db);
         Ok(())
     }
@@ -32,12 +55,16 @@ impl DatabaseManager {
         }
     }
 
+    /// Make `name` the active base, opening it from the data dir if needed
     pub fn set_active(&mut self, name: String) -> Result<()> {
-        if self.databases.contains_key(&name) {
-            self.active_base = Some(name);
-            Ok(())
-        } else {
-            Err("Base not found".into())
+        if !self.databases.contains_key(&name) {
+            if !self.base_exists(&name) {
+                return Err(BookdbError::Database(format!("Base not found: {}", name)));
+            }
+            let path = self.base_path(&name);
+            self.add_base(name.clone(), path)?;
         }
+        self.active_base = Some(name);
+        Ok(())
     }
 }
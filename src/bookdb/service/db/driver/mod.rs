@@ -8,6 +8,23 @@ pub mod workspace;
 pub mod keystore;
 pub mod docstore;
 pub mod multibase;
+pub mod action;
+pub mod alias;
+pub mod wildcard;
+pub mod find;
+pub mod fts;
+pub mod rename;
+pub mod copy;
+pub mod history;
+pub mod journal;
+pub mod vartype;
+pub mod counter;
+pub mod condition;
+pub mod expiry;
+pub mod crypto;
+pub mod sensitive;
+pub mod lock;
+pub mod audit;
 
 pub mod manager; //for managing base.sqlite3 files multi-base support
 
@@ -17,5 +34,23 @@ pub mod manager; //for managing base.sqlite3 files multi-base support
 pub use core::Database;
 pub use dbutils::{ExportItem, BaseStats};
 pub use workspace::WorkspaceMetadata;
+pub use manager::DatabaseManager;
+pub use multibase::{BaseInfo, list_bases_in};
+pub use action::{ActionBinding, ActionKind};
+pub use docstore::DocSegmentInfo;
+pub use keystore::SetOptions;
+pub use wildcard::TaggedVariable;
+pub use find::{FindHit, FindMatcher};
+pub use fts::DocSearchHit;
+pub use copy::{ConflictPolicy, CopyAction, CopyItem};
+pub use history::{HistoryPoint, VarChange, VarOp};
+pub use journal::{JournalOp, JOURNAL_LIMIT};
+pub use vartype::VarType;
+pub use counter::{CounterOptions, Number};
+pub use condition::WriteCondition;
+pub use crypto::PASSPHRASE_ENV;
+pub use sensitive::{display_value, looks_sensitive, MASK};
+pub use lock::LockInfo;
+pub use audit::{parse_since, AuditEntry};
 
 // Modules are already declared as pub mod above, so they're accessible directly
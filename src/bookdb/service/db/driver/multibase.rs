@@ -1,4 +1,233 @@
-// multibase support commands
+// src/bookdb/service/db/driver/multibase.rs - Multi-base support commands
+//
+// A base is a standalone SQLite file in the data dir: `$data_dir/<name>.sqlite3`.
+// The registry is the directory itself - no separate index is kept.
 
+use crate::bookdb::app::sup::error::{Result, BookdbError};
+use super::core::Database;
+use super::manager::DatabaseManager;
+use std::fs;
+use std::path::{Path, PathBuf};
 
-// create new base
+/// File extension used for base databases
+pub const BASE_EXTENSION: &str = "sqlite3";
+
+/// The default base; it can be rebased but never unbased
+pub const HOME_BASE: &str = "home";
+
+/// Summary of a base file on disk
+#[derive(Debug, Clone)]
+pub struct BaseInfo {
+    pub name: String,
+    pub path: PathBuf,
+    pub size_bytes: u64,
+}
+
+/// Check that a base name is safe to use as a file stem
+pub fn validate_base_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if !valid {
+        return Err(BookdbError::Argument(format!(
+            "Invalid base name '{}': use letters, digits, '_' or '-'",
+            name
+        )));
+    }
+    Ok(())
+}
+
+/// Scan a data dir for `*.sqlite3` files, sorted by name
+pub fn list_bases_in(data_dir: &Path) -> Result<Vec<BaseInfo>> {
+    let mut bases = Vec::new();
+    if !data_dir.exists() {
+        return Ok(bases);
+    }
+
+    for entry in fs::read_dir(data_dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some(BASE_EXTENSION) {
+            continue;
+        }
+        let name = match path.file_stem().and_then(|s| s.to_str()) {
+            Some(stem) => stem.to_string(),
+            None => continue,
+        };
+        let size_bytes = fs::metadata(&path)?.len();
+        bases.push(BaseInfo { name, path, size_bytes });
+    }
+
+    bases.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(bases)
+}
+
+impl DatabaseManager {
+    /// Path of the file backing a base
+    pub fn base_path(&self, name: &str) -> PathBuf {
+        self.data_dir.join(format!("{}.{}", name, BASE_EXTENSION))
+    }
+
+    pub fn base_exists(&self, name: &str) -> bool {
+        self.base_path(name).exists()
+    }
+
+    /// List all bases in the data dir with their on-disk sizes
+    pub fn list_bases(&self) -> Result<Vec<BaseInfo>> {
+        list_bases_in(&self.data_dir)
+    }
+
+    /// Create a new base with the full schema; fails if it already exists
+    pub fn create_base(&mut self, name: &str) -> Result<PathBuf> {
+        validate_base_name(name)?;
+        if self.base_exists(name) {
+            return Err(BookdbError::Database(format!("Base already exists: {}", name)));
+        }
+
+        fs::create_dir_all(&self.data_dir)?;
+        let path = self.base_path(name);
+        let db = Database::create_or_open(&path)?;
+        if let Some(unlock) = &self.unlocker {
+            unlock(&db);
+        }
+        self.databases.insert(name.to_string(), db);
+        Ok(path)
+    }
+
+    /// Remove a base file (and any WAL/SHM sidecars) from disk
+    pub fn drop_base(&mut self, name: &str) -> Result<()> {
+        validate_base_name(name)?;
+        if name == HOME_BASE {
+            return Err(BookdbError::Argument("The home base cannot be removed".to_string()));
+        }
+        if self.active_base.as_deref() == Some(name) {
+            return Err(BookdbError::Argument(format!(
+                "Base '{}' is active; select another base first",
+                name
+            )));
+        }
+        if !self.base_exists(name) {
+            return Err(BookdbError::Database(format!("Base not found: {}", name)));
+        }
+
+        // Close our handle before the file goes away
+        self.databases.remove(name);
+
+        let path = self.base_path(name);
+        fs::remove_file(&path)?;
+        for suffix in ["-wal", "-shm"] {
+            let sidecar = PathBuf::from(format!("{}{}", path.display(), suffix));
+            if sidecar.exists() {
+                fs::remove_file(&sidecar)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Wipe a base and recreate it with an empty schema
+    pub fn recreate_base(&mut self, name: &str) -> Result<PathBuf> {
+        validate_base_name(name)?;
+        let was_active = self.active_base.as_deref() == Some(name);
+        if was_active {
+            self.active_base = None;
+        }
+
+        // drop_base refuses home; rebasing home is allowed, so remove it directly
+        if name == HOME_BASE {
+            self.databases.remove(name);
+            let path = self.base_path(name);
+            if path.exists() {
+                fs::remove_file(&path)?;
+            }
+        } else if self.base_exists(name) {
+            self.drop_base(name)?;
+        }
+
+        let path = self.create_base(name)?;
+        if was_active {
+            self.active_base = Some(name.to_string());
+        }
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_test_manager() -> (DatabaseManager, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = DatabaseManager::new(temp_dir.path().to_path_buf());
+        (manager, temp_dir)
+    }
+
+    #[test]
+    fn test_create_and_list_bases() -> Result<()> {
+        let (mut manager, _temp) = create_test_manager();
+
+        manager.create_base("work")?;
+        manager.create_base("alpha")?;
+
+        let bases = manager.list_bases()?;
+        let names: Vec<&str> = bases.iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(names, vec!["alpha", "work"]);
+        assert!(bases.iter().all(|b| b.size_bytes > 0));
+
+        // Schema is in place on a fresh base
+        let db = manager.databases.get("work").unwrap();
+        let tables: i64 = db.connection.query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name IN ('project_ns', 'keyval_ns', 'vars', 'doc_stores', 'docs', 'doc_segments')",
+            [],
+            |row| row.get(0),
+        )?;
+        assert_eq!(tables, 6);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_create_rejects_duplicates_and_bad_names() -> Result<()> {
+        let (mut manager, _temp) = create_test_manager();
+
+        manager.create_base("work")?;
+        assert!(manager.create_base("work").is_err());
+        assert!(manager.create_base("../escape").is_err());
+        assert!(manager.create_base("").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_drop_base_guards() -> Result<()> {
+        let (mut manager, _temp) = create_test_manager();
+
+        manager.create_base(HOME_BASE)?;
+        manager.create_base("scratch")?;
+        manager.create_base("work")?;
+        manager.set_active("work".to_string())?;
+
+        assert!(manager.drop_base(HOME_BASE).is_err());
+        assert!(manager.drop_base("work").is_err());
+
+        manager.drop_base("scratch")?;
+        assert!(!manager.base_exists("scratch"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_recreate_base_clears_data() -> Result<()> {
+        let (mut manager, _temp) = create_test_manager();
+
+        manager.create_base("work")?;
+        manager.databases.get("work").unwrap()
+            .connection.execute("INSERT INTO project_ns (pns_name) VALUES ('demo')", [])?;
+
+        manager.recreate_base("work")?;
+
+        let count: i64 = manager.databases.get("work").unwrap()
+            .connection.query_row("SELECT COUNT(*) FROM project_ns", [], |row| row.get(0))?;
+        assert_eq!(count, 0);
+
+        Ok(())
+    }
+}
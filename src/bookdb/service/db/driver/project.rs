@@ -1,9 +1,10 @@
 // src/db/project.rs - Project-level database operations
 
-use crate::error::Result;
+use crate::error::{Result, BookdbError};
 use crate::sql;
 use rusqlite::{params, Transaction};
 use super::Database;
+use super::lock::WriteTarget;
 
 impl Database {
     /// List all projects in the database
@@ -12,7 +13,7 @@ impl Database {
         
         let mut stmt = self.connection.prepare(sql::LIST_PROJECTS)?;
         let project_iter = stmt.query_map([], |row| {
-            Ok(row.get::<_, String>(0)?)
+            row.get::<_, String>(0)
         })?;
         
         let mut projects = Vec::new();
@@ -24,18 +25,20 @@ impl Database {
     }
     
     /// Ensure a project exists in the database
-    pub fn ensure_project_exists(&self, project: &str) ->  Result<(), E> {
+    pub fn ensure_project_exists(&self, project: &str) -> Result<()> {
         self.logger.trace_fn("database", &format!("ensuring project exists: {}", project));
         
-        self.connection.execute(sql::CREATE_PROJECT, params![project])?;
+        let tx = self.connection.unchecked_transaction()?;
+        self.ensure_project_exists_tx(&tx, project)?;
+        tx.commit()?;
         Ok(())
     }
     
     /// Ensure project exists within transaction, return project ID
     pub fn ensure_project_exists_tx(&self, tx: &Transaction, project: &str) -> Result<i64> {
-        let mut stmt = tx.prepare(sql::RESOLVE_PROJECT_ID)?;
+        let mut stmt = tx.prepare(sql::GET_PROJECT_ID)?;
         let mut rows = stmt.query_map([project], |row| {
-            Ok(row.get::<_, i64>(0)?)
+            row.get::<_, i64>(0)
         })?;
         
         match rows.next() {
@@ -49,17 +52,33 @@ impl Database {
         }
     }
     
-    /// Create a new project
-    pub fn create_project(&self, name: &str) ->  Result<(), E> {
+    /// Create a new project; fails if it already exists
+    pub fn create_project(&self, name: &str) ->  Result<()> {
         self.logger.trace_fn("database", &format!("creating project: {}", name));
         
-        self.connection.execute(sql::CREATE_PROJECT, params![name])?;
+        if self.list_projects()?.iter().any(|p| p == name) {
+            return Err(BookdbError::Database(format!("Project already exists: {}", name)));
+        }
+        let tx = self.connection.unchecked_transaction()?;
+        self.check_writable(&tx, WriteTarget::Base, name)?;
+        tx.execute(sql::CREATE_PROJECT, params![name])?;
+        self.audit_tx(&tx, "mkproject", name, None, None, None)?;
+        tx.commit()?;
         Ok(())
     }
     
-    /// Delete a project (stub - not implemented)
-    pub fn delete_project(&self, _name: &str) ->  Result<(), E> {
-        // TODO: Implement project deletion with cascade
-        todo!("Project deletion not yet implemented")
+    /// Delete a project with all its workspaces; false if it did not exist
+    pub fn delete_project(&self, name: &str) -> Result<bool> {
+        self.logger.trace_fn("database", &format!("deleting project: {}", name));
+        
+        let tx = self.connection.unchecked_transaction()?;
+        self.check_scope_writable(&tx, name)?;
+        let changes = tx.execute(sql::DELETE_PROJECT, params![name])?;
+        if changes > 0 {
+            self.audit_tx(&tx, "rmproject", name, None, None, None)?;
+            self.journal_irreversible_tx(&tx, "del project", &format!("{}@{}", self.base_name, name))?;
+        }
+        tx.commit()?;
+        Ok(changes > 0)
     }
 }
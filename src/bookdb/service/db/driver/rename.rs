@@ -0,0 +1,252 @@
+// src/db/rename.rs - `mv`: rename and relocate projects, workspaces and keystores
+//
+// A move runs in one transaction. When the destination already exists it
+// fails, unless merging, in which case the moved entries win over existing ones.
+
+use crate::error::{Result, BookdbError};
+use crate::bookdb::service::ctx::{Anchor, ChainLevel, ResolvedContext};
+use crate::sql;
+use rusqlite::{params, OptionalExtension, Transaction};
+use super::Database;
+
+impl Database {
+    /// Move the namespace `from` names to `to`; both must be on this base
+    pub fn move_namespace(&self, from: &ResolvedContext, to: &ResolvedContext, merge: bool) -> Result<ChainLevel> {
+        self.logger.trace_fn("database", &format!("moving {} -> {} (merge: {})", from, to, merge));
+
+        let level = from.level()?;
+        if to.level()? != level {
+            return Err(BookdbError::Argument(format!(
+                "Cannot move {} to {}: both chains must name a {:?}", from, to, level
+            )));
+        }
+        if level == ChainLevel::Tail && (from.anchor != Anchor::Var || to.anchor != Anchor::Var) {
+            return Err(BookdbError::Argument(
+                "mv moves keystores, workspaces and projects; use cp for single documents".to_string()
+            ));
+        }
+
+        let tx = self.connection.unchecked_transaction()?;
+        // A move rewrites both ends; locks on either (or anything inside) apply
+        let scopes = [from, to].map(|context| match level {
+            ChainLevel::Project => context.project.clone(),
+            ChainLevel::Workspace => format!("{}.{}", context.project, context.workspace),
+            ChainLevel::Tail => format!("{}.{}.var.{}", context.project, context.workspace, context.tail),
+        });
+        for scope in &scopes {
+            self.check_scope_writable(&tx, scope)?;
+        }
+        match level {
+            ChainLevel::Project => self.move_project_tx(&tx, &from.project, &to.project, merge)?,
+            ChainLevel::Workspace => self.move_workspace_tx(
+                &tx, (&from.project, &from.workspace), (&to.project, &to.workspace), merge
+            )?,
+            ChainLevel::Tail => self.move_keystore_tx(
+                &tx, (&from.project, &from.workspace, &from.tail), (&to.project, &to.workspace, &to.tail), merge
+            )?,
+        }
+        self.audit_tx(&tx, "mv", &scopes[0], Some(scopes[1].as_str()), None, None)?;
+        // Journal entries name the old location: undo cannot follow a move
+        self.journal_irreversible_tx(&tx, "mv", &format!("{}@{}", self.base_name, scopes[0]))?;
+        tx.commit()?;
+        Ok(level)
+    }
+
+    fn move_project_tx(&self, tx: &Transaction, from: &str, to: &str, merge: bool) -> Result<()> {
+        if from == to {
+            return Err(BookdbError::Argument(format!("Project '{}' is already there", from)));
+        }
+        if query_id(tx, sql::GET_PROJECT_ID, params![from])?.is_none() {
+            return Err(BookdbError::Database(format!("Project not found: {}", from)));
+        }
+
+        match query_id(tx, sql::GET_PROJECT_ID, params![to])? {
+            None => {
+                tx.execute(sql::RENAME_PROJECT, params![from, to])?;
+            }
+            Some(_) if !merge => {
+                return Err(BookdbError::Database(format!(
+                    "Project already exists: {} (use --merge to combine)", to
+                )));
+            }
+            Some(_) => {
+                for workspace in query_names(tx, sql::LIST_WORKSPACES, params![from])? {
+                    self.move_workspace_tx(tx, (from, &workspace), (to, &workspace), true)?;
+                }
+                tx.execute(sql::DELETE_PROJECT, params![from])?;
+            }
+        }
+        Ok(())
+    }
+
+    fn move_workspace_tx(&self, tx: &Transaction, from: (&str, &str), to: (&str, &str), merge: bool) -> Result<()> {
+        if from == to {
+            return Err(BookdbError::Argument(format!("Workspace {}.{} is already there", from.0, from.1)));
+        }
+
+        let keystores = query_names(tx, sql::LIST_KEYSTORES, params![from.0, from.1])?;
+        let doc_store = query_id(tx, sql::GET_DOC_STORE, params![from.0, from.1])?;
+        if keystores.is_empty() && doc_store.is_none() {
+            return Err(BookdbError::Database(format!("Workspace not found: {}.{}", from.0, from.1)));
+        }
+
+        let to_exists = !query_names(tx, sql::LIST_KEYSTORES, params![to.0, to.1])?.is_empty()
+            || query_id(tx, sql::GET_DOC_STORE, params![to.0, to.1])?.is_some();
+        if to_exists && !merge {
+            return Err(BookdbError::Database(format!(
+                "Workspace already exists: {}.{} (use --merge to combine)", to.0, to.1
+            )));
+        }
+
+        for keystore in &keystores {
+            self.move_keystore_tx(tx, (from.0, from.1, keystore), (to.0, to.1, keystore), merge)?;
+        }
+
+        if let Some(src) = doc_store {
+            let project_id = self.ensure_project_exists_tx(tx, to.0)?;
+            match query_id(tx, sql::GET_DOC_STORE, params![to.0, to.1])? {
+                None => {
+                    tx.execute(sql::MOVE_DOC_STORE, params![project_id, to.1, src])?;
+                }
+                Some(dst) => {
+                    tx.execute(sql::DROP_CONFLICTING_DOCS, params![dst, src])?;
+                    tx.execute(sql::MOVE_DOCS, params![dst, src])?;
+                    tx.execute(sql::DELETE_DOC_STORE, params![from.0, from.1])?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn move_keystore_tx(&self, tx: &Transaction, from: (&str, &str, &str), to: (&str, &str, &str), merge: bool) -> Result<()> {
+        let src = query_id(tx, sql::GET_KEYSTORE_ID, params![from.0, from.1, from.2])?
+            .ok_or_else(|| BookdbError::Database(format!(
+                "Keystore not found: {}.{}.var.{}", from.0, from.1, from.2
+            )))?;
+        let project_id = self.ensure_project_exists_tx(tx, to.0)?;
+
+        match query_id(tx, sql::GET_KEYSTORE_ID, params![to.0, to.1, to.2])? {
+            None => {
+                tx.execute(sql::MOVE_KEYSTORE, params![to.2, project_id, to.1, src])?;
+            }
+            Some(dst) if dst == src => {
+                return Err(BookdbError::Argument(format!(
+                    "Keystore {}.{}.var.{} is already there", from.0, from.1, from.2
+                )));
+            }
+            Some(_) if !merge => {
+                return Err(BookdbError::Database(format!(
+                    "Keystore already exists: {}.{}.var.{} (use --merge to combine)", to.0, to.1, to.2
+                )));
+            }
+            Some(dst) => {
+                // Sealed values are bound to their keystore's key; merging would strand them
+                let encrypted = |id: i64| tx.query_row(sql::GET_KEYSTORE_CRYPTO, [id], |_| Ok(())).optional();
+                if encrypted(src)?.is_some() || encrypted(dst)?.is_some() {
+                    return Err(BookdbError::Argument(format!(
+                        "Cannot merge encrypted keystores; decrypt {}.{}.var.{} and {}.{}.var.{} first",
+                        from.0, from.1, from.2, to.0, to.1, to.2
+                    )));
+                }
+                let rev = self.last_revision(tx)?;
+                tx.execute(sql::RECORD_MERGE_HISTORY, params![dst, src])?;
+                self.audit_history_since_tx(tx, rev, "merge")?;
+                tx.execute(sql::MERGE_VARS, params![dst, src])?;
+                tx.execute(sql::DELETE_KEYSTORE, params![from.0, from.1, from.2])?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// First id column of a lookup, if any row matched
+fn query_id(tx: &Transaction, query: &str, params: impl rusqlite::Params) -> Result<Option<i64>> {
+    Ok(tx.query_row(query, params, |row| row.get::<_, i64>(0)).optional()?)
+}
+
+/// First text column of every row
+fn query_names(tx: &Transaction, query: &str, params: impl rusqlite::Params) -> Result<Vec<String>> {
+    let mut stmt = tx.prepare(query)?;
+    let rows = stmt.query_map(params, |row| row.get::<_, String>(0))?;
+
+    let mut names = Vec::new();
+    for name in rows {
+        names.push(name?);
+    }
+    Ok(names)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bookdb::service::ctx::ChainMode;
+    use tempfile::TempDir;
+
+    fn create_test_context(project: &str, workspace: &str, tail: &str) -> ResolvedContext {
+        ResolvedContext {
+            base: "test".to_string(),
+            project: project.to_string(),
+            workspace: workspace.to_string(),
+            anchor: Anchor::Var,
+            tail: tail.to_string(),
+            prefix_mode: ChainMode::Persistent,
+        }
+    }
+
+    fn create_test_db() -> (Database, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::create_or_open(&temp_dir.path().join("test.sqlite3")).unwrap();
+        db.set_variable("DB_URL", "prod-db", &create_test_context("app", "prod", "config")).unwrap();
+        db.set_variable("DB_URL", "stage-db", &create_test_context("app", "staging", "config")).unwrap();
+        db.set_variable("TOKEN", "t", &create_test_context("app", "staging", "secrets")).unwrap();
+        (db, temp_dir)
+    }
+
+    #[test]
+    fn test_move_keystore_and_rename_workspace() -> Result<()> {
+        let (db, _temp) = create_test_db();
+
+        db.move_namespace(
+            &create_test_context("app", "staging", "secrets"),
+            &create_test_context("other", "ops", "vault"),
+            false,
+        )?;
+        assert_eq!(db.get_variable("TOKEN", &create_test_context("other", "ops", "vault"))?, Some("t".to_string()));
+        assert_eq!(db.list_keystores("app", "staging")?, vec!["config"]);
+
+        db.move_namespace(&create_test_context("app", "staging", "*"), &create_test_context("app", "stage", "*"), false)?;
+        assert_eq!(db.list_workspaces("app")?, vec!["prod", "stage"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_move_collisions() -> Result<()> {
+        let (db, _temp) = create_test_db();
+        let staging = create_test_context("app", "staging", "*");
+        let prod = create_test_context("app", "prod", "*");
+
+        // Fails without --merge and leaves everything in place
+        assert!(db.move_namespace(&staging, &prod, false).is_err());
+        assert_eq!(db.list_workspaces("app")?, vec!["prod", "staging"]);
+
+        db.move_namespace(&staging, &prod, true)?;
+        assert_eq!(db.list_workspaces("app")?, vec!["prod"]);
+        assert_eq!(db.get_variable("DB_URL", &create_test_context("app", "prod", "config"))?, Some("stage-db".to_string()));
+        assert_eq!(db.list_keystores("app", "prod")?, vec!["config", "secrets"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rename_project() -> Result<()> {
+        let (db, _temp) = create_test_db();
+
+        db.move_namespace(&create_test_context("app", "*", "*"), &create_test_context("webapp", "*", "*"), false)?;
+        assert_eq!(db.list_projects()?, vec!["webapp"]);
+        assert!(db.move_namespace(&create_test_context("app", "*", "*"), &create_test_context("x", "*", "*"), false).is_err());
+        assert!(db.move_namespace(&create_test_context("webapp", "*", "*"), &create_test_context("x", "prod", "*"), false).is_err());
+
+        Ok(())
+    }
+}
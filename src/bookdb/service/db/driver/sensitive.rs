@@ -0,0 +1,138 @@
+// src/db/sensitive.rs - Sensitive values: masked in listings, previews and traces
+//
+// A value is sensitive when its key is marked (`bookdb sensitive KEY`), its
+// keystore is marked (`bookdb sensitive` with no key) or encrypted, or its name
+// looks like a secret (`*_KEY`, `*PASSWORD*`, `*TOKEN*`, `*SECRET*`). Masking is
+// for display only; `getv` and `export` still hand out the real value.
+
+use crate::error::{Result, BookdbError};
+use crate::bookdb::service::ctx::ResolvedContext;
+use crate::sql;
+use rusqlite::{params, Connection, Transaction};
+use super::Database;
+use super::lock::WriteTarget;
+
+/// What a masked value is shown as
+pub const MASK: &str = "********";
+
+/// Sensitive-marks a whole keystore rather than one key
+const WHOLE_KEYSTORE: &str = "*";
+
+/// Whether a key's name alone marks its value as sensitive
+pub fn looks_sensitive(key: &str) -> bool {
+    let key = key.to_ascii_uppercase();
+    key.ends_with("_KEY") || ["PASSWORD", "TOKEN", "SECRET"].iter().any(|word| key.contains(word))
+}
+
+/// A value as it may be displayed: masked if sensitive, unless revealed
+pub fn display_value(value: &str, sensitive: bool, reveal: bool) -> String {
+    if sensitive && !reveal {
+        MASK.to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+impl Database {
+    /// Mark (or with `sensitive = false`, unmark) a key, or the whole keystore when `key` is None
+    ///
+    /// Name heuristics cannot be switched off by unmarking; `--reveal` shows those.
+    pub fn mark_sensitive(&self, key: Option<&str>, sensitive: bool, context: &ResolvedContext) -> Result<()> {
+        self.logger.trace_fn("database", &format!("marking {:?} sensitive: {} in context: {}", key, sensitive, context));
+        let tx = self.connection.unchecked_transaction()?;
+        self.mark_sensitive_tx(&tx, key, sensitive, context)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// `mark_sensitive` inside a caller's transaction
+    pub(super) fn mark_sensitive_tx(&self, tx: &Transaction, key: Option<&str>, sensitive: bool, context: &ResolvedContext) -> Result<()> {
+        let mark = key.unwrap_or(WHOLE_KEYSTORE);
+
+        self.check_writable(tx, WriteTarget::Base, context)?;
+        if sensitive {
+            let kvns_id = self.ensure_keystore_context_exists(tx, context)?;
+            tx.execute(sql::MARK_SENSITIVE, params![mark, kvns_id])?;
+        } else {
+            let kvns_id = self.keystore_id(tx, context)?
+                .ok_or_else(|| BookdbError::KeyNotFound(format!("keystore {}", context)))?;
+            tx.execute(sql::UNMARK_SENSITIVE, params![mark, kvns_id])?;
+        }
+        self.audit_tx(tx, if sensitive { "mark-sensitive" } else { "unmark-sensitive" }, context, key, None, None)?;
+        Ok(())
+    }
+
+    /// Whether the value of `key` in `context` is sensitive
+    pub fn is_sensitive(&self, key: &str, context: &ResolvedContext) -> Result<bool> {
+        if looks_sensitive(key) {
+            return Ok(true);
+        }
+        match self.keystore_id(&self.connection, context)? {
+            Some(kvns_id) => self.is_sensitive_in(&self.connection, kvns_id, key),
+            None => Ok(false),
+        }
+    }
+
+    /// Whether the value of `key` in keystore `kvns_id` is sensitive
+    pub(super) fn is_sensitive_in(&self, conn: &Connection, kvns_id: i64, key: &str) -> Result<bool> {
+        if looks_sensitive(key) {
+            return Ok(true);
+        }
+        Ok(conn.query_row(sql::IS_MARKED_SENSITIVE, params![kvns_id, key], |row| row.get::<_, bool>(0))?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bookdb::service::ctx::{Anchor, ChainMode};
+    use tempfile::TempDir;
+
+    fn create_test_context() -> ResolvedContext {
+        ResolvedContext {
+            base: "test".to_string(),
+            project: "app".to_string(),
+            workspace: "ci".to_string(),
+            anchor: Anchor::Var,
+            tail: "deploy".to_string(),
+            prefix_mode: ChainMode::Persistent,
+        }
+    }
+
+    #[test]
+    fn test_name_heuristics() {
+        assert!(looks_sensitive("AWS_SECRET_ACCESS_KEY"));
+        assert!(looks_sensitive("api_key"));
+        assert!(looks_sensitive("DB_PASSWORD_FILE"));
+        assert!(looks_sensitive("GithubToken"));
+        assert!(!looks_sensitive("KEYBOARD"));
+        assert!(!looks_sensitive("HOST"));
+        assert_eq!(display_value("hunter2", true, false), MASK);
+        assert_eq!(display_value("hunter2", true, true), "hunter2");
+    }
+
+    #[test]
+    fn test_marks() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::create_or_open(&temp_dir.path().join("test.sqlite3"))?;
+        let context = create_test_context();
+        db.set_variable("DSN", "postgres://u:p@db", &context)?;
+        db.set_variable("HOST", "db", &context)?;
+
+        assert!(!db.is_sensitive("DSN", &context)?);
+        db.mark_sensitive(Some("DSN"), true, &context)?;
+        db.mark_sensitive(Some("DSN"), true, &context)?;
+        assert!(db.is_sensitive("DSN", &context)?);
+        assert!(!db.is_sensitive("HOST", &context)?);
+
+        db.mark_sensitive(None, true, &context)?;
+        assert!(db.is_sensitive("HOST", &context)?);
+        db.mark_sensitive(None, false, &context)?;
+        db.mark_sensitive(Some("DSN"), false, &context)?;
+        assert!(!db.is_sensitive("DSN", &context)?);
+
+        // Heuristics hold regardless of marks
+        assert!(db.is_sensitive("DEPLOY_TOKEN", &context)?);
+        Ok(())
+    }
+}
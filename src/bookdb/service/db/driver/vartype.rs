@@ -0,0 +1,249 @@
+// src/db/vartype.rs - Variable types: validation on write, native JSON on read
+//
+// A type is optional per variable (vars.var_type, NULL = plain string). Typed
+// values are validated and stored in a canonical text form, so "true " or
+// " 5" never reach the database.
+
+use crate::error::{Result, BookdbError};
+use serde_json::Value;
+use std::time::Duration;
+
+/// Type of a variable; `Str` is the untyped default
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VarType {
+    Str,
+    Int,
+    Float,
+    Bool,
+    Json,
+    Duration,
+}
+
+impl VarType {
+    /// Parse a type name as given to `--type` or `KEY:type=`
+    pub fn parse(name: &str) -> Result<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "str" | "string" => Ok(VarType::Str),
+            "int" | "integer" => Ok(VarType::Int),
+            "float" | "number" => Ok(VarType::Float),
+            "bool" | "boolean" => Ok(VarType::Bool),
+            "json" => Ok(VarType::Json),
+            "duration" => Ok(VarType::Duration),
+            other => Err(BookdbError::Argument(format!(
+                "Unknown type '{}' (expected str, int, float, bool, json or duration)", other
+            ))),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VarType::Str => "str",
+            VarType::Int => "int",
+            VarType::Float => "float",
+            VarType::Bool => "bool",
+            VarType::Json => "json",
+            VarType::Duration => "duration",
+        }
+    }
+
+    /// Value for the var_type column; strings are stored untyped (NULL)
+    pub fn to_column(self) -> Option<&'static str> {
+        match self {
+            VarType::Str => None,
+            other => Some(other.as_str()),
+        }
+    }
+
+    /// Read the var_type column back
+    pub fn from_column(column: Option<&str>) -> Result<Self> {
+        column.map_or(Ok(VarType::Str), VarType::parse)
+    }
+
+    /// Validate `raw` for this type and return its canonical stored form
+    pub fn normalize(&self, key: &str, raw: &str) -> Result<String> {
+        let mismatch = || BookdbError::TypeMismatch(key.to_string(), self.as_str().to_string(), raw.to_string());
+        let value = raw.trim();
+
+        match self {
+            VarType::Str => Ok(raw.to_string()),
+            VarType::Int => value.parse::<i64>().map(|n| n.to_string()).map_err(|_| mismatch()),
+            VarType::Float => match value.parse::<f64>() {
+                Ok(n) if n.is_finite() => Ok(format_float(n)),
+                _ => Err(mismatch()),
+            },
+            VarType::Bool => match value.to_ascii_lowercase().as_str() {
+                "true" | "yes" | "on" | "1" => Ok("true".to_string()),
+                "false" | "no" | "off" | "0" => Ok("false".to_string()),
+                _ => Err(mismatch()),
+            },
+            VarType::Json => serde_json::from_str::<Value>(value)
+                .map(|json| json.to_string())
+                .map_err(|_| mismatch()),
+            VarType::Duration => parse_duration(value).map(format_duration).ok_or_else(mismatch),
+        }
+    }
+
+    /// A stored value as native JSON; durations become seconds
+    pub fn to_json(self, stored: &str) -> Value {
+        let native = match self {
+            VarType::Str => None,
+            VarType::Int => stored.parse::<i64>().ok().map(Value::from),
+            VarType::Float => stored.parse::<f64>().ok().map(Value::from),
+            VarType::Bool => stored.parse::<bool>().ok().map(Value::from),
+            VarType::Json => serde_json::from_str(stored).ok(),
+            VarType::Duration => parse_duration(stored).map(|d| match d.subsec_millis() {
+                0 => Value::from(d.as_secs()),
+                _ => Value::from(d.as_secs_f64()),
+            }),
+        };
+        native.unwrap_or_else(|| Value::String(stored.to_string()))
+    }
+}
+
+/// Split `KEY:type` (from `setv KEY:type=value`) into the key and its type
+pub fn split_typed_key(raw: &str) -> Result<(&str, Option<VarType>)> {
+    match raw.rsplit_once(':') {
+        Some((key, type_name)) => Ok((key, Some(VarType::parse(type_name)?))),
+        None => Ok((raw, None)),
+    }
+}
+
+/// Floats always keep a decimal point (`5.0`, not `5`)
+pub fn format_float(n: f64) -> String {
+    format!("{:?}", n)
+}
+
+/// Parse `1h30m`, `90s`, `500ms`, `2d`, `1w`; a bare number is seconds
+pub fn parse_duration(raw: &str) -> Option<Duration> {
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return None;
+    }
+    if let Ok(secs) = raw.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+
+    let mut total = Duration::ZERO;
+    let mut rest = raw;
+    while !rest.is_empty() {
+        let digits = rest.find(|c: char| !c.is_ascii_digit())?;
+        if digits == 0 {
+            return None;
+        }
+        let amount: u64 = rest[..digits].parse().ok()?;
+        rest = &rest[digits..];
+
+        let unit_len = rest.find(|c: char| c.is_ascii_digit()).unwrap_or(rest.len());
+        let unit_millis: u64 = match &rest[..unit_len] {
+            "ms" => 1,
+            "s" => 1_000,
+            "m" => 60_000,
+            "h" => 3_600_000,
+            "d" => 86_400_000,
+            "w" => 604_800_000,
+            _ => return None,
+        };
+        rest = &rest[unit_len..];
+        total = total.checked_add(Duration::from_millis(amount.checked_mul(unit_millis)?))?;
+    }
+    Some(total)
+}
+
+/// Canonical duration text, largest units first: `1h30m`, `1s500ms`, `0s`
+pub fn format_duration(duration: Duration) -> String {
+    let mut millis = duration.as_millis();
+    if millis == 0 {
+        return "0s".to_string();
+    }
+
+    let mut text = String::new();
+    for (unit, size) in [("d", 86_400_000), ("h", 3_600_000), ("m", 60_000), ("s", 1_000), ("ms", 1)] {
+        if millis >= size {
+            text.push_str(&format!("{}{}", millis / size, unit));
+            millis %= size;
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bookdb::service::ctx::{Anchor, ChainMode, ResolvedContext};
+    use crate::bookdb::service::db::Database;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_normalize_per_type() {
+        assert_eq!(VarType::Int.normalize("K", " 42 ").unwrap(), "42");
+        assert_eq!(VarType::Float.normalize("K", "5").unwrap(), "5.0");
+        assert_eq!(VarType::Bool.normalize("K", "true ").unwrap(), "true");
+        assert_eq!(VarType::Bool.normalize("K", "Off").unwrap(), "false");
+        assert_eq!(VarType::Json.normalize("K", r#"{ "a": [1, 2] }"#).unwrap(), r#"{"a":[1,2]}"#);
+        assert_eq!(VarType::Duration.normalize("K", "90m").unwrap(), "1h30m");
+        assert_eq!(VarType::Str.normalize("K", " as is ").unwrap(), " as is ");
+
+        assert!(matches!(VarType::Int.normalize("K", "4.5"), Err(BookdbError::TypeMismatch(..))));
+        assert!(VarType::Float.normalize("K", "NaN").is_err());
+        assert!(VarType::Bool.normalize("K", "maybe").is_err());
+        assert!(VarType::Json.normalize("K", "{").is_err());
+        assert!(VarType::Duration.normalize("K", "5 minutes").is_err());
+    }
+
+    #[test]
+    fn test_to_json_is_native() {
+        assert_eq!(VarType::Int.to_json("5"), serde_json::json!(5));
+        assert_eq!(VarType::Float.to_json("0.5"), serde_json::json!(0.5));
+        assert_eq!(VarType::Bool.to_json("false"), serde_json::json!(false));
+        assert_eq!(VarType::Json.to_json(r#"{"a":1}"#), serde_json::json!({"a": 1}));
+        assert_eq!(VarType::Duration.to_json("1m"), serde_json::json!(60));
+        assert_eq!(VarType::Duration.to_json("1s500ms"), serde_json::json!(1.5));
+        assert_eq!(VarType::Str.to_json("5"), serde_json::json!("5"));
+    }
+
+    #[test]
+    fn test_durations_and_typed_keys() {
+        assert_eq!(parse_duration("1h30m"), Some(Duration::from_secs(5400)));
+        assert_eq!(parse_duration("250ms"), Some(Duration::from_millis(250)));
+        assert_eq!(parse_duration("45"), Some(Duration::from_secs(45)));
+        assert_eq!(parse_duration("h"), None);
+        assert_eq!(format_duration(Duration::from_millis(90_061_001)), "1d1h1m1s1ms");
+
+        assert_eq!(split_typed_key("PORT:int").unwrap(), ("PORT", Some(VarType::Int)));
+        assert_eq!(split_typed_key("PORT").unwrap(), ("PORT", None));
+        assert!(split_typed_key("PORT:uuid").is_err());
+    }
+
+    #[test]
+    fn test_typed_variables_in_database() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::create_or_open(&temp_dir.path().join("test.sqlite3"))?;
+        let context = ResolvedContext {
+            base: "test".to_string(),
+            project: "app".to_string(),
+            workspace: "prod".to_string(),
+            anchor: Anchor::Var,
+            tail: "config".to_string(),
+            prefix_mode: ChainMode::Persistent,
+        };
+
+        assert_eq!(db.set_typed_variable("DEBUG", "true ", Some(VarType::Bool), &context)?, "true");
+        assert_eq!(db.get_variable_type("DEBUG", &context)?, VarType::Bool);
+
+        // The type sticks: later untyped writes are validated against it
+        assert!(db.set_variable("DEBUG", "sometimes", &context).is_err());
+        db.set_variable("DEBUG", "no", &context)?;
+        assert_eq!(db.get_variable("DEBUG", &context)?, Some("false".to_string()));
+
+        db.set_typed_variable("RATE", "0.5", Some(VarType::Float), &context)?;
+        assert_eq!(db.increment_variable("RATE", 2, &context)?, "2.5");
+        db.set_typed_variable("TIMEOUT", "1m", Some(VarType::Duration), &context)?;
+        assert_eq!(db.increment_variable("TIMEOUT", 30, &context)?, "1m30s");
+        assert!(db.decrement_variable("TIMEOUT", 600, &context).is_err());
+
+        // Str drops the type again
+        db.set_typed_variable("DEBUG", "sometimes", Some(VarType::Str), &context)?;
+        assert_eq!(db.get_variable_type("DEBUG", &context)?, VarType::Str);
+        Ok(())
+    }
+}
@@ -0,0 +1,214 @@
+// src/db/wildcard.rs - Fan-out reads for wildcard chains (`@myapp.*.var.secrets`)
+//
+// Cross-base chains (`*@ROOT.GLOBAL.var.MAIN`) go through `DatabaseManager`,
+// which opens each matching base read-only and runs the same reads on it.
+
+use crate::error::Result;
+use crate::bookdb::service::ctx::{glob_match, Anchor, ResolvedContext};
+use super::{Database, DatabaseManager, ExportItem};
+
+/// A variable found through a wildcard chain, tagged with where it lives
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaggedVariable {
+    /// Fully-qualified chain of the keystore, `base@project.workspace.var.keystore`
+    pub chain: String,
+    pub key: String,
+    pub value: String,
+    /// Mask `value` when displaying it (see `sensitive.rs`)
+    pub sensitive: bool,
+}
+
+impl Database {
+    /// Expand a wildcard context into every concrete context it matches
+    ///
+    /// Projects, workspaces and keystores (or document keys) are matched in
+    /// listing order. A non-wildcard context expands to itself.
+    pub fn expand_wildcard(&self, context: &ResolvedContext) -> Result<Vec<ResolvedContext>> {
+        if !context.is_wildcard() {
+            return Ok(vec![context.clone()]);
+        }
+        self.logger.trace_fn("database", &format!("expanding wildcard context: {}", context));
+
+        // Doc tails may carry a segment path; only the document key is matched
+        let (tail_pattern, tail_suffix) = match context.anchor {
+            Anchor::Var => (context.tail.as_str(), ""),
+            Anchor::Doc => {
+                let doc_key = context.doc_key();
+                (doc_key, &context.tail[doc_key.len()..])
+            }
+        };
+
+        let mut matches = Vec::new();
+        for project in self.list_projects()? {
+            if !glob_match(&context.project, &project) {
+                continue;
+            }
+            for workspace in self.list_workspaces(&project)? {
+                if !glob_match(&context.workspace, &workspace) {
+                    continue;
+                }
+                let tails = match context.anchor {
+                    Anchor::Var => self.list_keystores(&project, &workspace)?,
+                    Anchor::Doc => self.list_documents(&context.with_target(&project, &workspace, ""))?,
+                };
+                for tail in tails.iter().filter(|t| glob_match(tail_pattern, t)) {
+                    let full_tail = format!("{}{}", tail, tail_suffix);
+                    matches.push(context.with_target(&project, &workspace, &full_tail));
+                }
+            }
+        }
+
+        self.logger.trace_fn("database", &format!("wildcard matched {} contexts", matches.len()));
+        Ok(matches)
+    }
+
+    /// Every variable under a (possibly wildcard) var context, sorted by key per keystore
+    pub fn collect_variables(&self, context: &ResolvedContext) -> Result<Vec<TaggedVariable>> {
+        let mut tagged = Vec::new();
+        for target in self.expand_wildcard(context)? {
+            let chain = target.qualified();
+            let mut variables: Vec<(String, String)> = self.list_variables(&target)?.into_iter().collect();
+            variables.sort();
+            for (key, value) in variables {
+                tagged.push(TaggedVariable {
+                    chain: chain.clone(),
+                    sensitive: self.is_sensitive(&key, &target)?,
+                    key,
+                    value,
+                });
+            }
+        }
+        Ok(tagged)
+    }
+
+    /// Export items for a wildcard context, each tagged with its fully-qualified chain
+    pub fn export_wildcard(&self, context: &ResolvedContext, key_filter: Option<&str>) -> Result<Vec<ExportItem>> {
+        self.logger.trace_fn("database", &format!("exporting wildcard context: {}", context));
+
+        let mut items = Vec::new();
+        match context.anchor {
+            Anchor::Var => {
+                for var in self.collect_variables(context)? {
+                    if key_filter.is_none_or(|k| k == var.key) {
+                        items.push(ExportItem {
+                            item_type: "variable".to_string(),
+                            key: var.key,
+                            value: var.value,
+                            context: var.chain,
+                            sensitive: var.sensitive,
+                        });
+                    }
+                }
+            }
+            Anchor::Doc => {
+                for target in self.expand_wildcard(context)? {
+                    let doc_key = target.doc_key().to_string();
+                    if key_filter.is_none_or(|k| k == doc_key) {
+                        items.push(ExportItem {
+                            item_type: "document".to_string(),
+                            value: self.get_document(&doc_key, &target)?.unwrap_or_default(),
+                            key: doc_key,
+                            context: target.qualified(),
+                            sensitive: false,
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(items)
+    }
+}
+
+impl DatabaseManager {
+    /// Run a read on every base a cross-base context matches, merging results
+    ///
+    /// Each base is opened read-only and handed the context with its own base
+    /// name, so tagged chains carry the base. A single-base context just runs
+    /// against that base.
+    pub fn fan_out<T, F>(&self, context: &ResolvedContext, mut read: F) -> Result<Vec<T>>
+    where
+        F: FnMut(&Database, &ResolvedContext) -> Result<Vec<T>>,
+    {
+        let mut results = Vec::new();
+        for base in self.list_bases()? {
+            if !glob_match(&context.base, &base.name) {
+                continue;
+            }
+            let database = self.open_readonly(&base.path)?;
+            results.extend(read(&database, &context.with_base(&base.name))?);
+        }
+        Ok(results)
+    }
+
+    /// Every variable under a context, across all bases it matches
+    pub fn collect_variables_all(&self, context: &ResolvedContext) -> Result<Vec<TaggedVariable>> {
+        self.fan_out(context, |database, target| database.collect_variables(target))
+    }
+
+    /// Export items for a context, across all bases it matches
+    pub fn export_all(&self, context: &ResolvedContext, key_filter: Option<&str>) -> Result<Vec<ExportItem>> {
+        self.fan_out(context, |database, target| database.export_wildcard(target, key_filter))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bookdb::service::ctx::ChainMode;
+    use crate::error::BookdbError;
+    use tempfile::TempDir;
+
+    fn create_test_context() -> ResolvedContext {
+        ResolvedContext {
+            base: "*".to_string(),
+            project: "ROOT".to_string(),
+            workspace: "GLOBAL".to_string(),
+            anchor: Anchor::Var,
+            tail: "MAIN".to_string(),
+            prefix_mode: ChainMode::Ephemeral,
+        }
+    }
+
+    #[test]
+    fn test_fan_out_across_bases() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let mut manager = DatabaseManager::new(temp_dir.path().to_path_buf());
+        manager.create_base("home")?;
+        manager.create_base("work")?;
+
+        let context = create_test_context();
+        manager.databases.get("home").unwrap().set_variable("TOKEN", "h", &context.with_base("home"))?;
+        manager.databases.get("work").unwrap().set_variable("TOKEN", "w", &context.with_base("work"))?;
+
+        let tagged = manager.collect_variables_all(&context)?;
+        let chains: Vec<&str> = tagged.iter().map(|v| v.chain.as_str()).collect();
+        assert_eq!(chains, vec!["home@ROOT.GLOBAL.var.MAIN", "work@ROOT.GLOBAL.var.MAIN"]);
+
+        let items = manager.export_all(&context.with_base("w*"), Some("TOKEN"))?;
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].value, "w");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fan_out_unlocks_every_base() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let mut manager = DatabaseManager::new(temp_dir.path().to_path_buf());
+        manager.create_base("work")?;
+
+        let context = create_test_context().with_base("work");
+        let work = manager.databases.get("work").unwrap();
+        work.unlock("fixed passphrase");
+        work.set_variable("TOKEN", "w", &context)?;
+        work.encrypt_keystore(&context)?;
+
+        // Opened fresh, the base needs the passphrase the unlocker supplies
+        let all = create_test_context();
+        assert!(matches!(manager.collect_variables_all(&all), Err(BookdbError::PassphraseRequired(_))));
+        manager.set_unlocker(|database| database.unlock("fixed passphrase"));
+        assert_eq!(manager.collect_variables_all(&all)?[0].value, "w");
+        Ok(())
+    }
+}
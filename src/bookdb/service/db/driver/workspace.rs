@@ -1,9 +1,13 @@
 // src/db/workspace.rs - Workspace-level database operations
 
-use crate::error::Result;
+use crate::error::{Result, BookdbError};
 use crate::sql;
+use rusqlite::params;
 use super::Database;
 
+/// Keystore a new workspace starts with (matches the superchain `ROOT.GLOBAL.var.MAIN`)
+pub const DEFAULT_KEYSTORE: &str = "MAIN";
+
 impl Database {
     /// List all workspaces in a project
     pub fn list_workspaces(&self, project: &str) -> Result<Vec<String>> {
@@ -11,7 +15,7 @@ impl Database {
         
         let mut stmt = self.connection.prepare(sql::LIST_WORKSPACES)?;
         let workspace_iter = stmt.query_map([project], |row| {
-            Ok(row.get::<_, String>(0)?)
+            row.get::<_, String>(0)
         })?;
         
         let mut workspaces = Vec::new();
@@ -23,7 +27,7 @@ impl Database {
     }
     
     /// Ensure a workspace exists within a project (implicit through keystores/docstores)
-    pub fn ensure_workspace_exists(&self, project: &str, workspace: &str) ->  Result<(), E> {
+    pub fn ensure_workspace_exists(&self, project: &str, workspace: &str) -> Result<()> {
         self.logger.trace_fn("database", &format!("ensuring workspace exists: {}.{}", project, workspace));
         
         // First ensure project exists
@@ -35,22 +39,40 @@ impl Database {
         Ok(())
     }
     
-    /// Create a new workspace (stub - implicit through first keystore/docstore)
-    pub fn create_workspace(&self, _project: &str, _name: &str) ->  Result<(), E> {
-        // TODO: Implement explicit workspace creation if needed
-        todo!("Explicit workspace creation not yet implemented")
+    /// Create a workspace by giving it an empty MAIN keystore
+    ///
+    /// Workspaces have no table of their own; they exist while they hold a
+    /// keystore or doc store.
+    pub fn create_workspace(&self, project: &str, name: &str) -> Result<()> {
+        self.logger.trace_fn("database", &format!("creating workspace {}.{}", project, name));
+        
+        if self.list_workspaces(project)?.iter().any(|w| w == name) {
+            return Err(BookdbError::Database(format!("Workspace already exists: {}.{}", project, name)));
+        }
+        self.create_keystore(project, name, DEFAULT_KEYSTORE)
     }
     
-    /// Delete a workspace (stub - not implemented)
-    pub fn delete_workspace(&self, _project: &str, _name: &str) ->  Result<(), E> {
-        // TODO: Implement workspace deletion with cascade
-        todo!("Workspace deletion not yet implemented")
+    /// Delete a workspace: every keystore and the doc store, with their contents
+    ///
+    /// Returns false if nothing was there.
+    pub fn delete_workspace(&self, project: &str, name: &str) -> Result<bool> {
+        self.logger.trace_fn("database", &format!("deleting workspace {}.{}", project, name));
+        
+        let tx = self.connection.unchecked_transaction()?;
+        self.check_scope_writable(&tx, &format!("{}.{}", project, name))?;
+        let changes = tx.execute(sql::DELETE_WORKSPACE_KEYSTORES, params![project, name])?
+            + tx.execute(sql::DELETE_DOC_STORE, params![project, name])?;
+        if changes > 0 {
+            self.audit_tx(&tx, "rmworkspace", format!("{}.{}", project, name), None, None, None)?;
+            self.journal_irreversible_tx(&tx, "del workspace", &format!("{}@{}.{}", self.base_name, project, name))?;
+        }
+        tx.commit()?;
+        Ok(changes > 0)
     }
     
     /// Get workspace metadata (stub - not implemented)
     pub fn get_workspace_metadata(&self, _project: &str, _workspace: &str) -> Result<Option<WorkspaceMetadata>> {
-        // TODO: Implement workspace metadata if needed
-        todo!("Workspace metadata not yet implemented")
+        Err(BookdbError::NotImplemented("workspace metadata".to_string()))
     }
 }
 
@@ -62,3 +84,70 @@ pub struct WorkspaceMetadata {
     pub keystore_count: usize,
     pub docstore_count: usize,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bookdb::service::ctx::{Anchor, ChainMode, ResolvedContext};
+    use tempfile::TempDir;
+
+    fn create_test_context(anchor: Anchor, tail: &str) -> ResolvedContext {
+        ResolvedContext {
+            base: "test".to_string(),
+            project: "myapp".to_string(),
+            workspace: "prod".to_string(),
+            anchor,
+            tail: tail.to_string(),
+            prefix_mode: ChainMode::Persistent,
+        }
+    }
+
+    fn create_test_db() -> (Database, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::create_or_open(&temp_dir.path().join("test.sqlite3")).unwrap();
+        (db, temp_dir)
+    }
+
+    #[test]
+    fn test_create_namespaces() -> Result<()> {
+        let (db, _temp) = create_test_db();
+
+        db.create_project("myapp")?;
+        assert!(db.create_project("myapp").is_err());
+
+        db.create_workspace("myapp", "prod")?;
+        assert_eq!(db.list_keystores("myapp", "prod")?, vec![DEFAULT_KEYSTORE]);
+        assert!(db.create_workspace("myapp", "prod").is_err());
+
+        db.create_keystore("myapp", "prod", "secrets")?;
+        assert!(db.create_keystore("myapp", "prod", "secrets").is_err());
+
+        db.create_docstore("myapp", "docs")?;
+        assert_eq!(db.list_docstores("myapp", "docs")?.len(), 1);
+        assert_eq!(db.list_workspaces("myapp")?, vec!["docs", "prod"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_delete_namespaces_cascade() -> Result<()> {
+        let (db, _temp) = create_test_db();
+        db.set_variable("API_KEY", "x", &create_test_context(Anchor::Var, "secrets"))?;
+        db.set_doc_segment("README", "_root", "text/plain", b"hi", &create_test_context(Anchor::Doc, "README"))?;
+
+        assert!(db.delete_keystore("myapp", "prod", "secrets")?);
+        assert!(!db.delete_keystore("myapp", "prod", "secrets")?);
+        let vars: i64 = db.connection.query_row("SELECT COUNT(*) FROM vars", [], |row| row.get(0))?;
+        assert_eq!(vars, 0);
+
+        assert!(db.delete_workspace("myapp", "prod")?);
+        let segments: i64 = db.connection.query_row("SELECT COUNT(*) FROM doc_segments", [], |row| row.get(0))?;
+        assert_eq!(segments, 0);
+
+        assert!(db.delete_project("myapp")?);
+        assert!(!db.delete_project("myapp")?);
+        assert!(db.list_projects()?.is_empty());
+
+        Ok(())
+    }
+}
@@ -1,5 +1,6 @@
 
 include_sql_mod!(); // expands to: pub mod sql { ... } at this path
+pub use self::sql::*;
 
 // access as: crate::bookdb::service::db::sql::SQL_VERSION
 // and crate::bookdb::service::db::sql::RESOLVE_PROJECT_ID, etc.
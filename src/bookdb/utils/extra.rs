@@ -16,7 +16,7 @@ impl LsTableFormatter {
     }
     
     /// Format and display a table of items
-    pub fn display_table<E>(&mut self, headers: &[&str], rows: &[Vec<String>], title: Option<&str>) ->  Result<(), E> {
+    pub fn display_table(&mut self, headers: &[&str], rows: &[Vec<String>], title: Option<&str>) -> std::io::Result<()> {
         if let Some(title) = title {
             self.logger.banner(title, '=')?;
         }
@@ -33,10 +33,7 @@ impl LsTableFormatter {
         }
         
         // Convert to &[&[&str]] format required by simple_table
-        let table_refs: Vec<Vec<&str>> = table_data.iter()
-            .map(|row| row.iter().map(|s| s.as_str()).collect())
-            .collect();
-        let table_slice: Vec<&[&str]> = table_refs.iter()
+        let table_slice: Vec<&[&str]> = table_data.iter()
             .map(|row| row.as_slice())
             .collect();
         
@@ -48,7 +45,7 @@ impl LsTableFormatter {
     }
     
     /// Display variables in a nice table format
-    pub fn display_variables<E>(&mut self, variables: &[(String, String)], context: &str) ->  Result<(), E> {
+    pub fn display_variables(&mut self, variables: &[(String, String)], context: &str) -> std::io::Result<()> {
         let title = format!("Variables in {}", context);
         
         let rows: Vec<Vec<String>> = variables.iter()
@@ -59,7 +56,7 @@ impl LsTableFormatter {
     }
     
     /// Display projects, workspaces, etc. in table format
-    pub fn display_namespaces<E>(&mut self, items: &[String], namespace_type: &str, context: &str) ->  Result<(), E> {
+    pub fn display_namespaces(&mut self, items: &[String], namespace_type: &str, context: &str) -> std::io::Result<()> {
         let title = format!("{} in {}", namespace_type, context);
         
         let rows: Vec<Vec<String>> = items.iter()
@@ -97,7 +94,7 @@ impl OperationProgress {
         self.logger.trace_fn("progress", &format!("{}: starting {} items", self.operation_name, total));
     }
     
-    pub fn increment <E>(&mut self, item_name: &str) ->  Result<(), E> {
+    pub fn increment (&mut self, item_name: &str) -> std::io::Result<()> {
         self.current_item += 1;
         
         if let Some(total) = self.total_items {
@@ -111,7 +108,7 @@ impl OperationProgress {
         Ok(())
     }
     
-    pub fn complete(&mut self) ->  Result<(), E> {
+    pub fn complete(&mut self) -> std::io::Result<()> {
         self.logger.okay(&format!("{} completed successfully! Processed {} items.", 
             self.operation_name, self.current_item));
         Ok(())
@@ -125,7 +122,7 @@ pub struct DestructiveOpConfirm {
     logger: Stderr,
 }
 
-impl <E>DestructiveOpConfirm {
+impl DestructiveOpConfirm {
     pub fn new() -> Self {
         Self {
             logger: Stderr::new(),
@@ -133,7 +130,7 @@ impl <E>DestructiveOpConfirm {
     }
     
     /// Confirm deletion operations
-    pub fn confirm_delete(&mut self, item_type: &str, item_name: &str) -> Result<bool, E> {
+    pub fn confirm_delete(&mut self, item_type: &str, item_name: &str) -> std::io::Result<bool> {
         self.logger.warn(&format!("About to delete {} '{}'", item_type, item_name));
         self.logger.error("This operation cannot be undone!");
         
@@ -153,7 +150,7 @@ impl <E>DestructiveOpConfirm {
     }
     
     /// Confirm import/export operations that might overwrite data
-    pub fn confirm_overwrite(&mut self, operation: &str, target: &str) -> Result<bool, E> {
+    pub fn confirm_overwrite(&mut self, operation: &str, target: &str) -> std::io::Result<bool> {
         self.logger.warn(&format!("About to {} - this may overwrite existing data", operation));
         self.logger.info(&format!("Target: {}", target));
         
@@ -170,7 +167,7 @@ impl <E>DestructiveOpConfirm {
     }
     
     /// Confirm reset operations
-    pub fn confirm_reset(&mut self, scope: &str) -> Result<bool, E> {
+    pub fn confirm_reset(&mut self, scope: &str) -> std::io::Result<bool> {
         self.logger.error(&format!("About to reset {} - this will delete all data in scope!", scope));
         self.logger.error("This operation cannot be undone!");
         
@@ -14,7 +14,7 @@ fn version(){
         return;
     }
 
-    logo;
+    logo();
     // Dynamically create the version string
     let version_string = format!(
         "          CLI   v{:<8}",  CLI_VERSION
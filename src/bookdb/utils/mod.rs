@@ -1,5 +1,6 @@
 
 
+#[macro_use]
 pub mod macros;
 pub mod info;
 pub mod extra;
@@ -8,14 +9,16 @@ pub mod extra;
 // Phase 1.7 upgrade
 
 // Group validator modules under one cfg block
-#[cfg(feature = "context-chain-v1")]
+#[cfg(all(feature = "context-chain-v1", not(feature = "context-chain-v3")))]
 pub mod validator {
+    #[allow(non_snake_case)]
     pub mod valV1;
     pub use valV1 as active_validator;
 }
 
-#[cfg(feature = "context-chain-v3")]
+#[cfg(all(feature = "context-chain-v3", not(feature = "dev-both-versions")))]
 pub mod validator {
+    #[allow(non_snake_case)]
     pub mod valV3;
     pub use valV3 as active_validator;
 }
@@ -23,7 +26,9 @@ pub mod validator {
 // For development/testing with both versions
 #[cfg(feature = "dev-both-versions")]
 pub mod validator {
+    #[allow(non_snake_case)]
     pub mod valV1;
+    #[allow(non_snake_case)]
     pub mod valV3;
     // Default to V3 when both available
     pub use valV3 as active_validator;
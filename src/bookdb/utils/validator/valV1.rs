@@ -200,13 +200,13 @@ pub fn validate_and_expand_context_chain(
     
     // Step 7: Build canonical form
     let canonical_form = if is_fqcc {
-        format!("{}{}{}.{}.{}.{}", 
-            base, 
+        format!("{}{}@{}.{}.{}.{}", 
             match prefix_mode {
               ChainMode::Persistent => '@',
               ChainMode::Ephemeral => '%', 
               ChainMode::Action => '#',
             },
+            base, 
             project, 
             workspace, 
             match anchor {
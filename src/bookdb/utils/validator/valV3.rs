@@ -3,9 +3,12 @@
 // Clean, single-purpose functions for segment-based context chains
 
 use stderr::{Stderr, StderrConfig};
-use super::typesv3::{ContextChainV3, VarContextChain, DocContextChain, ContextType};
-use super::ctx_types::{Anchor, ChainMode};
+use crate::bookdb::service::ctx::types::typesV3::{
+    ContextChainV3, VarContextChain, DocContextChain, ContextType, TailSegment, parse_context_chain_v3,
+};
+use crate::bookdb::service::ctx::types::typesV1::Anchor;
 use crate::error::{Result, BookdbError};
+use std::ops::Deref;
 
 // ============================================================================
 // MAIN VALIDATOR FUNCTION
@@ -18,10 +21,10 @@ pub fn validate_and_create_v3(input: &str, fallback_base: &str) -> Result<V3Cont
     logger.trace_fn("v3_validator", &format!("validating context chain: '{}'", input));
     
     // Step 1: Parse into generic V3 chain
-    let generic_chain = parse_to_generic_v3(input, fallback_base)?;
+    let generic_chain = parse_to_generic_v3(input)?;
     
-    // Step 2: Specialize based on anchor type
-    let specialized = specialize_generic_chain(generic_chain)?;
+    // Step 2: Specialize based on anchor type (CDCC picks up the fallback base)
+    let specialized = specialize_generic_chain(generic_chain, fallback_base)?;
     
     logger.info(&format!("✅ V3 Context validated: {}", specialized.display_string()));
     Ok(specialized)
@@ -29,18 +32,20 @@ pub fn validate_and_create_v3(input: &str, fallback_base: &str) -> Result<V3Cont
 
 /// Upgrades a generic ContextChainV3 to the correct specialized type
 /// Helper for when you have a ContextChainV3 and want the specific variant
+/// The base is taken from the Base segment only; CDCC chains keep `base: None`
 pub fn upgrade_to_specialized(generic: &ContextChainV3) -> Result<V3ContextResult> {
+    let base = generic.base().map(str::to_string);
     match generic.chain_type {
         ContextType::Variable => {
-            let var_chain = create_var_context_from_generic(generic)?;
+            let var_chain = create_var_context_from_generic(generic, base)?;
             Ok(V3ContextResult::Variable(var_chain))
         }
         ContextType::Document => {
-            let doc_chain = create_doc_context_from_generic(generic)?;
+            let doc_chain = create_doc_context_from_generic(generic, base)?;
             Ok(V3ContextResult::Document(doc_chain))
         }
         ContextType::Mixed => {
-            Err(BookdbError::ContextParse("Mixed context types not yet supported".to_string()))
+            Err(BookdbError::ContextParse("Wildcard chains name several contexts and cannot be specialized".to_string()))
         }
     }
 }
@@ -87,216 +92,72 @@ impl V3ContextResult {
 // ============================================================================
 
 /// Parse string into generic ContextChainV3 without specialization
-fn parse_to_generic_v3(input: &str, fallback_base: &str) -> Result<ContextChainV3> {
-    // Basic validation
-    validate_input_string(input)?;
-    
-    // Parse prefix mode
-    let (prefix_mode, body) = parse_prefix_mode(input)?;
-    
-    // Parse base (FQCC vs CDCC)
-    let (base, rest, is_fqcc) = parse_base_component(body, fallback_base)?;
-    
-    // Parse main components
-    let components = parse_main_components(rest)?;
-    
-    // Create generic V3 chain
-    create_generic_v3_chain(base, prefix_mode, components, is_fqcc)
+fn parse_to_generic_v3(input: &str) -> Result<ContextChainV3> {
+    // Segment parser does prefix/base/component validation; SegmentError -> ContextParse
+    Ok(parse_context_chain_v3(input)?)
 }
 
 /// Determine context type and create specialized variant
-fn specialize_generic_chain(generic: ContextChainV3) -> Result<V3ContextResult> {
+fn specialize_generic_chain(generic: ContextChainV3, fallback_base: &str) -> Result<V3ContextResult> {
+    let base = Some(generic.base().unwrap_or(fallback_base).to_string());
     match generic.chain_type {
         ContextType::Variable => {
-            let var_chain = create_var_context_from_generic(&generic)?;
+            let var_chain = create_var_context_from_generic(&generic, base)?;
             Ok(V3ContextResult::Variable(var_chain))
         }
         ContextType::Document => {
-            let doc_chain = create_doc_context_from_generic(&generic)?;
+            let doc_chain = create_doc_context_from_generic(&generic, base)?;
             Ok(V3ContextResult::Document(doc_chain))
         }
         ContextType::Mixed => {
-            Err(BookdbError::ContextParse("Mixed context types not supported".to_string()))
+            Err(BookdbError::ContextParse("Wildcard chains name several contexts and cannot be specialized".to_string()))
         }
     }
 }
 
 // ============================================================================
-// VALIDATION HELPERS (Single Purpose Functions)
+// CREATION HELPERS (Single Purpose Functions)
 // ============================================================================
 
-/// Validate basic input string requirements
-fn validate_input_string(input: &str) ->  Result<(), E> {
-    if input.trim().is_empty() {
-        return Err(BookdbError::ContextParse("Empty context chain".to_string()));
-    }
-    Ok(())
-}
-
-/// Parse and validate prefix mode (@, %, #)
-fn parse_prefix_mode(input: &str) -> Result<(ChainMode, &str)> {
-    let input = input.trim();
-    match input.chars().next() {
-        Some('@') => Ok((ChainMode::Persistent, &input[1..])),
-        Some('%') => Ok((ChainMode::Ephemeral, &input[1..])),
-        Some('#') => Ok((ChainMode::Action, &input[1..])),
-        _ => Err(BookdbError::ContextParse(
-            "Context chain must start with @, %, or #".to_string()
-        )),
-    }
-}
-
-/// Parse base component for FQCC vs CDCC
-fn parse_base_component(body: &str, fallback_base: &str) -> Result<(Option<String>, &str, bool)> {
-    if body.contains('@') {
-        // FQCC format: base@rest
-        let parts: Vec<&str> = body.splitn(2, '@').collect();
-        if parts.len() != 2 {
-            return Err(BookdbError::ContextParse("Invalid FQCC format".to_string()));
-        }
-        
-        let base_part = parts[0];
-        if base_part.is_empty() {
-            return Err(BookdbError::ContextParse("Empty base name".to_string()));
-        }
-        
-        validate_component_name("base", base_part)?;
-        Ok((Some(base_part.to_string()), parts[1], true))
-    } else {
-        // CDCC format: use fallback base
-        Ok((Some(fallback_base.to_string()), body, false))
-    }
-}
-
-/// Parse main components: project.workspace.anchor.tail
-fn parse_main_components(rest: &str) -> Result<ParsedComponents> {
-    let parts: Vec<&str> = rest.split('.').collect();
-    if parts.len() != 4 {
-        return Err(BookdbError::ContextParse(
-            format!("Expected 4 components (project.workspace.anchor.tail), found {}", parts.len())
-        ));
-    }
-    
-    let project = parts[0];
-    let workspace = parts[1];
-    let anchor_str = parts[2];
-    let tail = parts[3];
-    
-    // Validate each component
-    validate_component_name("project", project)?;
-    validate_component_name("workspace", workspace)?;
-    validate_component_name("tail", tail)?;
-    
-    // Parse anchor
-    let anchor = parse_anchor(anchor_str)?;
-    
-    Ok(ParsedComponents {
-        project: project.to_string(),
-        workspace: workspace.to_string(),
-        anchor,
-        tail: tail.to_string(),
-    })
+/// Pull project and workspace namespaces out of a generic chain
+fn extract_namespaces(generic: &ContextChainV3) -> Result<(String, String)> {
+    let project = generic.project()
+        .ok_or_else(|| missing_segment("project"))?;
+    let workspace = generic.workspace()
+        .ok_or_else(|| missing_segment("workspace"))?;
+    Ok((project.to_string(), workspace.to_string()))
 }
 
-/// Parse anchor type (var/doc)
-fn parse_anchor(anchor_str: &str) -> Result<Anchor> {
-    match anchor_str.to_lowercase().as_str() {
-        "var" => Ok(Anchor::Var),
-        "doc" => Ok(Anchor::Doc),
-        _ => Err(BookdbError::ContextParse(
-            format!("Invalid anchor '{}', must be 'var' or 'doc'", anchor_str)
-        )),
-    }
+fn missing_segment(name: &str) -> BookdbError {
+    BookdbError::ContextParse(format!("Missing required segment: {}", name))
 }
 
-/// Validate component name (alphanumeric, underscore, hyphen only)
-fn validate_component_name(component_type: &str, name: &str) ->  Result<(), E> {
-    if name.is_empty() {
-        return Err(BookdbError::ContextParse(
-            format!("{} cannot be empty", component_type)
-        ));
-    }
-    
-    for ch in name.chars() {
-        if !ch.is_alphanumeric() && ch != '_' && ch != '-' {
-            return Err(BookdbError::ContextParse(
-                format!("Invalid character '{}' in {}", ch, component_type)
-            ));
+/// Create VarContextChain from generic ContextChainV3
+fn create_var_context_from_generic(generic: &ContextChainV3, base: Option<String>) -> Result<VarContextChain> {
+    let (project, workspace) = extract_namespaces(generic)?;
+    let keystore = match generic.tail() {
+        Some(TailSegment::Variable(tail)) => tail.keystore.clone(),
+        Some(TailSegment::Document(_)) => {
+            return Err(BookdbError::ContextParse("Document tail in a variable context".to_string()))
         }
-    }
-    
-    Ok(())
-}
-
-// ============================================================================
-// CREATION HELPERS (Single Purpose Functions)
-// ============================================================================
-
-/// Create generic ContextChainV3 from parsed components
-fn create_generic_v3_chain(
-    base: Option<String>,
-    prefix_mode: ChainMode,
-    components: ParsedComponents,
-    is_fqcc: bool,
-) -> Result<ContextChainV3> {
-    let chain_type = match components.anchor {
-        Anchor::Var => ContextType::Variable,
-        Anchor::Doc => ContextType::Document,
+        None => return Err(missing_segment("tail")),
     };
-    
-    // Create segments (stub implementation for now)
-    let segments = vec![]; // TODO: Implement segment creation
-    
-    Ok(ContextChainV3 {
-        segments,
-        chain_type,
-        is_fqcc,
-    })
-}
 
-/// Create VarContextChain from generic ContextChainV3
-fn create_var_context_from_generic(generic: &ContextChainV3) -> Result<VarContextChain> {
-    // Extract fields from segments (stub implementation)
-    // TODO: Implement proper segment extraction
-    
-    Ok(VarContextChain {
-        inner: generic.clone(),
-        base: None, // TODO: Extract from segments
-        prefix_mode: ChainMode::Persistent, // TODO: Extract from segments
-        project: "TODO".to_string(), // TODO: Extract from segments
-        workspace: "TODO".to_string(), // TODO: Extract from segments
-        keystore: "TODO".to_string(), // TODO: Extract from segments
-        is_fqcc: generic.is_fqcc,
-    })
+    Ok(VarContextChain::new(generic.clone(), base, project, workspace, keystore))
 }
 
 /// Create DocContextChain from generic ContextChainV3
-fn create_doc_context_from_generic(generic: &ContextChainV3) -> Result<DocContextChain> {
-    // Extract fields from segments (stub implementation)
-    // TODO: Implement proper segment extraction
-    
-    Ok(DocContextChain {
-        inner: generic.clone(),
-        base: None, // TODO: Extract from segments
-        prefix_mode: ChainMode::Persistent, // TODO: Extract from segments
-        project: "TODO".to_string(), // TODO: Extract from segments
-        workspace: "TODO".to_string(), // TODO: Extract from segments
-        document_key: "TODO".to_string(), // TODO: Extract from segments
-        is_fqcc: generic.is_fqcc,
-    })
-}
-
-// ============================================================================
-// HELPER TYPES
-// ============================================================================
+fn create_doc_context_from_generic(generic: &ContextChainV3, base: Option<String>) -> Result<DocContextChain> {
+    let (project, workspace) = extract_namespaces(generic)?;
+    let (document_key, segment_path) = match generic.tail() {
+        Some(TailSegment::Document(tail)) => (tail.document_key.clone(), tail.segment_path.clone()),
+        Some(TailSegment::Variable(_)) => {
+            return Err(BookdbError::ContextParse("Variable tail in a document context".to_string()))
+        }
+        None => return Err(missing_segment("tail")),
+    };
 
-/// Intermediate structure for parsed components
-#[derive(Debug)]
-struct ParsedComponents {
-    project: String,
-    workspace: String,
-    anchor: Anchor,
-    tail: String,
+    Ok(DocContextChain::new(generic.clone(), base, project, workspace, document_key, segment_path))
 }
 
 // ============================================================================
@@ -314,11 +175,7 @@ VALIDATOR DESIGN PRINCIPLES:
 2. Clean Call Chain:
    validate_and_create_v3() -> 
      parse_to_generic_v3() -> 
-       validate_input_string()
-       parse_prefix_mode()
-       parse_base_component()
-       parse_main_components()
-       create_generic_v3_chain()
+       parse_context_chain_v3()   (segment.rs: prefix, base, components, validate_segments)
      specialize_generic_chain() ->
        create_var_context_from_generic() OR
        create_doc_context_from_generic()
@@ -327,10 +184,9 @@ VALIDATOR DESIGN PRINCIPLES:
    - upgrade_to_specialized() for existing ContextChainV3
    - Individual validation functions can be used separately
 
-4. Stub Implementation:
-   - Segment creation/extraction is stubbed
-   - Ready for Phase 1.7 implementation
-   - Structure supports future segment system
+4. Segment Extraction:
+   - Fields are read from the Prefix/Base/Namespace/Tail segments
+   - CDCC chains carry no Base segment; validate_and_create_v3 fills in the fallback
 
 USAGE EXAMPLES:
 ```rust
@@ -356,3 +212,388 @@ process_context(&var_chain); // ✅ Works automatically!
 
 
 */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bookdb::service::ctx::types::typesV1::{ChainMode, ContextChain};
+
+    // ========================================================================
+    // MAIN VALIDATOR TESTS
+    // ========================================================================
+
+    #[test]
+    fn test_validate_and_create_v3_variable_context() -> Result<()> {
+        let result = validate_and_create_v3("@work@proj.workspace.var.keystore", "home")?;
+        
+        match result {
+            V3ContextResult::Variable(var_chain) => {
+                assert_eq!(var_chain.anchor(), Anchor::Var);
+                assert_eq!(var_chain.prefix_mode, ChainMode::Persistent);
+                assert!(var_chain.is_fqcc);
+                // Test Deref trait
+                let generic: &ContextChainV3 = var_chain.deref();
+                assert_eq!(generic.chain_type, ContextType::Variable);
+            }
+            _ => panic!("Expected Variable result, got Document"),
+        }
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_and_create_v3_document_context() -> Result<()> {
+        let result = validate_and_create_v3("@base@proj.workspace.doc.readme", "home")?;
+        
+        match result {
+            V3ContextResult::Document(doc_chain) => {
+                assert_eq!(doc_chain.anchor(), Anchor::Doc);
+                assert_eq!(doc_chain.prefix_mode, ChainMode::Persistent);
+                assert!(doc_chain.is_fqcc);
+                // Test Deref trait
+                let generic: &ContextChainV3 = doc_chain.deref();
+                assert_eq!(generic.chain_type, ContextType::Document);
+            }
+            _ => panic!("Expected Document result, got Variable"),
+        }
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_upgrade_to_specialized() -> Result<()> {
+        // Generic chain straight from the segment parser
+        let generic = parse_context_chain_v3("@work@proj.workspace.var.keystore")?;
+        
+        let result = upgrade_to_specialized(&generic)?;
+        match result {
+            V3ContextResult::Variable(var_chain) => {
+                assert_eq!(var_chain.base, Some("work".to_string()));
+                assert_eq!(var_chain.keystore, "keystore");
+            }
+            _ => panic!("Expected Variable specialization"),
+        }
+        
+        // Segment-less chains can no longer be specialized
+        let empty = ContextChainV3 {
+            segments: vec![],
+            chain_type: ContextType::Variable,
+            is_fqcc: true,
+        };
+        assert!(upgrade_to_specialized(&empty).is_err());
+        
+        Ok(())
+    }
+
+    // ========================================================================
+    // PREFIX MODE PARSING TESTS
+    // ========================================================================
+
+    #[test]
+    fn test_persistent_prefix_mode() -> Result<()> {
+        let result = validate_and_create_v3("@proj.workspace.var.keystore", "home")?;
+        assert_eq!(result.as_generic().chain_type, ContextType::Variable);
+        Ok(())
+    }
+
+    #[test]
+    fn test_ephemeral_prefix_mode() -> Result<()> {
+        let result = validate_and_create_v3("%proj.workspace.var.keystore", "home")?;
+        match result {
+            V3ContextResult::Variable(var_chain) => {
+                assert_eq!(var_chain.prefix_mode, ChainMode::Ephemeral);
+            }
+            _ => panic!("Expected Variable result"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_action_prefix_mode() -> Result<()> {
+        let result = validate_and_create_v3("#proj.workspace.var.keystore", "home")?;
+        match result {
+            V3ContextResult::Variable(var_chain) => {
+                assert_eq!(var_chain.prefix_mode, ChainMode::Action);
+            }
+            _ => panic!("Expected Variable result"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_prefix_mode() {
+        let result = validate_and_create_v3("proj.workspace.var.keystore", "home");
+        assert!(result.is_err());
+        
+        if let Err(BookdbError::ContextParse(msg)) = result {
+            assert!(msg.contains("must start with @, %, or #"));
+        } else {
+            panic!("Expected ContextParse error");
+        }
+    }
+
+    // ========================================================================
+    // BASE COMPONENT PARSING TESTS (FQCC vs CDCC)
+    // ========================================================================
+
+    #[test]
+    fn test_fqcc_base_parsing() -> Result<()> {
+        let result = validate_and_create_v3("@work@proj.workspace.var.keystore", "home")?;
+        match result {
+            V3ContextResult::Variable(var_chain) => {
+                assert!(var_chain.is_fqcc);
+                assert_eq!(var_chain.base, Some("work".to_string()));
+            }
+            _ => panic!("Expected Variable result"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_cdcc_base_fallback() -> Result<()> {
+        let result = validate_and_create_v3("@proj.workspace.var.keystore", "fallback_base")?;
+        match result {
+            V3ContextResult::Variable(var_chain) => {
+                assert!(!var_chain.is_fqcc);
+                assert_eq!(var_chain.base, Some("fallback_base".to_string()));
+            }
+            _ => panic!("Expected Variable result"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_empty_base_name_error() {
+        let result = validate_and_create_v3("@@proj.workspace.var.keystore", "home");
+        assert!(result.is_err());
+        
+        if let Err(BookdbError::ContextParse(msg)) = result {
+            assert!(msg.contains("Empty base name"));
+        } else {
+            panic!("Expected ContextParse error for empty base");
+        }
+    }
+
+    // ========================================================================
+    // COMPONENT VALIDATION TESTS
+    // ========================================================================
+
+    #[test]
+    fn test_invalid_component_count() {
+        // Too few components
+        let result = validate_and_create_v3("@proj.workspace.var", "home");
+        assert!(result.is_err());
+        
+        // Too many components
+        let result = validate_and_create_v3("@proj.workspace.var.keystore.extra", "home");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_anchor_case_insensitive() -> Result<()> {
+        let var_upper = validate_and_create_v3("@proj.workspace.VAR.keystore", "home")?;
+        let var_lower = validate_and_create_v3("@proj.workspace.var.keystore", "home")?;
+        
+        assert_eq!(var_upper.anchor(), var_lower.anchor());
+        assert_eq!(var_upper.anchor(), Anchor::Var);
+        
+        let doc_upper = validate_and_create_v3("@proj.workspace.DOC.readme", "home")?;
+        let doc_lower = validate_and_create_v3("@proj.workspace.doc.readme", "home")?;
+        
+        assert_eq!(doc_upper.anchor(), doc_lower.anchor());
+        assert_eq!(doc_upper.anchor(), Anchor::Doc);
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_anchor() {
+        let result = validate_and_create_v3("@proj.workspace.invalid.keystore", "home");
+        assert!(result.is_err());
+        
+        if let Err(BookdbError::ContextParse(msg)) = result {
+            assert!(msg.contains("Invalid anchor"));
+        } else {
+            panic!("Expected ContextParse error for invalid anchor");
+        }
+    }
+
+    // ========================================================================
+    // DEREF TRAIT COERCION TESTS
+    // ========================================================================
+
+    #[test]
+    fn test_var_chain_deref_coercion() -> Result<()> {
+        let result = validate_and_create_v3("@proj.workspace.var.keystore", "home")?;
+        
+        if let V3ContextResult::Variable(var_chain) = result {
+            // Test that we can use VarContextChain where ContextChainV3 is expected
+            fn accepts_generic_context(ctx: &ContextChainV3) -> ContextType {
+                ctx.chain_type
+            }
+            
+            // This should work via Deref trait
+            let context_type = accepts_generic_context(&var_chain);
+            assert_eq!(context_type, ContextType::Variable);
+        } else {
+            panic!("Expected Variable result");
+        }
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_doc_chain_deref_coercion() -> Result<()> {
+        let result = validate_and_create_v3("@proj.workspace.doc.readme", "home")?;
+        
+        if let V3ContextResult::Document(doc_chain) = result {
+            // Test that we can use DocContextChain where ContextChainV3 is expected
+            fn accepts_generic_context(ctx: &ContextChainV3) -> bool {
+                ctx.is_fqcc
+            }
+            
+            // This should work via Deref trait
+            let is_fqcc = accepts_generic_context(&doc_chain);
+            assert!(!is_fqcc); // CDCC in this case
+        } else {
+            panic!("Expected Document result");
+        }
+        
+        Ok(())
+    }
+
+    // ========================================================================
+    // ERROR HANDLING TESTS
+    // ========================================================================
+
+    #[test]
+    fn test_empty_input_error() {
+        let result = validate_and_create_v3("", "home");
+        assert!(result.is_err());
+        
+        let result = validate_and_create_v3("   ", "home");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_detailed_error_messages() {
+        // Test that errors provide helpful context
+        let result = validate_and_create_v3("invalid_format", "home");
+        assert!(result.is_err());
+        
+        if let Err(BookdbError::ContextParse(msg)) = result {
+            assert!(!msg.is_empty());
+            assert!(msg.len() > 10); // Should be descriptive
+        } else {
+            panic!("Expected ContextParse error");
+        }
+    }
+
+    // ========================================================================
+    // V3CONTEXTRESULT TESTS
+    // ========================================================================
+
+    #[test]
+    fn test_v3_context_result_display() -> Result<()> {
+        let var_result = validate_and_create_v3("@proj.workspace.var.keystore", "home")?;
+        let doc_result = validate_and_create_v3("@proj.workspace.doc.readme", "home")?;
+        
+        // Test display strings are not empty
+        assert!(!var_result.display_string().is_empty());
+        assert!(!doc_result.display_string().is_empty());
+        
+        // Test anchor accessors
+        assert_eq!(var_result.anchor(), Anchor::Var);
+        assert_eq!(doc_result.anchor(), Anchor::Doc);
+        
+        Ok(())
+    }
+
+    #[test]
+    fn test_as_generic_accessor() -> Result<()> {
+        let result = validate_and_create_v3("@proj.workspace.var.keystore", "home")?;
+        
+        // Test that as_generic() returns the underlying ContextChainV3
+        let generic = result.as_generic();
+        assert_eq!(generic.chain_type, ContextType::Variable);
+        
+        Ok(())
+    }
+
+    // ========================================================================
+    // INTEGRATION TESTS WITH OTHER COMPONENTS
+    // ========================================================================
+
+    #[test]
+    fn test_v3_with_resolver_compatibility() -> Result<()> {
+        // Test that V3 types work with existing resolver logic
+        let result = validate_and_create_v3("@proj.workspace.var.keystore", "home")?;
+        
+        match result {
+            V3ContextResult::Variable(var_chain) => {
+                assert_eq!(var_chain.project, "proj");
+                assert_eq!(var_chain.workspace, "workspace");
+                assert_eq!(var_chain.keystore, "keystore");
+                
+                // Round-trips into the legacy chain the resolver consumes
+                let legacy: ContextChain = var_chain.into_inner().into();
+                assert_eq!(legacy.project, "proj");
+                assert_eq!(legacy.tail, "keystore");
+                assert!(!legacy.is_fqcc);
+            }
+            _ => panic!("Expected Variable result"),
+        }
+        
+        Ok(())
+    }
+
+    // ========================================================================
+    // PERFORMANCE TESTS
+    // ========================================================================
+
+    #[test]
+    fn test_parsing_performance() {
+        use std::time::Instant;
+        
+        let start = Instant::now();
+        
+        // Parse 1000 context chains
+        for i in 0..1000 {
+            let input = format!("@proj{}.workspace.var.keystore", i);
+            let _ = validate_and_create_v3(&input, "home");
+        }
+        
+        let duration = start.elapsed();
+        println!("1000 V3 context parses took: {:?}", duration);
+        
+        // Should be reasonably fast - this is critical path
+        assert!(duration.as_millis() < 500, "V3 parsing too slow: {:?}", duration);
+    }
+
+    // ========================================================================
+    // PROPERTY-BASED TEST HELPERS
+    // ========================================================================
+
+    #[test]
+    fn test_roundtrip_property() -> Result<()> {
+        // Test that valid inputs can be parsed and displayed consistently
+        let inputs = vec![
+            "@proj.workspace.var.keystore",
+            "%temp.test.doc.readme", 
+            "#quick.action.var.secret",
+            "@base@proj.workspace.var.config",
+        ];
+        
+        for input in inputs {
+            let result = validate_and_create_v3(input, "home")?;
+            let display = result.display_string();
+            
+            // The display should be a valid context chain
+            // (Though format might differ slightly)
+            assert!(!display.is_empty());
+            assert!(display.contains('.'));
+        }
+        
+        Ok(())
+    }
+}
@@ -1,12 +1,17 @@
 // src/main.rs — BookDB with ODX initialization
 #![allow(dead_code)]
 #![allow(unused_imports)]
+// Handlers take their dependencies explicitly; export filters are plain tuples
+#![allow(clippy::too_many_arguments, clippy::type_complexity)]
 
 mod bookdb; // this points at src/bookdb/mod.rs
 
+// Crate-root shorthands used throughout the tree (`crate::error`, `crate::sql`, ...)
+use bookdb::app::sup::error;
+use bookdb::service::db::sql;
+use bookdb::app::ctrl::cli;
+use bookdb::service::ctx;
+
 fn main() {
-    println!("BookDB starting...");
-    
-    // Test basic module access
-    println!("BookDB packages loaded successfully");
+    std::process::exit(bookdb::app::ctrl::dispatch::run());
 }